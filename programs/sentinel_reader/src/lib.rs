@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use sol_sentinel::cpi::accounts::ReadSentiment;
+use sol_sentinel::program::SolSentinel;
+
+declare_id!("8n6Q4o28Mr8jkiop6pFHML3EEb47TfPWzgdyFWHRnrSk");
+
+/// A minimal SolSentinel consumer: reads a feed over CPI the way an
+/// integrating protocol would, so tests can exercise the CPI read path.
+#[program]
+pub mod sentinel_reader {
+    use super::*;
+
+    /// Read `symbol` through `get_sentiment` and hand the reading back as
+    /// this program's own return data.
+    pub fn read_sentiment(ctx: Context<ReadFeed>, symbol: String, max_age: i64) -> Result<Reading> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.sentinel_program.to_account_info(),
+            ReadSentiment {
+                sentinel: ctx.accounts.sentinel.to_account_info(),
+                sentiment: ctx.accounts.sentiment.to_account_info(),
+                symbol_config: ctx.accounts.symbol_config.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+                alias: ctx.accounts.alias.to_account_info(),
+            },
+        );
+        let view = sol_sentinel::cpi::get_sentiment(cpi_ctx, symbol, max_age)?.get();
+        msg!("score={} confidence={} stale={}", view.score, view.confidence, view.stale);
+        Ok(Reading {
+            score: view.score,
+            confidence: view.confidence,
+            timestamp: view.timestamp,
+            stale: view.stale,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Reading {
+    pub score: i8,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub stale: bool,
+}
+
+#[derive(Accounts)]
+pub struct ReadFeed<'info> {
+    /// CHECK: validated by SolSentinel's `get_sentiment`
+    pub sentinel: UncheckedAccount<'info>,
+
+    /// CHECK: validated by SolSentinel's `get_sentiment`
+    pub sentiment: UncheckedAccount<'info>,
+
+    /// CHECK: validated by SolSentinel's `get_sentiment`
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by SolSentinel's `get_sentiment`
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: validated by SolSentinel's `get_sentiment`
+    pub alias: UncheckedAccount<'info>,
+
+    pub sentinel_program: Program<'info, SolSentinel>,
+}
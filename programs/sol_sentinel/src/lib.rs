@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
 
 declare_id!("HFkhRjLJVwgm6UHfvSqkzJhaQE8GyzjNet8SUNAGjVgm");
 
@@ -8,16 +9,154 @@ declare_id!("HFkhRjLJVwgm6UHfvSqkzJhaQE8GyzjNet8SUNAGjVgm");
 
 pub const MAX_SYMBOL_LEN: usize = 10;
 pub const MAX_USERNAME_LEN: usize = 20;
+pub const MAX_NOTE_LEN: usize = 64;
+pub const MAX_METADATA_LEN: usize = 32;
+pub const MAX_READERS: usize = 4; // SymbolConfig::reader_allowlist
 pub const MAX_OPERATORS: usize = 5;
-pub const MAX_HISTORY: usize = 24; // 24 historical snapshots per token
+pub const MAX_HISTORY: usize = 24; // default snapshots per token
+pub const MAX_HISTORY_CAP: usize = 96; // largest capacity record_history accepts
+pub const MAX_HISTORY_QUERY: u16 = 12; // snapshots the history decode views emit per call
 pub const MAX_BATCH_SIZE: usize = 10;
+pub const MAX_SUBSCRIPTIONS: usize = 20; // symbols per SubscriptionList
+pub const MAX_AUTHORITIES: usize = 5; // scoped authorities besides the root
+pub const MAX_DELTA_HISTORY: usize = 48; // delta-encoded snapshots per token
+pub const MAX_DELTA_KEYFRAMES: usize = 8;
+pub const DELTA_KEYFRAME_INTERVAL: u16 = 8; // full keyframe at least every N snapshots
+pub const MAX_COMPACT_HISTORY_BYTES: usize = 512; // encoded deltas per CompactHistory
+pub const MAX_AUDIT_ENTRIES: usize = 32;
+pub const MAX_PROPOSAL_VOTERS: usize = 32;
+pub const MAX_APPROVERS: usize = MAX_OPERATORS + 1; // operators plus the root authority
+pub const MAX_CONTRIBUTORS: usize = MAX_OPERATORS + 1; // distinct sources per contribution window
+pub const PENDING_UPDATE_TTL: i64 = 3_600; // seconds a proposed update stays open
+pub const PREDICTION_SETTLE_WINDOW: i64 = 3_600; // seconds a matured Prediction may be settled in
+pub const DEFAULT_UNSTAKE_DELAY: i64 = 7 * 86_400;
+pub const LEADERBOARD_SIZE: usize = 10;
+pub const SKILLED_REPUTATION: u16 = 200; // ReputationTier thresholds
+pub const EXPERT_REPUTATION: u16 = 400;
+pub const ORACLE_REPUTATION: u16 = 800;
+pub const ORACLE_MIN_ACCURACY: u8 = 70; // percent of predictions correct
+pub const DISPUTE_REPUTATION_DELTA: u16 = 10; // disputer's gain if upheld, loss if rejected
+pub const VOLUME_FP_SHIFT: u32 = 8;  // volume_ema / volume_stddev fixed-point fraction bits
+pub const VOLUME_EMA_SHIFT: u32 = 3; // EMA smoothing factor 1/8
+pub const MAX_DISPLAY_DECIMALS: u8 = 4;
+pub const MAX_WRITE_FEE: u64 = 1_000_000_000; // SymbolConfig::write_fee ceiling, 1 SOL
+pub const MAX_SYMBOL_COOLDOWN: i64 = 86_400; // set_symbol_cooldown ceiling, one day
+pub const SCALE_FACTOR_ONE: i16 = 100; // OperatorEntry::scale_factor fixed-point 1.0
+const TRANSACTION_LEVEL_STACK_HEIGHT: usize = 1;
+pub const CONFIG_SCHEMA_VERSION: u8 = 18; // bump whenever SentinelConfigView's layout changes
+pub const ALL_HOURS: u32 = (1 << 24) - 1; // SymbolConfig::active_hours, one bit per UTC hour
+
+// Admin scopes for AuthorityEntry::scope_flags (the root authority holds all)
+pub const SCOPE_PAUSE: u16 = 1 << 0;
+pub const SCOPE_OPERATORS: u16 = 1 << 1;
+pub const SCOPE_FEES: u16 = 1 << 2;
+pub const SCOPE_TRANSFER: u16 = 1 << 3;
+pub const SCOPE_CONFIG: u16 = 1 << 4;
+
+// Sentinel::pause_flags bits; a set bit rejects the matching instructions
+pub const PAUSE_STORE: u8 = 1 << 0;
+pub const PAUSE_UPDATE: u8 = 1 << 1;
+pub const PAUSE_VOTE: u8 = 1 << 2;
+pub const PAUSE_SUBSCRIBE: u8 = 1 << 3;
+pub const PAUSE_WRITES: u8 = PAUSE_STORE | PAUSE_UPDATE; // oracle feed only
+pub const PAUSE_ALL: u8 = PAUSE_WRITES | PAUSE_VOTE | PAUSE_SUBSCRIBE;
+
+// OperatorEntry::perms bits; each operator-gated instruction needs its bits
+pub const CAN_STORE: u8 = 1 << 0;
+pub const CAN_UPDATE: u8 = 1 << 1;
+pub const CAN_HISTORY: u8 = 1 << 2;
+pub const CAN_BATCH: u8 = 1 << 3;
+pub const CAN_ALL: u8 = CAN_STORE | CAN_UPDATE | CAN_HISTORY | CAN_BATCH;
+
+// AuditEntry::action_code values
+pub const AUDIT_TRANSFER_AUTHORITY: u8 = 1;
+pub const AUDIT_SET_SCOPED_AUTHORITY: u8 = 2;
+pub const AUDIT_REMOVE_SCOPED_AUTHORITY: u8 = 3;
+pub const AUDIT_SET_PAUSED: u8 = 4;
+pub const AUDIT_SET_OPERATORS_ENABLED: u8 = 5;
+pub const AUDIT_ADD_OPERATOR: u8 = 6;
+pub const AUDIT_REMOVE_OPERATOR: u8 = 7;
+pub const AUDIT_SET_OPERATOR_MAX_CONFIDENCE: u8 = 8;
+pub const AUDIT_SET_CONFIG: u8 = 9;
+pub const AUDIT_SET_AUDIT_ENABLED: u8 = 10;
+pub const AUDIT_SET_OPERATOR_SCALE_FACTOR: u8 = 11;
+pub const AUDIT_NOMINATE_AUTHORITY: u8 = 12;
+pub const AUDIT_CANCEL_AUTHORITY_TRANSFER: u8 = 13;
+pub const AUDIT_GRANT_SYMBOL_OPERATOR: u8 = 14;
+pub const AUDIT_REVOKE_SYMBOL_OPERATOR: u8 = 15;
+pub const AUDIT_SET_OPERATOR_PERMS: u8 = 16;
+pub const AUDIT_SLASH_OPERATOR: u8 = 17;
+pub const AUDIT_INIT_LEADERBOARD: u8 = 18;
+pub const AUDIT_REGISTER_SYMBOL: u8 = 19;
+pub const AUDIT_ALIAS_SYMBOL: u8 = 20;
+pub const AUDIT_CLOSE_ACCOUNT: u8 = 21;
+pub const AUDIT_REPAIR_HISTORY: u8 = 22;
+pub const AUDIT_MIGRATE_SENTINEL: u8 = 23;
+pub const AUDIT_PROPOSE_OPERATOR: u8 = 24;
+pub const AUDIT_JUDGE_PREDICTION: u8 = 25;
+pub const AUDIT_RESOLVE_DISPUTE: u8 = 26;
+
+// AlertTriggered::reason bits
+pub const ALERT_REASON_SCORE: u8 = 1;
+pub const ALERT_REASON_VOLUME: u8 = 2;
+
+pub const SLOT_HASHES_ID: Pubkey = pubkey!("SysvarS1otHashes111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey = pubkey!("Sysvar1nstructions1111111111111111111111111");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+pub const TOKEN_ACCOUNT_FROZEN: u8 = 2;
+
+// Pyth (legacy) price account layout
+pub const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+pub const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+pub const PYTH_STATUS_TRADING: u32 = 1; // aggregate status; anything else has no usable price
+// Pyth oracle program on devnet; mainnet's is FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+// Sentinel before the operator stake policy: the full layout without
+// min_operator_stake and unstake_delay
+pub const SENTINEL_V3_LEN: usize = Sentinel::LEN - 8 - 8;
+
+// Sentinel as created by the slim v2 program: discriminator, authority,
+// total_updates, bump
+pub const SENTINEL_V2_LEN: usize = 8 + 32 + 8 + 1;
+
+// Sentinel before pause_flags and operator entries: discriminator, authority,
+// total_updates, paused bool, MAX_OPERATORS bare operator keys, bump
+pub const SENTINEL_V1_LEN: usize = 8 + 32 + 8 + 1 + 4 + (32 * MAX_OPERATORS) + 1;
+
+// SentimentHistory before its capacity field: discriminator, symbol, head,
+// count, MAX_HISTORY snapshots, bump
+pub const HISTORY_V1_LEN: usize = SentimentHistory::LEN - 2;
 
 pub const SENTINEL_SEED: &[u8] = b"sentinel";
 pub const SENTIMENT_SEED: &[u8] = b"sentiment";
 pub const HISTORY_SEED: &[u8] = b"history";
+pub const DELTA_HISTORY_SEED: &[u8] = b"delta_history";
+pub const COMPACT_HISTORY_SEED: &[u8] = b"compact_history";
 pub const USER_PROFILE_SEED: &[u8] = b"user_profile";
 pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
 pub const VOTE_SEED: &[u8] = b"vote";
+pub const TOMBSTONE_SEED: &[u8] = b"rep_tombstone";
+pub const OPERATOR_DISPUTE_SEED: &[u8] = b"op_dispute";
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+pub const SYMBOL_CONFIG_SEED: &[u8] = b"symbol_config";
+pub const VOTE_AGG_SEED: &[u8] = b"vote_agg";
+pub const OPERATOR_PROPOSAL_SEED: &[u8] = b"op_proposal";
+pub const OPERATOR_SCOPE_SEED: &[u8] = b"op_scope";
+pub const PREDICTION_SEED: &[u8] = b"prediction";
+pub const PENDING_UPDATE_SEED: &[u8] = b"pending_update";
+pub const SYMBOL_REGISTRY_SEED: &[u8] = b"symbol_registry";
+pub const FOLLOW_SEED: &[u8] = b"follow";
+pub const OPERATOR_STATS_SEED: &[u8] = b"op_stats";
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const SUB_LIST_SEED: &[u8] = b"sub_list";
+pub const OPERATOR_STAKE_SEED: &[u8] = b"op_stake";
+pub const SYMBOL_ALIAS_SEED: &[u8] = b"symbol_alias";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const USERNAME_SEED: &[u8] = b"username";
 
 #[program]
 pub mod sol_sentinel {
@@ -30,804 +169,9757 @@ pub mod sol_sentinel {
         let sentinel = &mut ctx.accounts.sentinel;
         sentinel.authority = ctx.accounts.authority.key();
         sentinel.total_updates = 0;
-        sentinel.paused = false;
-        sentinel.operators = Vec::new();
+        set_default_settings(sentinel);
+        sentinel.initialized_at = Clock::get()?.unix_timestamp;
+        sentinel.last_update_ts = Clock::get()?.unix_timestamp;
         sentinel.bump = ctx.bumps.sentinel;
         msg!("SolSentinel initialized");
         Ok(())
     }
 
-    /// Transfer authority to a new admin.
+    /// Grow a sentinel created by the slim v2 program, or by the original
+    /// program with a `paused` flag and bare operator keys, to the full
+    /// layout, with the authority funding the extra rent. The authority and
+    /// update count carry over; from the original layout so do its operators
+    /// (with every permission) and a set `paused`, which becomes `PAUSE_ALL`.
+    /// Every other setting takes its `initialize` default. A sentinel from
+    /// before the operator stake policy keeps every setting and gets the
+    /// policy's defaults. Only those sizes are accepted, which makes this a
+    /// one-shot.
+    pub fn migrate_sentinel(ctx: Context<MigrateSentinel>) -> Result<()> {
+        let info = ctx.accounts.sentinel.to_account_info();
+        require!(info.owner == ctx.program_id, SentinelError::InvalidAccount);
+        let old_len = info.data_len();
+        require!(old_len != Sentinel::LEN, SentinelError::AlreadyMigrated);
+        require!(
+            old_len == SENTINEL_V2_LEN || old_len == SENTINEL_V1_LEN || old_len == SENTINEL_V3_LEN,
+            SentinelError::UnsupportedSentinelLayout
+        );
+        if old_len == SENTINEL_V3_LEN {
+            require!(&info.try_borrow_data()?[..8] == Sentinel::DISCRIMINATOR, SentinelError::InvalidAccount);
+            resize_account(
+                &info,
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                Sentinel::LEN,
+            )?;
+            let mut data = info.try_borrow_mut_data()?;
+            // Everything up to the stake policy keeps its offset; the policy
+            // fields and `bump` read the old bump and padding and are reset
+            let mut sentinel = Sentinel::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(sentinel.authority, ctx.accounts.authority.key(), SentinelError::Unauthorized);
+            sentinel.min_operator_stake = 0;
+            sentinel.unstake_delay = DEFAULT_UNSTAKE_DELAY;
+            sentinel.bump = ctx.bumps.sentinel;
+            let (authority, total_updates) = (sentinel.authority, sentinel.total_updates);
+            emit_event(&mut sentinel, SentinelMigrated {
+                authority,
+                total_updates,
+                old_len: old_len as u32,
+                new_len: Sentinel::LEN as u32,
+            });
+            record_audit(
+                &mut sentinel,
+                ctx.remaining_accounts,
+                ctx.program_id,
+                AUDIT_MIGRATE_SENTINEL,
+                authority,
+                audit_value(old_len as u64),
+            )?;
+            let mut writer = &mut data[..];
+            sentinel.try_serialize(&mut writer)?;
+            return Ok(());
+        }
+        let (authority, total_updates, paused, operators) = {
+            let data = info.try_borrow_data()?;
+            require!(&data[..8] == Sentinel::DISCRIMINATOR, SentinelError::InvalidAccount);
+            let (paused, operators) = if old_len == SENTINEL_V1_LEN {
+                let count = u32::from_le_bytes(data[49..53].try_into().unwrap()) as usize;
+                require!(count <= MAX_OPERATORS, SentinelError::InvalidAccount);
+                let operators: Vec<Pubkey> = data[53..53 + 32 * count]
+                    .chunks_exact(32)
+                    .map(|key| Pubkey::new_from_array(key.try_into().unwrap()))
+                    .collect();
+                (data[48] != 0, operators)
+            } else {
+                (false, Vec::new())
+            };
+            (
+                Pubkey::new_from_array(data[8..40].try_into().unwrap()),
+                u64::from_le_bytes(data[40..48].try_into().unwrap()),
+                paused,
+                operators,
+            )
+        };
+        require_keys_eq!(authority, ctx.accounts.authority.key(), SentinelError::Unauthorized);
+
+        resize_account(
+            &info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Sentinel::LEN,
+        )?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        // All-zero Borsh reads back as empty vecs, None, false and 0
+        data[8..].fill(0);
+        let mut sentinel = Sentinel::try_deserialize(&mut &data[..])?;
+        sentinel.authority = authority;
+        sentinel.total_updates = total_updates;
+        set_default_settings(&mut sentinel);
+        for operator in operators {
+            push_operator(&mut sentinel, operator, CAN_ALL)?;
+        }
+        // The old flag paused everything, not just the store bit that `1` means now
+        if paused {
+            sentinel.pause_flags = PAUSE_ALL;
+        }
+        // Neither layout recorded its creation time; uptime counts from here
+        sentinel.initialized_at = Clock::get()?.unix_timestamp;
+        sentinel.last_update_ts = Clock::get()?.unix_timestamp;
+        sentinel.bump = ctx.bumps.sentinel;
+        let mut writer = &mut data[..];
+        emit_event(&mut sentinel, SentinelMigrated {
+            authority,
+            total_updates,
+            old_len: old_len as u32,
+            new_len: Sentinel::LEN as u32,
+        });
+        record_audit(
+            &mut sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_MIGRATE_SENTINEL,
+            authority,
+            audit_value(old_len as u64),
+        )?;
+        sentinel.try_serialize(&mut writer)?;
+        Ok(())
+    }
+
+    /// Transfer authority to a new admin immediately. Prefer
+    /// `nominate_authority`, which requires the new admin to accept.
     pub fn transfer_authority(ctx: Context<AdminAction>, new_authority: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_TRANSFER)?;
         require!(new_authority != Pubkey::default(), SentinelError::InvalidAuthority);
         let sentinel = &mut ctx.accounts.sentinel;
         let old = sentinel.authority;
         sentinel.authority = new_authority;
-        emit!(AuthorityTransferred { old_authority: old, new_authority });
+        sentinel.pending_authority = None;
+        emit_event(&mut ctx.accounts.sentinel, AuthorityTransferred { old_authority: old, new_authority });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_TRANSFER_AUTHORITY,
+            ctx.accounts.authority.key(),
+            new_authority.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Pause or unpause the oracle.
-    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
-        ctx.accounts.sentinel.paused = paused;
-        emit!(PauseToggled { paused });
+    /// Nominate `candidate` as the next authority. Nothing changes until the
+    /// candidate signs `accept_authority`, so a mistyped key can't lock out admin
+    /// access. A new nomination replaces any pending one.
+    pub fn nominate_authority(ctx: Context<AdminAction>, candidate: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_TRANSFER)?;
+        require!(candidate != Pubkey::default(), SentinelError::InvalidAuthority);
+        ctx.accounts.sentinel.pending_authority = Some(candidate);
+        let authority = ctx.accounts.sentinel.authority;
+        emit_event(&mut ctx.accounts.sentinel, AuthorityNominated { authority, candidate });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_NOMINATE_AUTHORITY,
+            ctx.accounts.authority.key(),
+            candidate.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Add an operator who can submit sentiment updates (max 5).
-    pub fn add_operator(ctx: Context<AdminAction>, operator: Pubkey) -> Result<()> {
+    /// Complete a nomination. Only the pending candidate may sign.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
         let sentinel = &mut ctx.accounts.sentinel;
-        require!(sentinel.operators.len() < MAX_OPERATORS, SentinelError::TooManyOperators);
-        require!(!sentinel.operators.contains(&operator), SentinelError::OperatorAlreadyExists);
-        sentinel.operators.push(operator);
-        emit!(OperatorAdded { operator });
+        let old = sentinel.authority;
+        let new_authority = ctx.accounts.candidate.key();
+        sentinel.authority = new_authority;
+        sentinel.pending_authority = None;
+        emit_event(&mut ctx.accounts.sentinel, AuthorityTransferred { old_authority: old, new_authority });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_TRANSFER_AUTHORITY,
+            new_authority,
+            new_authority.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Remove an operator.
-    pub fn remove_operator(ctx: Context<AdminAction>, operator: Pubkey) -> Result<()> {
-        let sentinel = &mut ctx.accounts.sentinel;
-        let idx = sentinel.operators.iter().position(|o| *o == operator)
-            .ok_or(SentinelError::OperatorNotFound)?;
-        sentinel.operators.remove(idx);
-        emit!(OperatorRemoved { operator });
+    /// Withdraw a pending authority nomination.
+    pub fn cancel_authority_transfer(ctx: Context<AdminAction>) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_TRANSFER)?;
+        let candidate = ctx.accounts.sentinel.pending_authority.take()
+            .ok_or(SentinelError::NoPendingAuthority)?;
+        emit_event(&mut ctx.accounts.sentinel, AuthorityNominationCancelled { candidate });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_CANCEL_AUTHORITY_TRANSFER,
+            ctx.accounts.authority.key(),
+            candidate.to_bytes(),
+        )?;
         Ok(())
     }
 
-    // ===== Core Oracle Functions =====
-
-    /// Store sentiment for a token (creates or updates the record).
-    /// Only authority or operators may call this.
-    pub fn store_sentiment(
-        ctx: Context<StoreSentiment>,
-        symbol: String,
-        score: i8,
-        confidence: u8,
-        volume: u32,
-        timestamp: i64,
+    /// Grant (or re-scope) an additional authority. Root authority only.
+    pub fn set_scoped_authority(
+        ctx: Context<RootAdminAction>,
+        key: Pubkey,
+        scope_flags: u16,
     ) -> Result<()> {
-        validate_sentiment_input(&symbol, score, confidence, timestamp)?;
-        let sentinel = &ctx.accounts.sentinel;
-        require!(!sentinel.paused, SentinelError::OraclePaused);
-
-        let sentiment = &mut ctx.accounts.sentiment;
-        sentiment.symbol = symbol.clone();
-        sentiment.score = score;
-        sentiment.confidence = confidence;
-        sentiment.volume = volume;
-        sentiment.timestamp = timestamp;
-        sentiment.updater = ctx.accounts.authority.key();
-        sentiment.update_count = 0;
-        sentiment.bump = ctx.bumps.sentiment;
-
+        require!(key != Pubkey::default(), SentinelError::InvalidAuthority);
         let sentinel = &mut ctx.accounts.sentinel;
-        sentinel.total_updates = sentinel.total_updates.saturating_add(1);
-
-        emit!(SentimentUpdated {
-            symbol,
-            score,
-            confidence,
-            volume,
-            timestamp,
-            updater: ctx.accounts.authority.key(),
-        });
-
+        match sentinel.authorities.iter_mut().find(|a| a.key == key) {
+            Some(entry) => entry.scope_flags = scope_flags,
+            None => {
+                require!(sentinel.authorities.len() < MAX_AUTHORITIES, SentinelError::TooManyAuthorities);
+                sentinel.authorities.push(AuthorityEntry { key, scope_flags });
+            }
+        }
+        emit_event(&mut ctx.accounts.sentinel, AuthorityScopeSet { key, scope_flags });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_SCOPED_AUTHORITY,
+            ctx.accounts.authority.key(),
+            key.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Update an existing sentiment record in place (no realloc needed).
-    pub fn update_sentiment(
-        ctx: Context<UpdateSentiment>,
-        score: i8,
-        confidence: u8,
-        volume: u32,
-        timestamp: i64,
-    ) -> Result<()> {
-        let sentinel = &ctx.accounts.sentinel;
-        require!(!sentinel.paused, SentinelError::OraclePaused);
-        require!(score >= -100 && score <= 100, SentinelError::InvalidScore);
-        require!(confidence <= 100, SentinelError::InvalidConfidence);
-
-        let sentiment = &mut ctx.accounts.sentiment;
-        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
-
-        sentiment.score = score;
-        sentiment.confidence = confidence;
-        sentiment.volume = volume;
-        sentiment.timestamp = timestamp;
-        sentiment.updater = ctx.accounts.authority.key();
-        sentiment.update_count = sentiment.update_count.saturating_add(1);
-
+    /// Revoke an additional authority. Root authority only.
+    pub fn remove_scoped_authority(ctx: Context<RootAdminAction>, key: Pubkey) -> Result<()> {
         let sentinel = &mut ctx.accounts.sentinel;
-        sentinel.total_updates = sentinel.total_updates.saturating_add(1);
-
-        emit!(SentimentUpdated {
-            symbol: sentiment.symbol.clone(),
-            score,
-            confidence,
-            volume,
-            timestamp,
-            updater: ctx.accounts.authority.key(),
-        });
-
+        let idx = sentinel.authorities.iter().position(|a| a.key == key)
+            .ok_or(SentinelError::AuthorityNotFound)?;
+        sentinel.authorities.remove(idx);
+        emit_event(&mut ctx.accounts.sentinel, AuthorityScopeRemoved { key });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REMOVE_SCOPED_AUTHORITY,
+            ctx.accounts.authority.key(),
+            key.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Batch update multiple existing sentiment records in a single tx.
-    /// Reduces tx count for multi-token oracles.
-    pub fn batch_update_sentiments(
-        ctx: Context<BatchUpdateSentiments>,
-        updates: Vec<SentimentInput>,
-    ) -> Result<()> {
-        let sentinel_account = &ctx.accounts.sentinel;
-        require!(!sentinel_account.paused, SentinelError::OraclePaused);
-        require!(!updates.is_empty(), SentinelError::EmptyBatch);
-        require!(updates.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
-
-        let remaining = &ctx.remaining_accounts;
-        require!(remaining.len() == updates.len(), SentinelError::AccountMismatch);
-
-        let authority_key = ctx.accounts.authority.key();
-        let mut total_applied: u64 = 0;
-
-        for (i, update) in updates.iter().enumerate() {
-            require!(update.score >= -100 && update.score <= 100, SentinelError::InvalidScore);
-            require!(update.confidence <= 100, SentinelError::InvalidConfidence);
-
-            let account_info = &remaining[i];
-            // Verify the account is owned by our program
-            require!(account_info.owner == ctx.program_id, SentinelError::InvalidAccount);
-
-            let mut data = account_info.try_borrow_mut_data()?;
-            // Anchor discriminator is first 8 bytes — verify it matches SentimentRecord
-            let disc = &data[..8];
-            let expected = SentimentRecord::DISCRIMINATOR;
-            require!(disc == expected, SentinelError::InvalidAccount);
+    /// Pause or unpause everything: shorthand for `set_pause_flags` with all
+    /// bits set or cleared.
+    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
+        set_pause_flags(ctx, if paused { PAUSE_ALL } else { 0 })
+    }
 
-            // Deserialize, mutate, reserialize
-            let mut record = SentimentRecord::try_deserialize(&mut &data[..])?;
-            require!(update.timestamp > record.timestamp, SentinelError::StaleTimestamp);
+    /// Pause individual instruction groups (`PAUSE_*` bits), e.g. halt oracle
+    /// writes while votes and subscriptions stay open.
+    pub fn set_pause_flags(ctx: Context<AdminAction>, flags: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_PAUSE)?;
+        require!(flags & !PAUSE_ALL == 0, SentinelError::InvalidPauseFlags);
+        let sentinel = &mut ctx.accounts.sentinel;
+        // Resuming writes re-arms the watchdog, so it can't trip again at once
+        // on the quiet spell the pause itself caused
+        if sentinel.pause_flags & PAUSE_WRITES != 0 && flags & PAUSE_WRITES == 0 {
+            sentinel.last_update_ts = Clock::get()?.unix_timestamp;
+        }
+        sentinel.pause_flags = flags;
+        emit_event(&mut ctx.accounts.sentinel, PauseToggled { paused: flags != 0, pause_flags: flags });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_PAUSED,
+            ctx.accounts.authority.key(),
+            audit_value(flags as u64),
+        )?;
+        Ok(())
+    }
 
-            record.score = update.score;
-            record.confidence = update.confidence;
-            record.volume = update.volume;
-            record.timestamp = update.timestamp;
-            record.updater = authority_key;
-            record.update_count = record.update_count.saturating_add(1);
+    /// Require at least `interval` seconds between a symbol's reading
+    /// timestamps (0 = unlimited).
+    pub fn set_update_interval(ctx: Context<AdminAction>, interval: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(interval >= 0, SentinelError::InvalidUpdateInterval);
+        ctx.accounts.sentinel.min_update_interval = interval;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(interval as u64),
+        )?;
+        Ok(())
+    }
 
-            let mut writer = &mut data[..];
-            record.try_serialize(&mut writer)?;
+    /// Limit every writer to `max_updates` sentiment writes per `window`
+    /// seconds, containing the damage a leaked operator key can do (a window
+    /// of 0 disables).
+    pub fn set_operator_rate_limit(ctx: Context<AdminAction>, window: i64, max_updates: u16) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(
+            window == 0 || (window > 0 && max_updates > 0),
+            SentinelError::InvalidRateLimit
+        );
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.rate_limit_window = window;
+        sentinel.rate_limit_max = max_updates;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(window as u64),
+        )?;
+        Ok(())
+    }
 
-            emit!(SentimentUpdated {
-                symbol: record.symbol.clone(),
-                score: update.score,
-                confidence: update.confidence,
-                volume: update.volume,
-                timestamp: update.timestamp,
-                updater: authority_key,
-            });
+    /// Bound how far a reading's timestamp may sit ahead of or behind the
+    /// on-chain clock, so operators can't future- or backdate readings
+    /// (0 leaves that side unchecked).
+    pub fn set_timestamp_skew(ctx: Context<AdminAction>, max_future_skew: i64, max_past_skew: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(max_future_skew >= 0 && max_past_skew >= 0, SentinelError::InvalidTimestamp);
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.max_future_skew = max_future_skew;
+        sentinel.max_past_skew = max_past_skew;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(max_future_skew as u64),
+        )?;
+        Ok(())
+    }
 
-            total_applied += 1;
-        }
+    /// Reject readings whose score moves more than `max_delta` points from the
+    /// current canonical score (0 disables).
+    pub fn set_max_score_delta(ctx: Context<AdminAction>, max_delta: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.max_score_delta = max_delta;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(max_delta as u64),
+        )?;
+        Ok(())
+    }
 
-        // Update global counter
+    /// Idle profiles lose one reputation point per `period` seconds without
+    /// activity, via `decay_reputation`, but never drop below `floor`.
+    pub fn set_reputation_decay(ctx: Context<AdminAction>, period: i64, floor: u16) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(period > 0, SentinelError::InvalidDecayPeriod);
         let sentinel = &mut ctx.accounts.sentinel;
-        sentinel.total_updates = sentinel.total_updates.saturating_add(total_applied);
-
-        emit!(BatchUpdateCompleted { count: total_applied as u8 });
+        sentinel.reputation_decay_period = period;
+        sentinel.reputation_floor = floor;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(period as u64),
+        )?;
         Ok(())
     }
 
-    /// Take a historical snapshot of a sentiment record.
-    /// Stores the last N readings in a ring buffer for historical queries.
-    pub fn record_history(ctx: Context<RecordHistory>, symbol: String) -> Result<()> {
-        let sentiment = &ctx.accounts.sentiment;
-        let history = &mut ctx.accounts.history;
-        let clock = Clock::get()?;
+    /// Let anyone pause the oracle via `check_watchdog` once no symbol has been
+    /// written for `timeout` seconds (0 disables).
+    pub fn set_watchdog_timeout(ctx: Context<AdminAction>, timeout: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_PAUSE)?;
+        require!(timeout >= 0, SentinelError::InvalidWatchdogTimeout);
+        // The timeout counts from now, not from a write made before it was set
+        ctx.accounts.sentinel.watchdog_timeout = timeout;
+        ctx.accounts.sentinel.last_update_ts = Clock::get()?.unix_timestamp;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(timeout as u64),
+        )?;
+        Ok(())
+    }
 
-        // Initialize on first use
-        if history.symbol.is_empty() {
-            history.symbol = symbol;
-            history.bump = ctx.bumps.history;
-            history.count = 0;
-            history.head = 0;
-            history.snapshots = vec![HistoryEntry::default(); MAX_HISTORY];
+    /// Pause oracle writes if the feed has gone quiet for longer than the
+    /// watchdog timeout. Permissionless; only the authority can unpause.
+    pub fn check_watchdog(ctx: Context<CheckWatchdog>) -> Result<()> {
+        let sentinel = &mut ctx.accounts.sentinel;
+        let now = Clock::get()?.unix_timestamp;
+        let idle = now.saturating_sub(sentinel.last_update_ts);
+        let writes_paused = sentinel.pause_flags & PAUSE_WRITES == PAUSE_WRITES;
+        if writes_paused || sentinel.watchdog_timeout == 0 || idle <= sentinel.watchdog_timeout {
+            msg!("Watchdog not tripped");
+            return Ok(());
         }
 
-        let entry = HistoryEntry {
-            score: sentiment.score,
-            confidence: sentiment.confidence,
-            volume: sentiment.volume,
-            timestamp: sentiment.timestamp,
-            recorded_at: clock.unix_timestamp,
-        };
+        sentinel.pause_flags |= PAUSE_WRITES;
+        let (last_update_ts, timeout) = (sentinel.last_update_ts, sentinel.watchdog_timeout);
+        emit_event(sentinel, WatchdogTripped { last_update_ts, timeout, tripped_at: now });
+        Ok(())
+    }
 
-        let idx = history.head as usize % MAX_HISTORY;
-        history.snapshots[idx] = entry;
-        history.head = history.head.wrapping_add(1);
-        if (history.count as usize) < MAX_HISTORY {
-            history.count += 1;
-        }
+    /// Set the milestone interval for `MilestoneReached` events (0 = powers of ten).
+    pub fn set_milestone_interval(ctx: Context<AdminAction>, interval: u64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.milestone_interval = interval;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(interval),
+        )?;
+        Ok(())
+    }
 
-        emit!(HistoryRecorded {
-            symbol: history.symbol.clone(),
-            entries: history.count,
-        });
+    /// Enable or disable the operator subsystem. When disabled, only the
+    /// authority may write, even if operators are listed.
+    pub fn set_operators_enabled(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        ctx.accounts.sentinel.operators_enabled = enabled;
+        emit_event(&mut ctx.accounts.sentinel, OperatorsToggled { enabled });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_OPERATORS_ENABLED,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
+        Ok(())
+    }
 
+    /// Set how long newly added operators stay on probation (0 = no probation).
+    pub fn set_operator_probation(ctx: Context<AdminAction>, probation: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(probation >= 0, SentinelError::InvalidProbation);
+        ctx.accounts.sentinel.operator_probation = probation;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(probation as u64),
+        )?;
         Ok(())
     }
 
-    // ===== Social Functions =====
+    /// Opt in to leaving a reputation tombstone when profiles are closed, so
+    /// users cannot reset a bad reputation by closing and recreating.
+    pub fn set_reputation_tombstones(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.reputation_tombstones = enabled;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
+        Ok(())
+    }
 
-    pub fn create_profile(ctx: Context<CreateProfile>, username: String) -> Result<()> {
-        require!(!username.is_empty(), SentinelError::EmptyUsername);
-        require!(username.len() <= MAX_USERNAME_LEN, SentinelError::UsernameTooLong);
-        require!(username.chars().all(|c| c.is_alphanumeric() || c == '_'), SentinelError::InvalidUsername);
+    /// Turn event emission on or off. With events off, state-mutating
+    /// instructions skip `emit!` entirely to save compute.
+    pub fn set_events_enabled(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.events_enabled = enabled;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
+        Ok(())
+    }
 
-        let profile = &mut ctx.accounts.profile;
-        let clock = Clock::get()?;
+    /// Set how many operator votes resolve a value dispute (0 = simple majority).
+    pub fn set_dispute_quorum(ctx: Context<AdminAction>, quorum: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(quorum as usize <= MAX_OPERATORS, SentinelError::InvalidQuorum);
+        ctx.accounts.sentinel.dispute_quorum = quorum;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(quorum as u64),
+        )?;
+        Ok(())
+    }
 
-        profile.owner = ctx.accounts.user.key();
-        profile.username = username;
-        profile.predictions_made = 0;
-        profile.correct_predictions = 0;
-        profile.reputation = 100;
-        profile.created_at = clock.unix_timestamp;
-        profile.last_active = clock.unix_timestamp;
-        profile.bump = ctx.bumps.profile;
+    /// Set the minimum profile age (seconds) before a profile may vote.
+    pub fn set_min_profile_age(ctx: Context<AdminAction>, min_age: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(min_age >= 0, SentinelError::InvalidTimestamp);
+        ctx.accounts.sentinel.min_profile_age = min_age;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(min_age as u64),
+        )?;
+        Ok(())
+    }
 
+    /// Set how strongly operator disagreement lowers the resolved confidence,
+    /// as a percentage (0 disables, 100 zeroes confidence on a full -100/100 split).
+    pub fn set_spread_penalty_factor(ctx: Context<AdminAction>, factor: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(factor <= 100, SentinelError::InvalidPenaltyFactor);
+        ctx.accounts.sentinel.spread_penalty_factor = factor;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(factor as u64),
+        )?;
         Ok(())
     }
 
-    pub fn subscribe_token(
-        ctx: Context<SubscribeToken>,
-        symbol: String,
-        direction: i8,
-        alert_threshold: u8,
+    /// Emit `VolumeAnomaly` when a reading's volume exceeds the EMA by more than
+    /// this many standard deviations (0 disables).
+    pub fn set_volume_anomaly_sigmas(ctx: Context<AdminAction>, sigmas: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.volume_anomaly_sigmas = sigmas;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(sigmas as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Trip a symbol's volatility circuit when its volume standard deviation
+    /// exceeds `threshold` percent of the EMA (0 disables). With `enforced`,
+    /// only the root authority may write to a tripped symbol until a write
+    /// brings volatility back under the threshold.
+    pub fn set_volatility_circuit(
+        ctx: Context<AdminAction>,
+        threshold: u16,
+        enforced: bool,
     ) -> Result<()> {
-        require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
-        require!(!symbol.is_empty(), SentinelError::EmptySymbol);
-        require!(direction >= -1 && direction <= 1, SentinelError::InvalidDirection);
-        require!(alert_threshold <= 100, SentinelError::InvalidThreshold);
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.volatility_threshold = threshold;
+        sentinel.volatility_circuit = enforced;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(threshold as u64),
+        )?;
+        Ok(())
+    }
 
-        let subscription = &mut ctx.accounts.subscription;
-        let clock = Clock::get()?;
+    /// Configure `score_display`: decimal places and the label thresholds.
+    pub fn set_display_config(
+        ctx: Context<AdminAction>,
+        decimals: u8,
+        bullish_threshold: i8,
+        bearish_threshold: i8,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(decimals <= MAX_DISPLAY_DECIMALS, SentinelError::InvalidDisplayConfig);
+        require!(
+            (-100..=100).contains(&bullish_threshold)
+                && (-100..=100).contains(&bearish_threshold)
+                && bearish_threshold < bullish_threshold,
+            SentinelError::InvalidDisplayConfig
+        );
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.display_decimals = decimals;
+        sentinel.bullish_threshold = bullish_threshold;
+        sentinel.bearish_threshold = bearish_threshold;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(decimals as u64),
+        )?;
+        Ok(())
+    }
 
-        subscription.user = ctx.accounts.user.key();
-        subscription.symbol = symbol;
-        subscription.direction = direction;
-        subscription.alert_threshold = alert_threshold;
-        subscription.subscribed_at = clock.unix_timestamp;
-        subscription.last_alert = 0;
-        subscription.bump = ctx.bumps.subscription;
+    /// Configure expiry of unresolved votes: the age after which anyone may
+    /// expire them (0 disables) and the reputation penalty applied.
+    pub fn set_prediction_expiry(ctx: Context<AdminAction>, horizon: i64, penalty: u16) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(horizon >= 0, SentinelError::InvalidTimestamp);
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.prediction_horizon = horizon;
+        sentinel.expiry_penalty = penalty;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(horizon as u64),
+        )?;
+        Ok(())
+    }
 
+    /// Break ranking ties with per-refresh entropy instead of lexical key order,
+    /// so tied entries can't win by choosing a favorable key. Tied entries may
+    /// then change order between refreshes.
+    pub fn set_random_tiebreak(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.random_tiebreak = enabled;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
         Ok(())
     }
 
-    pub fn unsubscribe_token(ctx: Context<Unsubscribe>) -> Result<()> {
-        // Account is closed via the close constraint
-        emit!(Unsubscribed {
-            user: ctx.accounts.user.key(),
-            symbol: ctx.accounts.subscription.symbol.clone(),
-        });
+    /// Add an operator who can submit sentiment updates (max 5).
+    pub fn add_operator(ctx: Context<AdminAction>, operator: Pubkey, perms: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        push_operator(&mut ctx.accounts.sentinel, operator, perms)?;
+        emit_event(&mut ctx.accounts.sentinel, OperatorAdded { operator, perms });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_ADD_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
         Ok(())
     }
 
-    pub fn vote_sentiment(
-        ctx: Context<VoteSentiment>,
-        symbol: String,
-        score: i8,
-        confidence: u8,
+    /// Configure community review of operator proposals: how long a proposal
+    /// stays open and the reputation a profile needs to endorse or object.
+    pub fn set_proposal_config(
+        ctx: Context<AdminAction>,
+        window: i64,
+        min_reputation: u16,
     ) -> Result<()> {
-        require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
-        require!(!symbol.is_empty(), SentinelError::EmptySymbol);
-        require!(score >= -100 && score <= 100, SentinelError::InvalidScore);
-        require!(confidence <= 100, SentinelError::InvalidConfidence);
-
-        let vote = &mut ctx.accounts.vote;
-        let profile = &mut ctx.accounts.profile;
-        let clock = Clock::get()?;
-
-        vote.voter = ctx.accounts.user.key();
-        vote.symbol = symbol.clone();
-        vote.score = score;
-        vote.confidence = confidence;
-        vote.timestamp = clock.unix_timestamp;
-        vote.bump = ctx.bumps.vote;
-
-        profile.predictions_made = profile.predictions_made.saturating_add(1);
-        profile.last_active = clock.unix_timestamp;
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(window > 0, SentinelError::InvalidProposalWindow);
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.proposal_window = window;
+        sentinel.min_endorser_reputation = min_reputation;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(window as u64),
+        )?;
+        Ok(())
+    }
 
-        emit!(CommunityVoteEvent {
-            voter: vote.voter,
-            symbol,
-            score,
-            confidence,
-            timestamp: clock.unix_timestamp,
+    /// Open a community review window for adding `candidate` as an operator.
+    pub fn propose_operator(ctx: Context<ProposeOperator>, candidate: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(
+            !ctx.accounts.sentinel.operators.iter().any(|o| o.key == candidate),
+            SentinelError::OperatorAlreadyExists
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.candidate = candidate;
+        proposal.proposer = ctx.accounts.authority.key();
+        proposal.opened_at = now;
+        proposal.closes_at = now.saturating_add(ctx.accounts.sentinel.proposal_window);
+        proposal.endorsements = 0;
+        proposal.objections = 0;
+        proposal.voters = Vec::new();
+        proposal.bump = ctx.bumps.proposal;
+
+        emit_event(&mut ctx.accounts.sentinel, OperatorProposed {
+            candidate,
+            proposer: proposal.proposer,
+            closes_at: proposal.closes_at,
         });
-
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_PROPOSE_OPERATOR,
+            ctx.accounts.authority.key(),
+            candidate.to_bytes(),
+        )?;
         Ok(())
     }
 
-    /// Admin can resolve a user's prediction (correct or not) and adjust reputation.
-    pub fn resolve_prediction(
-        ctx: Context<ResolvePrediction>,
-        correct: bool,
-    ) -> Result<()> {
-        let profile = &mut ctx.accounts.profile;
+    /// Endorse an open operator proposal. One endorsement or objection per profile.
+    pub fn endorse_operator(ctx: Context<ReviewOperatorProposal>) -> Result<()> {
+        review_operator_proposal(ctx, true)
+    }
 
-        if correct {
-            profile.correct_predictions = profile.correct_predictions.saturating_add(1);
-            profile.reputation = profile.reputation.saturating_add(10).min(1000);
-        } else {
-            profile.reputation = profile.reputation.saturating_sub(5).max(0);
-        }
+    /// Object to an open operator proposal. One endorsement or objection per profile.
+    pub fn object_operator(ctx: Context<ReviewOperatorProposal>) -> Result<()> {
+        review_operator_proposal(ctx, false)
+    }
 
-        emit!(PredictionResolved {
-            user: profile.owner,
-            correct,
-            new_reputation: profile.reputation,
+    /// Close a proposal after its review window, adding the candidate as an
+    /// operator only if endorsements outnumber objections. A passing proposal
+    /// that can't be applied (the operator set is full, or the candidate was
+    /// added meanwhile) is closed as rejected rather than left stuck open.
+    pub fn finalize_operator(ctx: Context<FinalizeOperator>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.closes_at,
+            SentinelError::ProposalStillOpen
+        );
+        let candidate = proposal.candidate;
+        // Narrow with set_operator_perms afterwards if needed
+        let accepted = proposal.endorsements > proposal.objections
+            && push_operator(&mut ctx.accounts.sentinel, candidate, CAN_ALL).is_ok();
+        if accepted {
+            emit_event(&mut ctx.accounts.sentinel, OperatorAdded { operator: candidate, perms: CAN_ALL });
+        }
+        emit_event(&mut ctx.accounts.sentinel, OperatorProposalFinalized {
+            candidate,
+            endorsements: proposal.endorsements,
+            objections: proposal.objections,
+            accepted,
         });
+        Ok(())
+    }
 
+    /// Set the reputation and prediction count a profile needs before
+    /// `nominate_operator_from_profile` will promote its owner.
+    pub fn set_operator_promotion(
+        ctx: Context<AdminAction>,
+        min_reputation: u16,
+        min_predictions: u32,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.min_operator_rep = min_reputation;
+        sentinel.min_operator_predictions = min_predictions;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(min_reputation as u64),
+        )?;
         Ok(())
     }
 
-    /// Close a sentiment record and reclaim rent (admin only).
-    pub fn close_sentiment(ctx: Context<CloseSentiment>, _symbol: String) -> Result<()> {
-        emit!(SentimentClosed { symbol: ctx.accounts.sentiment.symbol.clone() });
+    /// Promote a proven community predictor to operator. The profile must
+    /// meet `min_operator_rep` and `min_operator_predictions`; its owner is
+    /// added with every permission, as `finalize_operator` does.
+    pub fn nominate_operator_from_profile(ctx: Context<NominateOperatorFromProfile>) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        let profile = &ctx.accounts.profile;
+        require!(
+            profile.reputation >= ctx.accounts.sentinel.min_operator_rep
+                && profile.predictions_made >= ctx.accounts.sentinel.min_operator_predictions,
+            SentinelError::ProfileNotQualified
+        );
+        let (operator, reputation) = (profile.owner, profile.reputation);
+        push_operator(&mut ctx.accounts.sentinel, operator, CAN_ALL)?;
+        emit_event(&mut ctx.accounts.sentinel, OperatorAdded { operator, perms: CAN_ALL });
+        emit_event(&mut ctx.accounts.sentinel, OperatorPromoted { profile_owner: operator, reputation });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_ADD_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Replace an operator's `CAN_*` permission bits.
+    pub fn set_operator_perms(ctx: Context<AdminAction>, operator: Pubkey, perms: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(perms & !CAN_ALL == 0, SentinelError::InvalidOperatorPerms);
+        let entry = ctx.accounts.sentinel.operators.iter_mut().find(|o| o.key == operator)
+            .ok_or(SentinelError::OperatorNotFound)?;
+        entry.perms = perms;
+        emit_event(&mut ctx.accounts.sentinel, OperatorPermsSet { operator, perms });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_OPERATOR_PERMS,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Cap the confidence an operator may report; higher submissions are clamped.
+    pub fn set_operator_max_confidence(
+        ctx: Context<AdminAction>,
+        operator: Pubkey,
+        max_confidence: u8,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(max_confidence <= 100, SentinelError::InvalidConfidence);
+        let entry = ctx.accounts.sentinel.operators.iter_mut().find(|o| o.key == operator)
+            .ok_or(SentinelError::OperatorNotFound)?;
+        entry.max_confidence = max_confidence;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_OPERATOR_MAX_CONFIDENCE,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Set how an operator's native score maps onto -100..100: `score_offset`
+    /// is subtracted first, then the result is multiplied by `scale_factor`
+    /// (`SCALE_FACTOR_ONE` = 1.0). A native 0..1000 output is offset 500,
+    /// factor 20. Scaled scores are clamped.
+    pub fn set_operator_scale_factor(
+        ctx: Context<AdminAction>,
+        operator: Pubkey,
+        scale_factor: i16,
+        score_offset: i16,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(scale_factor > 0, SentinelError::InvalidScaleFactor);
+        let entry = ctx.accounts.sentinel.operators.iter_mut().find(|o| o.key == operator)
+            .ok_or(SentinelError::OperatorNotFound)?;
+        entry.scale_factor = scale_factor;
+        entry.score_offset = score_offset;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_OPERATOR_SCALE_FACTOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Post `amount` lamports of stake as an operator, creating the stake
+    /// account on first use. Stake can be slashed with `slash_operator`.
+    pub fn post_stake(ctx: Context<PostStake>, amount: u64) -> Result<()> {
+        require!(amount > 0, SentinelError::InvalidStakeAmount);
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.operator.to_account_info(),
+                    to: ctx.accounts.stake.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let stake = &mut ctx.accounts.stake;
+        stake.operator = ctx.accounts.operator.key();
+        stake.bump = ctx.bumps.stake;
+        stake.staked = stake.staked.saturating_add(amount);
+        stake.unstake_requested_at = 0;
+        emit_event(&mut ctx.accounts.sentinel, StakePosted {
+            operator: stake.operator,
+            amount,
+            remaining: stake.remaining(),
+        });
+        Ok(())
+    }
+
+    /// Move `amount` lamports of an operator's unslashed stake to the
+    /// treasury after a provably bad reading. The stake account's rent
+    /// reserve is never touched.
+    pub fn slash_operator(ctx: Context<SlashOperator>, amount: u64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(amount > 0, SentinelError::InvalidStakeAmount);
+        let stake = &mut ctx.accounts.stake;
+        require!(amount <= stake.remaining(), SentinelError::InsufficientStake);
+        stake.slashed_total += amount;
+        let operator = stake.operator;
+
+        **ctx.accounts.stake.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        emit_event(&mut ctx.accounts.sentinel, OperatorSlashed { operator, amount });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SLASH_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Start the unstake delay for an operator that has been removed from the
+    /// set. The stake stays slashable until `withdraw_stake` after
+    /// `unstake_delay` seconds.
+    pub fn request_unstake(ctx: Context<RequestUnstake>) -> Result<()> {
+        let operator = ctx.accounts.operator.key();
+        require!(
+            !ctx.accounts.sentinel.operators.iter().any(|o| o.key == operator),
+            SentinelError::OperatorStillActive
+        );
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.stake.unstake_requested_at = now;
+        let available_at = now.saturating_add(ctx.accounts.sentinel.unstake_delay);
+        emit_event(&mut ctx.accounts.sentinel, UnstakeRequested { operator, available_at });
+        Ok(())
+    }
+
+    /// Return a removed operator's unslashed stake, with the stake account's
+    /// rent, once its unstake delay has run out.
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let operator = ctx.accounts.operator.key();
+        require!(
+            !ctx.accounts.sentinel.operators.iter().any(|o| o.key == operator),
+            SentinelError::OperatorStillActive
+        );
+        let stake = &ctx.accounts.stake;
+        require!(stake.unstake_requested_at > 0, SentinelError::UnstakeNotRequested);
+        require!(
+            Clock::get()?.unix_timestamp >= stake.unstake_requested_at.saturating_add(ctx.accounts.sentinel.unstake_delay),
+            SentinelError::UnstakeDelayNotElapsed
+        );
+        let amount = stake.remaining();
+        emit_event(&mut ctx.accounts.sentinel, StakeWithdrawn { operator, amount });
+        Ok(())
+    }
+
+    /// Set the unslashed stake an operator needs to write readings (0 = none)
+    /// and how long a removed operator waits between `request_unstake` and
+    /// `withdraw_stake`.
+    pub fn set_stake_policy(ctx: Context<AdminAction>, min_stake: u64, unstake_delay: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(unstake_delay >= 0, SentinelError::InvalidUnstakeDelay);
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.min_operator_stake = min_stake;
+        sentinel.unstake_delay = unstake_delay;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(min_stake),
+        )?;
+        Ok(())
+    }
+
+    /// Set how much of `publish_blended`'s score comes from the community
+    /// consensus, in percent (0 = oracle only, 100 = community only).
+    pub fn set_community_weight(ctx: Context<AdminAction>, weight: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(weight <= 100, SentinelError::InvalidCommunityWeight);
+        ctx.accounts.sentinel.community_weight = weight;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(weight as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set the account slashed stake is paid to.
+    pub fn set_treasury(ctx: Context<AdminAction>, treasury: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_FEES)?;
+        ctx.accounts.sentinel.treasury = treasury;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            treasury.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Remove an operator. Refused if an explicit dispute quorum could no
+    /// longer be reached by the remaining operators.
+    pub fn remove_operator(ctx: Context<AdminAction>, operator: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        let sentinel = &mut ctx.accounts.sentinel;
+        let idx = sentinel.operators.iter().position(|o| o.key == operator)
+            .ok_or(SentinelError::OperatorNotFound)?;
+        require!(
+            (sentinel.dispute_quorum as usize) < sentinel.operators.len(),
+            SentinelError::WouldBreakQuorum
+        );
+        sentinel.operators.remove(idx);
+        emit_event(&mut ctx.accounts.sentinel, OperatorRemoved { operator });
+        if ctx.accounts.sentinel.operators.is_empty() && ctx.accounts.sentinel.operators_enabled {
+            emit_event(&mut ctx.accounts.sentinel, LastOperatorRemoved { operator });
+        }
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REMOVE_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Let `operator` write `symbol` without joining the global operator set.
+    /// Scoped writes are still subject to `operators_enabled`.
+    pub fn grant_symbol_operator(
+        ctx: Context<GrantSymbolOperator>,
+        operator: Pubkey,
+        symbol: String,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+        require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+        require!(symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
+
+        let scope = &mut ctx.accounts.operator_scope;
+        scope.operator = operator;
+        scope.symbol = symbol.clone();
+        scope.granted_by = ctx.accounts.authority.key();
+        scope.granted_at = Clock::get()?.unix_timestamp;
+        scope.bump = ctx.bumps.operator_scope;
+
+        emit_event(&mut ctx.accounts.sentinel, SymbolOperatorGranted { operator, symbol });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_GRANT_SYMBOL_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Withdraw a per-symbol grant, refunding its rent to the caller.
+    pub fn revoke_symbol_operator(
+        ctx: Context<RevokeSymbolOperator>,
+        operator: Pubkey,
+        symbol: String,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_OPERATORS)?;
+        emit_event(&mut ctx.accounts.sentinel, SymbolOperatorRevoked { operator, symbol });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REVOKE_SYMBOL_OPERATOR,
+            ctx.accounts.authority.key(),
+            operator.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Turn the on-chain audit log on or off, creating the log on first use.
+    /// While enabled, admin instructions must pass the `AuditLog` PDA as their
+    /// first remaining account.
+    pub fn set_audit_enabled(ctx: Context<SetAuditEnabled>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        let log = &mut ctx.accounts.audit_log;
+        if log.entries.is_empty() {
+            log.entries = vec![AuditEntry::default(); MAX_AUDIT_ENTRIES];
+            log.bump = ctx.bumps.audit_log;
+        }
+        ctx.accounts.sentinel.audit_enabled = enabled;
+
+        let entry = AuditEntry {
+            action_code: AUDIT_SET_AUDIT_ENABLED,
+            actor: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            detail: audit_value(enabled as u64),
+        };
+        ctx.accounts.audit_log.push(entry.clone());
+        emit_event(&mut ctx.accounts.sentinel, AuditEntryRecorded {
+            action_code: entry.action_code,
+            actor: entry.actor,
+            timestamp: entry.timestamp,
+        });
+        Ok(())
+    }
+
+    // ===== Core Oracle Functions =====
+
+    /// Store sentiment for a token (creates or updates the record).
+    /// Only authority or operators may call this.
+    pub fn store_sentiment(
+        ctx: Context<StoreSentiment>,
+        symbol: String,
+        score: i16,
+        confidence: u8,
+        volume: u32,
+        timestamp: i64,
+        note: String,
+    ) -> Result<()> {
+        store_reading(ctx, symbol, score as i32 * 100, confidence, volume, timestamp, note)
+    }
+
+    /// `store_sentiment` with the score in basis points (-10000..=10000). The
+    /// legacy `score` is kept populated as `score_bps / 100`.
+    pub fn store_sentiment_precise(
+        ctx: Context<StoreSentiment>,
+        symbol: String,
+        score_bps: i16,
+        confidence: u8,
+        volume: u32,
+        timestamp: i64,
+        note: String,
+    ) -> Result<()> {
+        require!((-10_000..=10_000).contains(&score_bps), SentinelError::InvalidScoreBps);
+        store_reading(ctx, symbol, score_bps as i32, confidence, volume, timestamp, note)
+    }
+
+    /// Update an existing sentiment record in place (no realloc needed).
+    /// Repeating the previous update's `idempotency_key` is a successful no-op,
+    /// so clients can safely retry. With `auto_history` on and the symbol's
+    /// history passed, the new reading is also snapshotted into it.
+    pub fn update_sentiment(
+        ctx: Context<UpdateSentiment>,
+        score: i16,
+        confidence: u8,
+        volume: u32,
+        timestamp: i64,
+        idempotency_key: Option<[u8; 16]>,
+        note: String,
+    ) -> Result<()> {
+        let sentinel = &ctx.accounts.sentinel;
+        require_unpaused(sentinel, PAUSE_UPDATE)?;
+        require_market_open(&ctx.accounts.symbol_config, ctx.program_id, Clock::get()?.unix_timestamp)?;
+
+        if let Some(key) = idempotency_key {
+            if key != [0; 16] && key == ctx.accounts.sentiment.last_idempotency_key {
+                msg!("Duplicate idempotency key, update already applied");
+                return Ok(());
+            }
+        }
+        let raw_score = score;
+        let symbol_config = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?;
+        let symbol_scale = symbol_config.as_ref().map_or(SCALE_FACTOR_ONE, |c| c.scale_factor);
+        let score = normalize_score(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), raw_score, symbol_scale);
+        require!((-100..=100).contains(&score), SentinelError::InvalidScore);
+        let score = score as i8;
+        require!(confidence <= 100, SentinelError::InvalidConfidence);
+        validate_note(&note)?;
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        require_timestamp_in_range(&ctx.accounts.sentinel, timestamp, now)?;
+        charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, authority_key, ctx.bumps.operator_stats, now)?;
+        charge_write_fee(
+            symbol_config.as_ref().map_or(0, |c| c.write_fee),
+            &ctx.accounts.authority,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+        let submitted_confidence = confidence;
+        let confidence = confidence.min(operator_confidence_cap(&ctx.accounts.sentinel, &authority_key));
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+        require_direct_write(sentiment)?;
+        require_update_interval(&ctx.accounts.sentinel, sentiment, timestamp)?;
+        require_calm(&ctx.accounts.sentinel, sentiment, &authority_key)?;
+        require_score_delta(&mut ctx.accounts.sentinel, sentiment, score)?;
+
+        if provisional {
+            let sentinel = &mut ctx.accounts.sentinel;
+            record_provisional(sentinel, sentiment, score, confidence, volume, timestamp, authority_key);
+            return Ok(());
+        }
+
+        observe_volume(&mut ctx.accounts.sentinel, sentiment, volume);
+        sentiment.score = score;
+        sentiment.score_bps = score as i16 * 100;
+        sentiment.confidence = confidence;
+        sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+        sentiment.last_update_slot = Clock::get()?.slot;
+        sentiment.volume = volume;
+        sentiment.timestamp = timestamp;
+        sentiment.updater = ctx.accounts.authority.key();
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        sentiment.last_idempotency_key = idempotency_key.unwrap_or_default();
+        sentiment.note = note;
+        let hash = sentiment.advance_hash_chain();
+        let out_of_turn = take_turn(&ctx.accounts.sentinel, sentiment, &authority_key);
+
+        bump_total_updates(&mut ctx.accounts.sentinel, 1, now);
+
+        if let Some(history) = ctx.accounts.history.as_mut().filter(|_| sentiment.auto_history) {
+            history.head %= history.capacity.max(1);
+            assert_history_invariant(history)?;
+            push_history(history, snapshot_of(sentiment, now));
+            emit_event(&mut ctx.accounts.sentinel, HistoryRecorded {
+                symbol: history.symbol.clone(),
+                entries: history.count,
+            });
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, SentimentUpdated {
+            symbol: sentiment.symbol.clone(),
+            score,
+            raw_score,
+            confidence,
+            submitted_confidence,
+            volume,
+            timestamp,
+            updater: ctx.accounts.authority.key(),
+            note: sentiment.note.clone(),
+            out_of_turn,
+            adjusted_score: sentiment.adjusted_score,
+            hash,
+        });
+
+        Ok(())
+    }
+
+    /// Open a reading of a consensus-gated symbol for approval, counting the
+    /// proposer's own approval unless they're on probation. Replaces an
+    /// earlier proposal only once it has expired. Commits at once if the
+    /// threshold is a single approval. The proposer is rate limited, pays the
+    /// symbol's write fee and has their confidence capped as on a direct write.
+    pub fn propose_update(
+        ctx: Context<ProposeUpdate>,
+        symbol: String,
+        score: i8,
+        confidence: u8,
+        volume: u32,
+        timestamp: i64,
+    ) -> Result<()> {
+        validate_sentiment_input(&symbol, score, confidence, timestamp)?;
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_UPDATE)?;
+        let sentiment = &ctx.accounts.sentiment;
+        require!(sentiment.consensus_threshold > 0, SentinelError::ConsensusNotRequired);
+        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposer = ctx.accounts.proposer.key();
+        charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, proposer, ctx.bumps.operator_stats, now)?;
+        let write_fee = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?.map_or(0, |c| c.write_fee);
+        charge_write_fee(
+            write_fee,
+            &ctx.accounts.proposer,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &proposer, now);
+        let confidence = confidence.min(operator_confidence_cap(&ctx.accounts.sentinel, &proposer));
+        let pending = &mut ctx.accounts.pending;
+        require!(
+            pending.symbol.is_empty() || now > pending.deadline,
+            SentinelError::PendingUpdateExists
+        );
+        pending.symbol = symbol.clone();
+        pending.proposer = proposer;
+        pending.score = score;
+        pending.confidence = confidence;
+        pending.volume = volume;
+        pending.timestamp = timestamp;
+        pending.deadline = now + PENDING_UPDATE_TTL;
+        pending.approvals = if provisional { Vec::new() } else { vec![proposer] };
+        pending.bump = ctx.bumps.pending;
+
+        emit_event(&mut ctx.accounts.sentinel, UpdateProposed {
+            symbol,
+            proposer,
+            score,
+            confidence,
+            deadline: pending.deadline,
+        });
+
+        if pending.approvals.len() >= ctx.accounts.sentiment.consensus_threshold as usize {
+            commit_pending_update(
+                &mut ctx.accounts.sentinel,
+                &mut ctx.accounts.sentiment,
+                pending,
+                &ctx.accounts.symbol_config,
+                ctx.accounts.history.as_mut(),
+                ctx.program_id,
+                now,
+            )?;
+            pending.close(ctx.accounts.proposer.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Add the signer's approval to a pending update. The approval that
+    /// reaches the symbol's threshold commits the reading and closes the
+    /// proposal, refunding its rent to the proposer. Operators on probation
+    /// can't approve. Approvals from signers who have since lost update
+    /// rights or been put back on probation, or whose stake has fallen below the
+    /// minimum, are dropped first; pass earlier approvers' `OperatorStake`
+    /// PDAs as remaining accounts to keep theirs while a minimum is set. The
+    /// commit runs the same guards as `update_sentiment`.
+    pub fn approve_update(ctx: Context<ApproveUpdate>, symbol: String) -> Result<()> {
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_UPDATE)?;
+        let now = Clock::get()?.unix_timestamp;
+        let approver = ctx.accounts.approver.key();
+        require!(
+            !operator_on_probation(&mut ctx.accounts.sentinel, &approver, now),
+            SentinelError::OperatorOnProbation
+        );
+        let sentinel = &ctx.accounts.sentinel;
+        let pending = &mut ctx.accounts.pending;
+        require!(now <= pending.deadline, SentinelError::PendingUpdateExpired);
+        require!(!pending.approvals.contains(&approver), SentinelError::AlreadyApproved);
+        let stakes = ctx.remaining_accounts;
+        pending.approvals.retain(|key| {
+            counts_as_approver(sentinel, key) && approval_meets_min_stake(sentinel, key, stakes)
+        });
+        pending.approvals.push(approver);
+
+        let threshold = ctx.accounts.sentiment.consensus_threshold;
+        emit_event(&mut ctx.accounts.sentinel, UpdateApproved {
+            symbol,
+            approver,
+            approvals: pending.approvals.len() as u8,
+            threshold,
+        });
+
+        if pending.approvals.len() >= threshold as usize {
+            commit_pending_update(
+                &mut ctx.accounts.sentinel,
+                &mut ctx.accounts.sentiment,
+                pending,
+                &ctx.accounts.symbol_config,
+                ctx.accounts.history.as_mut(),
+                ctx.program_id,
+                now,
+            )?;
+            pending.close(ctx.accounts.proposer.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Batch update multiple existing sentiment records in a single tx.
+    /// Reduces tx count for multi-token oracles. Every entry is validated
+    /// before any is written, so one bad entry leaves all records untouched.
+    /// `remaining_accounts` holds a (sentiment record, symbol config PDA)
+    /// pair per entry, in order; the config PDA need not exist.
+    pub fn batch_update_sentiments(
+        ctx: Context<BatchUpdateSentiments>,
+        updates: Vec<SentimentInput>,
+    ) -> Result<()> {
+        let sentinel_account = &ctx.accounts.sentinel;
+        require_unpaused(sentinel_account, PAUSE_UPDATE)?;
+        require!(!updates.is_empty(), SentinelError::EmptyBatch);
+        require!(updates.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
+
+        let remaining = &ctx.remaining_accounts;
+        require!(remaining.len() == updates.len() * 2, SentinelError::AccountMismatch);
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+        let cap = operator_confidence_cap(&ctx.accounts.sentinel, &authority_key);
+        let mut total_applied: u64 = 0;
+        let mut total_fee: u64 = 0;
+
+        // First pass: check every entry and its account before writing any
+        let mut staged = Vec::with_capacity(updates.len());
+        for (i, update) in updates.iter().enumerate() {
+            require!(update.confidence <= 100, SentinelError::InvalidConfidence);
+
+            let account_info = &remaining[2 * i];
+            // Verify the account is owned by our program
+            require!(account_info.owner == ctx.program_id, SentinelError::InvalidAccount);
+            // Entries are validated against the stored record, so each may appear once
+            require!(
+                !remaining[..2 * i].iter().step_by(2).any(|a| a.key == account_info.key),
+                SentinelError::DuplicateBatchAccount
+            );
+
+            let data = account_info.try_borrow_data()?;
+            // Anchor discriminator is first 8 bytes — verify it matches SentimentRecord
+            let disc = &data[..8];
+            let expected = SentimentRecord::DISCRIMINATOR;
+            require!(disc == expected, SentinelError::InvalidAccount);
+
+            let record = SentimentRecord::try_deserialize(&mut &data[..])?;
+            // Owner and discriminator alone would admit a record at any address
+            let (expected, _) =
+                Pubkey::find_program_address(&[SENTIMENT_SEED, record.symbol.as_bytes()], ctx.program_id);
+            require_keys_eq!(account_info.key(), expected, SentinelError::WrongSentimentPda);
+            let config_info = &remaining[2 * i + 1];
+            let (expected, _) =
+                Pubkey::find_program_address(&[SYMBOL_CONFIG_SEED, record.symbol.as_bytes()], ctx.program_id);
+            require_keys_eq!(config_info.key(), expected, SentinelError::InvalidAccount);
+            let symbol_config = load_symbol_config(config_info, ctx.program_id)?;
+            require_market_open(config_info, ctx.program_id, now)?;
+            let symbol_scale = symbol_config.as_ref().map_or(SCALE_FACTOR_ONE, |c| c.scale_factor);
+            total_fee = total_fee.saturating_add(symbol_config.as_ref().map_or(0, |c| c.write_fee));
+
+            let score = normalize_score(&ctx.accounts.sentinel, &authority_key, update.score, symbol_scale);
+            require!((-100..=100).contains(&score), SentinelError::InvalidScore);
+            let score = score as i8;
+            require_timestamp_in_range(&ctx.accounts.sentinel, update.timestamp, now)?;
+            require!(update.timestamp > record.timestamp, SentinelError::StaleTimestamp);
+            require_direct_write(&record)?;
+            require_update_interval(&ctx.accounts.sentinel, &record, update.timestamp)?;
+            require_calm(&ctx.accounts.sentinel, &record, &authority_key)?;
+            require_score_delta(&mut ctx.accounts.sentinel, &record, score)?;
+            // Each entry is a write against the operator's rate limit
+            charge_rate_limit(
+                &mut ctx.accounts.sentinel,
+                &mut ctx.accounts.operator_stats,
+                authority_key,
+                ctx.bumps.operator_stats,
+                now,
+            )?;
+            staged.push((record, score));
+        }
+        charge_write_fee(
+            total_fee,
+            &ctx.accounts.authority,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+
+        // Second pass: apply and emit
+        for (i, (mut record, score)) in staged.into_iter().enumerate() {
+            let update = &updates[i];
+            let mut data = remaining[2 * i].try_borrow_mut_data()?;
+            let confidence = update.confidence.min(cap);
+
+            if provisional {
+                record_provisional(
+                    &mut ctx.accounts.sentinel,
+                    &mut record,
+                    score,
+                    confidence,
+                    update.volume,
+                    update.timestamp,
+                    authority_key,
+                );
+                let mut writer = &mut data[..];
+                record.try_serialize(&mut writer)?;
+                continue;
+            }
+
+            observe_volume(&mut ctx.accounts.sentinel, &mut record, update.volume);
+            record.score = score;
+            record.score_bps = score as i16 * 100;
+            record.confidence = confidence;
+            record.adjusted_score = adjusted_score(record.score, record.confidence);
+            record.last_update_slot = Clock::get()?.slot;
+            record.volume = update.volume;
+            record.timestamp = update.timestamp;
+            record.updater = authority_key;
+            record.update_count = record.update_count.saturating_add(1);
+            // Batch entries carry no note; don't leave the previous reading's
+            record.note.clear();
+            let hash = record.advance_hash_chain();
+            let out_of_turn = take_turn(&ctx.accounts.sentinel, &mut record, &authority_key);
+
+            let mut writer = &mut data[..];
+            record.try_serialize(&mut writer)?;
+
+            emit_event(&mut ctx.accounts.sentinel, SentimentUpdated {
+                symbol: record.symbol.clone(),
+                score,
+                raw_score: update.score,
+                confidence,
+                submitted_confidence: update.confidence,
+                volume: update.volume,
+                timestamp: update.timestamp,
+                updater: authority_key,
+                note: record.note.clone(),
+                out_of_turn,
+                adjusted_score: record.adjusted_score,
+                hash,
+            });
+
+            total_applied += 1;
+        }
+
+        // Update global counter
+        bump_total_updates(&mut ctx.accounts.sentinel, total_applied, now);
+
+        emit_event(&mut ctx.accounts.sentinel, BatchUpdateCompleted { count: total_applied as u8 });
+        Ok(())
+    }
+
+    /// Combine several readings for the same symbol (e.g. from client-side
+    /// sub-sources) into one confidence-weighted write instead of last-write-wins.
+    pub fn batch_aggregate_symbol(
+        ctx: Context<UpdateSentiment>,
+        symbol: String,
+        inputs: Vec<SentimentInput>,
+    ) -> Result<()> {
+        let sentinel = &ctx.accounts.sentinel;
+        require_unpaused(sentinel, PAUSE_UPDATE)?;
+        require!(
+            is_authority_or_operator(sentinel, &ctx.accounts.authority.key(), CAN_UPDATE | CAN_BATCH)
+                || holds_operator_scope(&ctx.accounts.operator_scope),
+            SentinelError::OperatorNotScoped
+        );
+        require!(inputs.len() >= 2, SentinelError::AggregateTooSmall);
+        require_market_open(&ctx.accounts.symbol_config, ctx.program_id, Clock::get()?.unix_timestamp)?;
+        require!(inputs.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
+        require!(ctx.accounts.sentiment.symbol == symbol, SentinelError::InvalidSymbol);
+        let authority_key = ctx.accounts.authority.key();
+        let symbol_config = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?;
+        let symbol_scale = symbol_config.as_ref().map_or(SCALE_FACTOR_ONE, |c| c.scale_factor);
+        let mut normalized = inputs.clone();
+        for input in normalized.iter_mut() {
+            input.score = normalize_score(&ctx.accounts.sentinel, &authority_key, input.score, symbol_scale);
+            require!((-100..=100).contains(&input.score), SentinelError::InvalidScore);
+            require!(input.confidence <= 100, SentinelError::InvalidConfidence);
+            require!(input.timestamp > 0, SentinelError::InvalidTimestamp);
+        }
+
+        let raw_score = aggregate_inputs(&inputs).score;
+        let combined = aggregate_inputs(&normalized);
+        let score = combined.score as i8;
+        let now = Clock::get()?.unix_timestamp;
+        require_timestamp_in_range(&ctx.accounts.sentinel, combined.timestamp, now)?;
+        charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, authority_key, ctx.bumps.operator_stats, now)?;
+        charge_write_fee(
+            symbol_config.as_ref().map_or(0, |c| c.write_fee),
+            &ctx.accounts.authority,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+        let cap = operator_confidence_cap(&ctx.accounts.sentinel, &authority_key);
+        let confidence = combined.confidence.min(cap);
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        require!(combined.timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+        require_direct_write(sentiment)?;
+        require_update_interval(&ctx.accounts.sentinel, sentiment, combined.timestamp)?;
+        require_calm(&ctx.accounts.sentinel, sentiment, &authority_key)?;
+        require_score_delta(&mut ctx.accounts.sentinel, sentiment, score)?;
+
+        if provisional {
+            record_provisional(
+                &mut ctx.accounts.sentinel,
+                sentiment,
+                score,
+                confidence,
+                combined.volume,
+                combined.timestamp,
+                authority_key,
+            );
+            return Ok(());
+        }
+
+        observe_volume(&mut ctx.accounts.sentinel, sentiment, combined.volume);
+        sentiment.score = score;
+        sentiment.score_bps = score as i16 * 100;
+        sentiment.confidence = confidence;
+        sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+        sentiment.last_update_slot = Clock::get()?.slot;
+        sentiment.volume = combined.volume;
+        sentiment.timestamp = combined.timestamp;
+        sentiment.updater = authority_key;
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        sentiment.last_idempotency_key = [0; 16];
+        sentiment.note.clear();
+        let hash = sentiment.advance_hash_chain();
+        let out_of_turn = take_turn(&ctx.accounts.sentinel, sentiment, &authority_key);
+
+        bump_total_updates(&mut ctx.accounts.sentinel, 1, now);
+
+        emit_event(&mut ctx.accounts.sentinel, SentimentUpdated {
+            symbol,
+            score,
+            raw_score,
+            confidence,
+            submitted_confidence: combined.confidence,
+            volume: combined.volume,
+            timestamp: combined.timestamp,
+            updater: authority_key,
+            note: sentiment.note.clone(),
+            out_of_turn,
+            adjusted_score: sentiment.adjusted_score,
+            hash,
+        });
+
+        Ok(())
+    }
+
+    /// Restrict updates of `symbol` to the UTC hours set in `active_hours`
+    /// (bit 0 = 00:00-00:59). `ALL_HOURS` restores round-the-clock updates.
+    pub fn set_active_hours(
+        ctx: Context<SetSymbolConfig>,
+        symbol: String,
+        active_hours: u32,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(active_hours <= ALL_HOURS, SentinelError::InvalidActiveHours);
+        let config = &mut ctx.accounts.symbol_config;
+        if config.symbol.is_empty() {
+            config.set_defaults(symbol, ctx.bumps.symbol_config);
+        }
+        config.active_hours = active_hours;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(active_hours as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Restrict which programs may CPI into the read instructions for `symbol`
+    /// (`score_display`, `next_update_time`). An empty list keeps the feed public.
+    pub fn set_reader_allowlist(
+        ctx: Context<SetSymbolConfig>,
+        symbol: String,
+        readers: Vec<Pubkey>,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(readers.len() <= MAX_READERS, SentinelError::TooManyReaders);
+        let config = &mut ctx.accounts.symbol_config;
+        if config.symbol.is_empty() {
+            config.set_defaults(symbol, ctx.bumps.symbol_config);
+        }
+        config.reader_allowlist = readers;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(config.reader_allowlist.len() as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Require `threshold` distinct operator approvals (via `propose_update`
+    /// and `approve_update`) before a new reading of `symbol` lands. Direct
+    /// writes are refused while it is set; 0 turns the requirement off.
+    pub fn set_consensus_threshold(
+        ctx: Context<SetConsensusThreshold>,
+        _symbol: String,
+        threshold: u8,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!((threshold as usize) <= MAX_APPROVERS, SentinelError::InvalidConsensusThreshold);
+        ctx.accounts.sentiment.consensus_threshold = threshold;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(threshold as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set the minimum seconds between readings of one symbol, on top of the
+    /// global `min_update_interval` (the stricter wins; 0 = global only), up
+    /// to `MAX_SYMBOL_COOLDOWN`. Open to admins with `SCOPE_CONFIG` and to
+    /// the symbol's scoped operators.
+    pub fn set_symbol_cooldown(ctx: Context<SetSymbolCooldown>, _symbol: String, cooldown: i64) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        if !holds_operator_scope(&ctx.accounts.operator_scope) {
+            require_scope(&ctx.accounts.sentinel, &authority, SCOPE_CONFIG)?;
+        }
+        require!((0..=MAX_SYMBOL_COOLDOWN).contains(&cooldown), SentinelError::InvalidSymbolCooldown);
+        ctx.accounts.sentiment.cooldown = cooldown;
+        emit_event(&mut ctx.accounts.sentinel, SymbolCooldownSet {
+            symbol: ctx.accounts.sentiment.symbol.clone(),
+            cooldown,
+            set_by: authority,
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            authority,
+            audit_value(cooldown as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set how often a symbol is expected to be updated, for `check_liveness`
+    /// (0 = not monitored). Admin only, so operators can't silence the
+    /// liveness alarm on their own symbols.
+    pub fn set_expected_interval(
+        ctx: Context<SetExpectedInterval>,
+        _symbol: String,
+        expected_interval: i64,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require_scope(&ctx.accounts.sentinel, &authority, SCOPE_CONFIG)?;
+        require!(expected_interval >= 0, SentinelError::InvalidExpectedInterval);
+        ctx.accounts.sentiment.expected_interval = expected_interval;
+        emit_event(&mut ctx.accounts.sentinel, ExpectedIntervalSet {
+            symbol: ctx.accounts.sentiment.symbol.clone(),
+            expected_interval,
+            set_by: authority,
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            authority,
+            audit_value(expected_interval as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Have `update_sentiment` snapshot this symbol into its history on every
+    /// reading, saving a separate `record_history` call. The history must
+    /// already exist.
+    pub fn set_auto_history(ctx: Context<SetAutoHistory>, _symbol: String, enabled: bool) -> Result<()> {
+        ctx.accounts.sentiment.auto_history = enabled;
+        emit_event(&mut ctx.accounts.sentinel, AutoHistorySet {
+            symbol: ctx.accounts.sentiment.symbol.clone(),
+            enabled,
+            set_by: ctx.accounts.authority.key(),
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
         Ok(())
     }
+
+    /// Emit `SentimentStalled` if the symbol has gone more than twice its
+    /// expected interval without a reading. Permissionless, so watchdogs can
+    /// alert on a dark operator without admin action.
+    pub fn check_liveness(ctx: Context<CheckLiveness>, _symbol: String) -> Result<()> {
+        let sentiment = &ctx.accounts.sentiment;
+        let interval = sentiment.expected_interval;
+        let idle = Clock::get()?.unix_timestamp.saturating_sub(sentiment.timestamp);
+        if interval == 0 || idle <= interval.saturating_mul(2) {
+            msg!("Sentiment is live");
+            return Ok(());
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, SentimentStalled {
+            symbol: sentiment.symbol.clone(),
+            // Measured from when the next reading was due
+            seconds_overdue: idle - interval,
+        });
+        Ok(())
+    }
+
+    /// Reserve and configure a symbol ahead of its first `store_sentiment`,
+    /// which then picks up these settings. Re-registering overwrites them.
+    pub fn register_symbol_config(
+        ctx: Context<RegisterSymbolConfig>,
+        symbol: String,
+        config: SymbolConfigInput,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+        require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+        require!(symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
+        if let Some(decimals) = config.decimals {
+            require!(decimals <= MAX_DISPLAY_DECIMALS, SentinelError::InvalidDisplayConfig);
+        }
+        require!(config.scale_factor > 0, SentinelError::InvalidScaleFactor);
+        require!(config.active_hours <= ALL_HOURS, SentinelError::InvalidActiveHours);
+        require!(config.metadata.len() <= MAX_METADATA_LEN, SentinelError::MetadataTooLong);
+        require!(
+            config.metadata.bytes().all(|b| (0x20..=0x7e).contains(&b)),
+            SentinelError::InvalidMetadata
+        );
+        require!(config.write_fee <= MAX_WRITE_FEE, SentinelError::InvalidWriteFee);
+        if config.write_fee != ctx.accounts.symbol_config.write_fee {
+            require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_FEES)?;
+        }
+
+        let account = &mut ctx.accounts.symbol_config;
+        if account.symbol.is_empty() {
+            account.set_defaults(symbol.clone(), ctx.bumps.symbol_config);
+        }
+        account.active_hours = config.active_hours;
+        account.decimals = config.decimals;
+        account.scale_factor = config.scale_factor;
+        account.metadata = config.metadata;
+        account.write_fee = config.write_fee;
+
+        let detail = audit_symbol(&symbol);
+        emit_event(&mut ctx.accounts.sentinel, SymbolConfigured {
+            symbol,
+            active_hours: account.active_hours,
+            decimals: account.decimals,
+            scale_factor: account.scale_factor,
+            write_fee: account.write_fee,
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            detail,
+        )?;
+        Ok(())
+    }
+
+    /// Bind `symbol` to its SPL `mint` as the canonical spelling, so lookalike
+    /// symbols can't be created while `permissionless_symbols` is off.
+    pub fn register_symbol(ctx: Context<RegisterSymbol>, symbol: String, mint: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+        require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+        require!(symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
+
+        let registry = &mut ctx.accounts.symbol_registry;
+        registry.symbol = symbol.clone();
+        registry.mint = mint;
+        registry.registered_by = ctx.accounts.authority.key();
+        registry.registered_at = Clock::get()?.unix_timestamp;
+        registry.bump = ctx.bumps.symbol_registry;
+
+        emit_event(&mut ctx.accounts.sentinel, SymbolRegistered { symbol, mint });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REGISTER_SYMBOL,
+            ctx.accounts.authority.key(),
+            mint.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Point `new_symbol` at the registered `old_symbol` after a rebrand, so
+    /// record and history reads under the new name find the old symbol's
+    /// data. Aliases resolve a single hop: the target can't itself be an
+    /// alias, and `new_symbol` can't be registered or hold a record of its
+    /// own. Since every target is registered, that also keeps `new_symbol`
+    /// from being some other alias's target.
+    pub fn alias_symbol(ctx: Context<AliasSymbol>, old_symbol: String, new_symbol: String) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(!new_symbol.is_empty(), SentinelError::EmptySymbol);
+        require!(new_symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+        require!(new_symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
+        require!(
+            new_symbol != old_symbol && ctx.accounts.target_alias.data_is_empty(),
+            SentinelError::AliasCycle
+        );
+        require!(
+            ctx.accounts.new_registry.data_is_empty() && ctx.accounts.new_sentiment.data_is_empty(),
+            SentinelError::SymbolInUse
+        );
+
+        let alias = &mut ctx.accounts.alias;
+        alias.alias = new_symbol.clone();
+        alias.target = old_symbol.clone();
+        alias.created_at = Clock::get()?.unix_timestamp;
+        alias.bump = ctx.bumps.alias;
+
+        let detail = audit_symbol(&new_symbol);
+        emit_event(&mut ctx.accounts.sentinel, SymbolAliased { old_symbol, new_symbol });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_ALIAS_SYMBOL,
+            ctx.accounts.authority.key(),
+            detail,
+        )?;
+        Ok(())
+    }
+
+    /// Bind a registered symbol to its Pyth price account. `store_sentiment`
+    /// only accepts this account as the symbol's price feed; the default key
+    /// unbinds it.
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>, symbol: String, feed: Pubkey) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.symbol_registry.price_feed = feed;
+        emit_event(&mut ctx.accounts.sentinel, PriceFeedSet { symbol, feed });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            feed.to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Revise only the confidence of an existing record, leaving score and
+    /// volume untouched. Subject to the same pause, market-hours and staleness
+    /// checks as a full update.
+    pub fn update_confidence(
+        ctx: Context<UpdateSentiment>,
+        symbol: String,
+        confidence: u8,
+        timestamp: i64,
+    ) -> Result<()> {
+        let sentinel = &ctx.accounts.sentinel;
+        require_unpaused(sentinel, PAUSE_UPDATE)?;
+        require_market_open(&ctx.accounts.symbol_config, ctx.program_id, Clock::get()?.unix_timestamp)?;
+        require!(ctx.accounts.sentiment.symbol == symbol, SentinelError::InvalidSymbol);
+        require!(confidence <= 100, SentinelError::InvalidConfidence);
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        require_timestamp_in_range(&ctx.accounts.sentinel, timestamp, now)?;
+        charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, authority_key, ctx.bumps.operator_stats, now)?;
+        let write_fee = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?.map_or(0, |c| c.write_fee);
+        charge_write_fee(
+            write_fee,
+            &ctx.accounts.authority,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+        let confidence = confidence.min(operator_confidence_cap(&ctx.accounts.sentinel, &authority_key));
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+        require_direct_write(sentiment)?;
+        require_update_interval(&ctx.accounts.sentinel, sentiment, timestamp)?;
+        require_calm(&ctx.accounts.sentinel, sentiment, &authority_key)?;
+
+        if provisional {
+            let (score, volume) = (sentiment.score, sentiment.volume);
+            record_provisional(&mut ctx.accounts.sentinel, sentiment, score, confidence, volume, timestamp, authority_key);
+            return Ok(());
+        }
+
+        let old_confidence = sentiment.confidence;
+        sentiment.confidence = confidence;
+        sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+        sentiment.last_update_slot = Clock::get()?.slot;
+        sentiment.timestamp = timestamp;
+        sentiment.updater = authority_key;
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        sentiment.last_idempotency_key = [0; 16];
+        let hash = sentiment.advance_hash_chain();
+
+        bump_total_updates(&mut ctx.accounts.sentinel, 1, now);
+
+        emit_event(&mut ctx.accounts.sentinel, ConfidenceUpdated {
+            symbol,
+            old_confidence,
+            confidence,
+            timestamp,
+            updater: authority_key,
+            hash,
+        });
+        Ok(())
+    }
+
+    /// Fold an operator's reading into the symbol's confidence-weighted mean
+    /// instead of replacing it. Readings within `contribution_window` seconds
+    /// of the window's first one are averaged; a later reading starts a new
+    /// window. Each key contributes at most once per window, and every reading
+    /// must be strictly newer than the last. Confidence becomes the mean
+    /// confidence of the window's sources.
+    pub fn contribute_sentiment(
+        ctx: Context<UpdateSentiment>,
+        symbol: String,
+        score: i16,
+        confidence: u8,
+        volume: u32,
+        timestamp: i64,
+    ) -> Result<()> {
+        let sentinel = &ctx.accounts.sentinel;
+        require_unpaused(sentinel, PAUSE_UPDATE)?;
+        require_market_open(&ctx.accounts.symbol_config, ctx.program_id, Clock::get()?.unix_timestamp)?;
+        require!(ctx.accounts.sentiment.symbol == symbol, SentinelError::InvalidSymbol);
+        let symbol_config = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?;
+        let symbol_scale = symbol_config.as_ref().map_or(SCALE_FACTOR_ONE, |c| c.scale_factor);
+        let score = normalize_score(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), score, symbol_scale);
+        require!((-100..=100).contains(&score), SentinelError::InvalidScore);
+        let score = score as i8;
+        require!(confidence <= 100, SentinelError::InvalidConfidence);
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        require_timestamp_in_range(&ctx.accounts.sentinel, timestamp, now)?;
+        charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, authority_key, ctx.bumps.operator_stats, now)?;
+        charge_write_fee(
+            symbol_config.as_ref().map_or(0, |c| c.write_fee),
+            &ctx.accounts.authority,
+            ctx.accounts.treasury.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+        let confidence = confidence.min(operator_confidence_cap(&ctx.accounts.sentinel, &authority_key));
+        let window = ctx.accounts.sentinel.contribution_window;
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+        require_direct_write(sentiment)?;
+        require_update_interval(&ctx.accounts.sentinel, sentiment, timestamp)?;
+        require_calm(&ctx.accounts.sentinel, sentiment, &authority_key)?;
+        require_score_delta(&mut ctx.accounts.sentinel, sentiment, score)?;
+
+        if provisional {
+            record_provisional(&mut ctx.accounts.sentinel, sentiment, score, confidence, volume, timestamp, authority_key);
+            return Ok(());
+        }
+
+        if sentiment.source_count == 0 || timestamp - sentiment.window_start > window {
+            sentiment.source_count = 0;
+            sentiment.window_start = timestamp;
+            sentiment.contrib_score_sum = 0;
+            sentiment.contrib_weight_sum = 0;
+            sentiment.contributors.clear();
+        }
+        require!(
+            !sentiment.contributors.contains(&authority_key),
+            SentinelError::DuplicateContribution
+        );
+        require!(
+            sentiment.contributors.len() < MAX_CONTRIBUTORS,
+            SentinelError::ContributionWindowFull
+        );
+        sentiment.contributors.push(authority_key);
+        let weight = confidence.max(1) as u32;
+        sentiment.source_count = sentiment.source_count.saturating_add(1);
+        sentiment.contrib_score_sum += score as i64 * weight as i64;
+        sentiment.contrib_weight_sum += weight;
+
+        observe_volume(&mut ctx.accounts.sentinel, sentiment, volume);
+        sentiment.score_bps = (sentiment.contrib_score_sum * 100 / sentiment.contrib_weight_sum as i64) as i16;
+        sentiment.score = (sentiment.score_bps / 100) as i8;
+        sentiment.confidence = (sentiment.contrib_weight_sum / sentiment.source_count as u32).min(100) as u8;
+        sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+        sentiment.last_update_slot = Clock::get()?.slot;
+        sentiment.volume = volume;
+        sentiment.timestamp = timestamp;
+        sentiment.updater = authority_key;
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        sentiment.last_idempotency_key = [0; 16];
+        sentiment.note.clear();
+        let hash = sentiment.advance_hash_chain();
+        let (running_score, source_count) = (sentiment.score, sentiment.source_count);
+
+        bump_total_updates(&mut ctx.accounts.sentinel, 1, now);
+
+        emit_event(&mut ctx.accounts.sentinel, SentimentContributed {
+            symbol,
+            contributor: authority_key,
+            running_score,
+            source_count,
+            hash,
+        });
+        Ok(())
+    }
+
+    /// Blend the oracle score with the community consensus using the
+    /// sentinel's `community_weight` and store it as `blended_score`. With no
+    /// community votes the oracle score is published as is. Permissionless.
+    pub fn publish_blended(ctx: Context<PublishBlended>, _symbol: String) -> Result<()> {
+        let weight = ctx.accounts.sentinel.community_weight as i64;
+        let aggregate = load_vote_aggregate(&ctx.accounts.aggregate, ctx.program_id)?
+            .filter(|a| a.vote_count > 0);
+        let sentiment = &mut ctx.accounts.sentiment;
+        let oracle = sentiment.score as i64 * 100; // hundredths, like consensus_score
+        let (community, vote_count) = aggregate.map_or((oracle, 0), |a| (a.consensus_score(), a.vote_count));
+        let blended = ((oracle * (100 - weight) + community * weight) / 10_000) as i8;
+        sentiment.blended_score = blended;
+
+        emit_event(&mut ctx.accounts.sentinel, BlendedPublished {
+            symbol: sentiment.symbol.clone(),
+            oracle_score: sentiment.score,
+            community_score: community,
+            vote_count,
+            community_weight: weight as u8,
+            blended_score: blended,
+        });
+        Ok(())
+    }
+
+    /// Drop a symbol's readings that came from keys that may no longer write
+    /// it: anyone but the root or a scoped authority, a global operator, or a
+    /// holder of this symbol's `OperatorScope` grant. The canonical reading
+    /// has a single contributor, so a removed operator's value is kept but its
+    /// confidence drops to zero until the next update; a removed operator's
+    /// provisional reading is cleared. Permissionless, since `remove_operator`
+    /// can't know which symbols an operator touched.
+    pub fn refresh_symbol(ctx: Context<RefreshSymbol>, _symbol: String) -> Result<()> {
+        let sentinel = &mut ctx.accounts.sentinel;
+        let sentiment = &mut ctx.accounts.sentiment;
+
+        let dropped_provisional = sentiment.provisional_updater != Pubkey::default()
+            && !is_current_updater(sentinel, &sentiment.provisional_updater, &ctx.accounts.provisional_scope);
+        if dropped_provisional {
+            sentiment.provisional_score = 0;
+            sentiment.provisional_confidence = 0;
+            sentiment.provisional_volume = 0;
+            sentiment.provisional_timestamp = 0;
+            sentiment.provisional_updater = Pubkey::default();
+        }
+
+        let dropped_canonical = sentiment.confidence > 0
+            && !is_current_updater(sentinel, &sentiment.updater, &ctx.accounts.updater_scope);
+        if dropped_canonical {
+            sentiment.confidence = 0;
+            sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+            sentiment.advance_hash_chain();
+        }
+
+        if sentiment.preferred_updater != Pubkey::default()
+            && !is_current_updater(sentinel, &sentiment.preferred_updater, &ctx.accounts.preferred_scope)
+        {
+            sentiment.preferred_updater = Pubkey::default();
+        }
+
+        emit_event(sentinel, CanonicalRefreshed {
+            symbol: sentiment.symbol.clone(),
+            score: sentiment.score,
+            confidence: sentiment.confidence,
+            dropped_canonical,
+            dropped_provisional,
+            hash: sentiment.prev_hash,
+        });
+        Ok(())
+    }
+
+    /// Take a historical snapshot of a sentiment record.
+    /// Stores the last `capacity` readings in a ring buffer for historical
+    /// queries. `capacity` only applies when the history is first created.
+    pub fn record_history(ctx: Context<RecordHistory>, symbol: String, capacity: u16) -> Result<()> {
+        let sentiment = &ctx.accounts.sentiment;
+        let history = &mut ctx.accounts.history;
+        let clock = Clock::get()?;
+
+        // Initialize on first use
+        if history.symbol.is_empty() {
+            require!(
+                (1..=MAX_HISTORY_CAP).contains(&(capacity as usize)),
+                SentinelError::InvalidHistoryCapacity
+            );
+            history.symbol = symbol;
+            history.bump = ctx.bumps.history;
+            history.count = 0;
+            history.head = 0;
+            history.capacity = capacity;
+            history.snapshots = vec![HistoryEntry::default(); capacity as usize];
+        } else {
+            // Older accounts kept an ever-growing `head` that wrapped at u16::MAX,
+            // which skipped slots; normalize it on first touch
+            history.head %= history.capacity.max(1);
+            assert_history_invariant(history)?;
+        }
+
+        push_history(history, snapshot_of(sentiment, clock.unix_timestamp));
+
+        emit_event(&mut ctx.accounts.sentinel, HistoryRecorded {
+            symbol: history.symbol.clone(),
+            entries: history.count,
+        });
+
+        Ok(())
+    }
+
+    /// Snapshot several symbols in one transaction. Pass `(sentiment, history)`
+    /// account pairs per symbol as remaining accounts; missing history
+    /// accounts are created with the default capacity.
+    pub fn batch_record_history<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchRecordHistory<'info>>,
+        symbols: Vec<String>,
+    ) -> Result<()> {
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_WRITES)?;
+        require!(!symbols.is_empty(), SentinelError::EmptyBatch);
+        require!(symbols.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
+        let remaining = ctx.remaining_accounts;
+        require!(remaining.len() == symbols.len() * 2, SentinelError::AccountMismatch);
+
+        let now = Clock::get()?.unix_timestamp;
+        let payer = ctx.accounts.authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for (i, symbol) in symbols.iter().enumerate() {
+            let sentiment = load_sentiment(&remaining[2 * i], symbol, ctx.program_id)?;
+            append_history_snapshot(
+                &remaining[2 * i + 1],
+                &sentiment,
+                &payer,
+                &system_program,
+                ctx.program_id,
+                now,
+            )?;
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, BatchHistoryRecorded { count: symbols.len() as u8 });
+        Ok(())
+    }
+
+    /// Rebuild a history account whose ring-buffer invariants are broken (e.g. a
+    /// `snapshots` vec of the wrong length). Recorded entries are kept in
+    /// chronological order, newest `capacity` first to survive. Authority only.
+    pub fn repair_history(ctx: Context<RepairHistory>, _symbol: String) -> Result<()> {
+        let history = &mut ctx.accounts.history;
+        if assert_history_invariant(history).is_ok() {
+            msg!("History is consistent, nothing to repair");
+            return Ok(());
+        }
+
+        let mut entries: Vec<HistoryEntry> = history.snapshots.iter()
+            .filter(|e| e.recorded_at != 0)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.recorded_at);
+        // A corrupt capacity falls back to the default size
+        let capacity = match history.capacity() {
+            c @ 1..=MAX_HISTORY_CAP => c,
+            _ => MAX_HISTORY,
+        };
+        let keep = entries.len().min(capacity);
+        let entries = &entries[entries.len() - keep..];
+
+        history.capacity = capacity as u16;
+        history.snapshots = vec![HistoryEntry::default(); capacity];
+        history.snapshots[..keep].clone_from_slice(entries);
+        history.count = keep as u16;
+        history.head = (keep % capacity) as u16;
+
+        emit_event(&mut ctx.accounts.sentinel, HistoryRepaired {
+            symbol: history.symbol.clone(),
+            recovered_entries: history.count,
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REPAIR_HISTORY,
+            ctx.accounts.authority.key(),
+            audit_symbol(&ctx.accounts.history.symbol),
+        )?;
+        Ok(())
+    }
+
+    /// Upgrade a history created before histories had a `capacity` field,
+    /// which every other history instruction fails to decode. The field is
+    /// spliced in after `count` as `MAX_HISTORY`, the fixed size those
+    /// accounts were created with; the payer funds the extra rent. Anyone
+    /// may call it, since the contents are unchanged.
+    pub fn migrate_history(ctx: Context<MigrateHistory>, _symbol: String) -> Result<()> {
+        let info = ctx.accounts.history.to_account_info();
+        require!(info.owner == ctx.program_id, SentinelError::InvalidAccount);
+        let old_len = info.data_len();
+        require!(old_len == HISTORY_V1_LEN, SentinelError::HistoryAlreadyMigrated);
+        let (split, tail) = {
+            let data = info.try_borrow_data()?;
+            require!(&data[..8] == SentimentHistory::DISCRIMINATOR, SentinelError::InvalidAccount);
+            let symbol_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
+            require!(symbol_len <= MAX_SYMBOL_LEN, SentinelError::InvalidAccount);
+            // discriminator, symbol, head and count keep their offsets
+            let split = 8 + 4 + symbol_len + 2 + 2;
+            (split, data[split..].to_vec())
+        };
+
+        resize_account(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            SentimentHistory::LEN,
+        )?;
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            data[split..split + 2].copy_from_slice(&(MAX_HISTORY as u16).to_le_bytes());
+            data[split + 2..split + 2 + tail.len()].copy_from_slice(&tail);
+        }
+
+        let history = SentimentHistory::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        emit_event(&mut ctx.accounts.sentinel, HistoryMigrated {
+            symbol: history.symbol,
+            old_len: old_len as u32,
+            new_len: SentimentHistory::LEN as u32,
+        });
+        Ok(())
+    }
+
+    /// Grow an account created under an older layout of its type to the
+    /// current one. Fields are only ever added just before `bump`, so an
+    /// older layout is a prefix of the type's `FIELDS` followed by its bump:
+    /// the stored fields keep their offsets, the added ones start at zero
+    /// (0, empty, `None`, `false`) and `bump` moves behind them. A record's
+    /// derived scores are filled in from its reading. The payer funds the
+    /// extra rent. Anyone may call it, since nothing stored changes.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        require!(info.owner == ctx.program_id, SentinelError::InvalidAccount);
+        let old_len = info.data_len();
+        let (new_len, bump_at, new_bump_at, bump) = {
+            let data = info.try_borrow_data()?;
+            let (fields, new_len) = data
+                .get(..8)
+                .and_then(migratable_layout)
+                .ok_or(SentinelError::UnsupportedLayout)?;
+            require!(old_len != new_len, SentinelError::LayoutAlreadyCurrent);
+            let kept = (0..fields.len())
+                .find(|&k| layout_len(&fields[..k]) == old_len)
+                .ok_or(SentinelError::UnsupportedLayout)?;
+            let mut bump_at = 8;
+            for field in &fields[..kept] {
+                bump_at += field.encoded_len(&data[bump_at..])?;
+            }
+            require!(bump_at < old_len, SentinelError::InvalidAccount);
+            let added: usize = fields[kept..].iter().map(|f| f.zero_len()).sum();
+            (new_len, bump_at, bump_at + added, data[bump_at])
+        };
+
+        resize_account(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_len,
+        )?;
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            data[bump_at..].fill(0);
+            data[new_bump_at] = bump;
+
+            if data[..8] == *SentimentRecord::DISCRIMINATOR {
+                let mut record = SentimentRecord::try_deserialize(&mut &data[..])?;
+                if record.score_bps == 0 {
+                    record.score_bps = record.score as i16 * 100;
+                }
+                record.adjusted_score = adjusted_score(record.score, record.confidence);
+                let mut writer = &mut data[..];
+                record.try_serialize(&mut writer)?;
+            }
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, AccountMigrated {
+            account: info.key(),
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+        });
+        Ok(())
+    }
+
+    /// Overwrite one recorded snapshot, addressed by chronological `index`
+    /// (0 = oldest). The corrected `recorded_at` must stay between its
+    /// neighbours'. Authority only.
+    pub fn correct_history_entry(
+        ctx: Context<RepairHistory>,
+        symbol: String,
+        index: u16,
+        corrected: HistoryEntry,
+    ) -> Result<()> {
+        require!((-100..=100).contains(&corrected.score), SentinelError::InvalidScore);
+        require!(corrected.confidence <= 100, SentinelError::InvalidConfidence);
+        let history = &mut ctx.accounts.history;
+        assert_history_invariant(history)?;
+        let count = history.count as usize;
+        let index = index as usize;
+        require!(index < count, SentinelError::InvalidHistoryIndex);
+
+        let capacity = history.capacity();
+        let oldest = (history.head as usize + capacity - count) % capacity;
+        let slot = |i: usize| (oldest + i) % capacity;
+        if index > 0 {
+            require!(
+                history.snapshots[slot(index - 1)].recorded_at <= corrected.recorded_at,
+                SentinelError::WouldBreakChronology
+            );
+        }
+        if index + 1 < count {
+            require!(
+                corrected.recorded_at <= history.snapshots[slot(index + 1)].recorded_at,
+                SentinelError::WouldBreakChronology
+            );
+        }
+        history.snapshots[slot(index)] = corrected;
+
+        let detail = audit_symbol(&symbol);
+        emit_event(&mut ctx.accounts.sentinel, HistoryCorrected { symbol, index: index as u16 });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_REPAIR_HISTORY,
+            ctx.accounts.authority.key(),
+            detail,
+        )?;
+        Ok(())
+    }
+
+    /// Time-weighted average score of the snapshots recorded in the last
+    /// `window_seconds`, in hundredths of a point. Each snapshot's score is
+    /// weighted by the gap to the next one in the window; with
+    /// `confidence_weighted` it is also weighted by the snapshot's confidence,
+    /// falling back to time only if every confidence in the window is zero.
+    pub fn compute_twap(
+        ctx: Context<ReadHistory>,
+        _symbol: String,
+        window_seconds: i64,
+        confidence_weighted: bool,
+    ) -> Result<i64> {
+        require!(window_seconds > 0, SentinelError::InvalidTwapWindow);
+        let history = &ctx.accounts.history;
+        assert_history_invariant(history)?;
+        let start = Clock::get()?.unix_timestamp.saturating_sub(window_seconds);
+        let samples: Vec<HistoryEntry> = history.chronological()
+            .into_iter()
+            .filter(|e| e.recorded_at >= start)
+            .collect();
+        require!(samples.len() >= 2, SentinelError::InsufficientHistory);
+
+        let (twap_score, weighted) = time_weighted_score(&samples, confidence_weighted);
+
+        emit_view_event(&ctx.accounts.sentinel, TwapComputed {
+            symbol: history.symbol.clone(),
+            twap_score,
+            sample_count: samples.len() as u16,
+            window_seconds,
+            confidence_weighted: weighted,
+        });
+        Ok(twap_score)
+    }
+
+    /// Emit the score and volume range and the mean score over every
+    /// recorded snapshot, skipping never-written (zeroed) slots.
+    pub fn compute_history_stats(ctx: Context<ReadHistory>, _symbol: String) -> Result<()> {
+        let history = &ctx.accounts.history;
+        assert_history_invariant(history)?;
+        let samples: Vec<HistoryEntry> = history.chronological()
+            .into_iter()
+            .filter(|e| e.recorded_at != 0)
+            .collect();
+        require!(!samples.is_empty(), SentinelError::InsufficientHistory);
+
+        let sum: i64 = samples.iter().map(|e| e.score as i64).sum();
+        emit_view_event(&ctx.accounts.sentinel, HistoryStats {
+            symbol: history.symbol.clone(),
+            min_score: samples.iter().map(|e| e.score).min().unwrap_or_default(),
+            max_score: samples.iter().map(|e| e.score).max().unwrap_or_default(),
+            avg_score: (sum / samples.len() as i64) as i8,
+            min_vol: samples.iter().map(|e| e.volume).min().unwrap_or_default(),
+            max_vol: samples.iter().map(|e| e.volume).max().unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    /// Emit one page of history as `HistorySnapshotEvent`s, oldest first: up
+    /// to `limit` (capped at `MAX_HISTORY_QUERY`) snapshots ending `start`
+    /// positions back from the newest. Lets light clients page through
+    /// history via logs instead of decoding the account.
+    pub fn query_history(ctx: Context<ReadHistory>, _symbol: String, start: u16, limit: u16) -> Result<()> {
+        let history = &ctx.accounts.history;
+        assert_history_invariant(history)?;
+        require!(start < history.count && limit > 0, SentinelError::InvalidRange);
+
+        let entries = history.chronological();
+        let end = entries.len() - start as usize;
+        let take = limit.min(MAX_HISTORY_QUERY).min(history.count - start) as usize;
+        for (i, entry) in entries[end - take..end].iter().enumerate() {
+            emit_view_event(&ctx.accounts.sentinel, HistorySnapshotEvent {
+                symbol: history.symbol.clone(),
+                position: start + (take - 1 - i) as u16,
+                score: entry.score,
+                confidence: entry.confidence,
+                volume: entry.volume,
+                timestamp: entry.timestamp,
+                recorded_at: entry.recorded_at,
+            });
+        }
+        Ok(())
+    }
+
+    /// Take a delta-encoded historical snapshot of a sentiment record.
+    /// Fits twice the entries of `record_history` in a similarly sized account.
+    pub fn record_delta_history(ctx: Context<RecordDeltaHistory>, symbol: String) -> Result<()> {
+        let sentiment = &ctx.accounts.sentiment;
+        let history = &mut ctx.accounts.history;
+        let clock = Clock::get()?;
+
+        // Initialize on first use
+        if history.symbol.is_empty() {
+            history.symbol = symbol;
+            history.bump = ctx.bumps.history;
+            history.slots = vec![DeltaEntry::default(); MAX_DELTA_HISTORY];
+            history.keyframes = vec![HistoryEntry::default(); MAX_DELTA_KEYFRAMES];
+        }
+
+        let keyframe = history.push(snapshot_of(sentiment, clock.unix_timestamp));
+
+        emit_event(&mut ctx.accounts.sentinel, DeltaHistoryRecorded {
+            symbol: history.symbol.clone(),
+            entries: history.count,
+            keyframe,
+        });
+
+        Ok(())
+    }
+
+    /// Reconstruct one page of delta-encoded history and emit it as
+    /// `HistorySnapshotEvent`s, paged exactly like `query_history`.
+    pub fn decode_delta_history(
+        ctx: Context<ReadDeltaHistory>,
+        _symbol: String,
+        start: u16,
+        limit: u16,
+    ) -> Result<()> {
+        let history = &ctx.accounts.history;
+        require!(start < history.count && limit > 0, SentinelError::InvalidRange);
+
+        let entries = history.decode();
+        let end = entries.len() - start as usize;
+        let take = limit.min(MAX_HISTORY_QUERY).min(history.count - start) as usize;
+        for (i, entry) in entries[end - take..end].iter().enumerate() {
+            emit_view_event(&ctx.accounts.sentinel, HistorySnapshotEvent {
+                symbol: history.symbol.clone(),
+                position: start + (take - 1 - i) as u16,
+                score: entry.score,
+                confidence: entry.confidence,
+                volume: entry.volume,
+                timestamp: entry.timestamp,
+                recorded_at: entry.recorded_at,
+            });
+        }
+        Ok(())
+    }
+
+    /// Take a snapshot into the varint-packed history of a sentiment record.
+    /// The oldest snapshots are folded into the base once the buffer fills.
+    pub fn record_compact_history(ctx: Context<RecordCompactHistory>, symbol: String) -> Result<()> {
+        let sentiment = &ctx.accounts.sentiment;
+        let history = &mut ctx.accounts.history;
+        let clock = Clock::get()?;
+
+        // Initialize on first use
+        if history.symbol.is_empty() {
+            history.symbol = symbol;
+            history.bump = ctx.bumps.history;
+        }
+
+        history.push(snapshot_of(sentiment, clock.unix_timestamp));
+
+        emit_event(&mut ctx.accounts.sentinel, CompactHistoryRecorded {
+            symbol: history.symbol.clone(),
+            entries: history.count,
+            bytes: history.data.len() as u16,
+        });
+
+        Ok(())
+    }
+
+    /// Reconstruct one page of compact history and emit it as
+    /// `HistorySnapshotEvent`s, paged exactly like `query_history`.
+    pub fn decode_compact_history(
+        ctx: Context<ReadCompactHistory>,
+        _symbol: String,
+        start: u16,
+        limit: u16,
+    ) -> Result<()> {
+        let history = &ctx.accounts.history;
+        require!(start < history.count && limit > 0, SentinelError::InvalidRange);
+
+        let entries = history.decode();
+        let end = entries.len() - start as usize;
+        let take = limit.min(MAX_HISTORY_QUERY).min(history.count - start) as usize;
+        for (i, entry) in entries[end - take..end].iter().enumerate() {
+            emit_view_event(&ctx.accounts.sentinel, HistorySnapshotEvent {
+                symbol: history.symbol.clone(),
+                position: start + (take - 1 - i) as u16,
+                score: entry.score,
+                confidence: entry.confidence,
+                volume: entry.volume,
+                timestamp: entry.timestamp,
+                recorded_at: entry.recorded_at,
+            });
+        }
+        Ok(())
+    }
+
+    /// Pause the oracle and record a forensic history snapshot for each symbol
+    /// in one atomic step. Pass `(sentiment, history)` account pairs per symbol
+    /// as remaining accounts, followed by the audit log when auditing is on;
+    /// missing history accounts are created.
+    pub fn pause_and_snapshot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PauseAndSnapshot<'info>>,
+        symbols: Vec<String>,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_PAUSE)?;
+        require!(!symbols.is_empty(), SentinelError::EmptyBatch);
+        require!(symbols.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
+        let remaining = ctx.remaining_accounts;
+        let pairs = symbols.len() * 2;
+        require!(
+            remaining.len() == pairs || remaining.len() == pairs + 1,
+            SentinelError::AccountMismatch
+        );
+
+        ctx.accounts.sentinel.pause_flags |= PAUSE_WRITES;
+        let now = Clock::get()?.unix_timestamp;
+        let payer = ctx.accounts.authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        for (i, symbol) in symbols.iter().enumerate() {
+            let sentiment = load_sentiment(&remaining[2 * i], symbol, ctx.program_id)?;
+            append_history_snapshot(
+                &remaining[2 * i + 1],
+                &sentiment,
+                &payer,
+                &system_program,
+                ctx.program_id,
+                now,
+            )?;
+        }
+
+        let pause_flags = ctx.accounts.sentinel.pause_flags;
+        emit_event(&mut ctx.accounts.sentinel, PauseToggled { paused: true, pause_flags });
+        emit_event(&mut ctx.accounts.sentinel, PausedWithSnapshot { count: symbols.len() as u8 });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            &remaining[pairs..],
+            ctx.program_id,
+            AUDIT_SET_PAUSED,
+            ctx.accounts.authority.key(),
+            audit_value(pause_flags as u64),
+        )?;
+        Ok(())
+    }
+
+    // ===== Operator Value Disputes =====
+
+    /// Open a dispute over a symbol's current value for operators to vote on.
+    pub fn open_value_dispute(ctx: Context<OpenValueDispute>, symbol: String) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        require!(!dispute.open, SentinelError::DisputeAlreadyOpen);
+
+        dispute.symbol = symbol.clone();
+        dispute.opener = ctx.accounts.authority.key();
+        dispute.opened_at = Clock::get()?.unix_timestamp;
+        dispute.open = true;
+        dispute.votes = Vec::new();
+        dispute.bump = ctx.bumps.dispute;
+
+        emit_event(&mut ctx.accounts.sentinel, ValueDisputeOpened {
+            symbol,
+            opener: dispute.opener,
+            disputed_score: ctx.accounts.sentiment.score,
+        });
+        Ok(())
+    }
+
+    /// Cast an active operator's vote for the correct value of a disputed symbol.
+    pub fn operator_dispute_vote(ctx: Context<OperatorDisputeVote>, value: i8) -> Result<()> {
+        require!((-100..=100).contains(&value), SentinelError::InvalidScore);
+        let operator = ctx.accounts.operator.key();
+        require!(
+            is_active_operator(&ctx.accounts.sentinel, &operator),
+            SentinelError::Unauthorized
+        );
+
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.open, SentinelError::DisputeNotOpen);
+        require!(
+            !dispute.votes.iter().any(|v| v.operator == operator),
+            SentinelError::AlreadyVoted
+        );
+        dispute.votes.push(DisputeVote { operator, value });
+
+        emit_event(&mut ctx.accounts.sentinel, OperatorDisputeVoted {
+            symbol: dispute.symbol.clone(),
+            operator,
+            value,
+        });
+        Ok(())
+    }
+
+    /// Resolve a dispute once quorum has voted, setting the record's score to
+    /// the median of the operator votes.
+    pub fn resolve_value_dispute(ctx: Context<ResolveValueDispute>, symbol: String) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.open, SentinelError::DisputeNotOpen);
+        require!(
+            dispute.votes.len() >= dispute_quorum(&ctx.accounts.sentinel),
+            SentinelError::QuorumNotReached
+        );
+
+        let mut values: Vec<i8> = dispute.votes.iter().map(|v| v.value).collect();
+        let resolved = median_score(&mut values);
+        dispute.open = false;
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        let old_score = sentiment.score;
+        let confidence_before = sentiment.confidence;
+        let confidence_after = spread_penalty(
+            confidence_before,
+            &values,
+            ctx.accounts.sentinel.spread_penalty_factor,
+        );
+        sentiment.score = resolved;
+        sentiment.score_bps = resolved as i16 * 100;
+        sentiment.confidence = confidence_after;
+        sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+        sentiment.last_update_slot = Clock::get()?.slot;
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        let hash = sentiment.advance_hash_chain();
+
+        emit_event(&mut ctx.accounts.sentinel, ValueDisputeResolved {
+            symbol,
+            old_score,
+            new_score: resolved,
+            votes: dispute.votes.len() as u8,
+            confidence_before,
+            confidence_after,
+            hash,
+        });
+        Ok(())
+    }
+
+    // ===== Community Disputes =====
+
+    /// Set the reputation a profile needs to open a community dispute.
+    pub fn set_min_dispute_rep(ctx: Context<AdminAction>, min_reputation: u16) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.min_dispute_rep = min_reputation;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(min_reputation as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Flag a symbol's current reading as suspicious. Needs a profile with
+    /// at least `min_dispute_rep` reputation; one open dispute per symbol.
+    pub fn open_dispute(ctx: Context<OpenDispute>, symbol: String, reason_code: u8) -> Result<()> {
+        require!(
+            ctx.accounts.profile.reputation >= ctx.accounts.sentinel.min_dispute_rep,
+            SentinelError::DisputeReputationTooLow
+        );
+        let dispute = &mut ctx.accounts.dispute;
+        require!(!dispute.open, SentinelError::DisputeAlreadyOpen);
+
+        let sentiment = &mut ctx.accounts.sentiment;
+        dispute.symbol = symbol.clone();
+        dispute.disputer = ctx.accounts.user.key();
+        dispute.reason_code = reason_code;
+        dispute.disputed_score = sentiment.score;
+        dispute.opened_at = Clock::get()?.unix_timestamp;
+        dispute.open = true;
+        dispute.bump = ctx.bumps.dispute;
+        sentiment.disputed = true;
+
+        emit_event(&mut ctx.accounts.sentinel, DisputeOpened {
+            symbol,
+            disputer: dispute.disputer,
+            reason_code,
+            disputed_score: dispute.disputed_score,
+        });
+        Ok(())
+    }
+
+    /// Close a symbol's community dispute and clear its `disputed` flag.
+    /// The disputer gains `DISPUTE_REPUTATION_DELTA` reputation if upheld and
+    /// loses as much if rejected, provided their profile still exists.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.open, SentinelError::DisputeNotOpen);
+        dispute.open = false;
+        ctx.accounts.sentiment.disputed = false;
+
+        let info = ctx.accounts.profile.to_account_info();
+        let mut updated = None;
+        if info.owner == ctx.program_id && !info.data_is_empty() {
+            let mut data = info.try_borrow_mut_data()?;
+            let mut profile = UserProfile::try_deserialize(&mut &data[..])?;
+            profile.reputation = if upheld {
+                capped_reward(profile.reputation, DISPUTE_REPUTATION_DELTA, ctx.accounts.sentinel.rep_cap)
+            } else {
+                profile.reputation.saturating_sub(DISPUTE_REPUTATION_DELTA)
+            };
+            let mut writer = &mut data[..];
+            profile.try_serialize(&mut writer)?;
+            updated = Some(profile);
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, DisputeResolved {
+            symbol: dispute.symbol.clone(),
+            disputer: dispute.disputer,
+            upheld,
+            disputer_reputation: updated.as_ref().map(|p| p.reputation),
+        });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_RESOLVE_DISPUTE,
+            ctx.accounts.authority.key(),
+            ctx.accounts.dispute.disputer.to_bytes(),
+        )?;
+        match updated {
+            Some(profile) => update_leaderboard(
+                &mut ctx.accounts.sentinel,
+                &ctx.accounts.leaderboard,
+                ctx.accounts.slot_hashes.as_ref(),
+                ctx.program_id,
+                &profile,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    // ===== Social Functions =====
+
+    /// Create the caller's profile. The username is claimed case-insensitively
+    /// through a `UsernameClaim` PDA, so a taken name fails here.
+    pub fn create_profile(ctx: Context<CreateProfile>, username: String) -> Result<()> {
+        require!(!username.is_empty(), SentinelError::EmptyUsername);
+        require!(username.len() <= MAX_USERNAME_LEN, SentinelError::UsernameTooLong);
+        require!(username.chars().all(|c| c.is_alphanumeric() || c == '_'), SentinelError::InvalidUsername);
+
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        profile.owner = ctx.accounts.user.key();
+        profile.username = username;
+        profile.predictions_made = 0;
+        profile.correct_predictions = 0;
+        profile.reputation = 100;
+        profile.created_at = clock.unix_timestamp;
+        profile.last_active = clock.unix_timestamp;
+        profile.open_predictions = 0;
+        profile.decayed_through = clock.unix_timestamp;
+        profile.follower_count = 0;
+        profile.following_count = 0;
+        profile.bump = ctx.bumps.profile;
+
+        let claim = &mut ctx.accounts.username_claim;
+        claim.owner = profile.owner;
+        claim.username = profile.username.clone();
+        claim.bump = ctx.bumps.username_claim;
+
+        // A closed predecessor's reputation caps the fresh profile's starting value
+        let tombstone = &ctx.accounts.tombstone;
+        if tombstone.owner == ctx.program_id && !tombstone.data_is_empty() {
+            let data = tombstone.try_borrow_data()?;
+            let tombstone = ReputationTombstone::try_deserialize(&mut &data[..])?;
+            profile.reputation = profile.reputation.min(tombstone.reputation);
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, UsernameClaimed {
+            user: profile.owner,
+            username: profile.username.clone(),
+        });
+        Ok(())
+    }
+
+    /// Close the caller's profile and reclaim rent, dropping it from the
+    /// leaderboard. Fails while predictions are open. Votes and `Follow`
+    /// accounts on either side are left in place; both ends can still
+    /// unfollow after the profile is gone.
+    pub fn close_profile(ctx: Context<CloseProfile>) -> Result<()> {
+        let profile = &ctx.accounts.profile;
+        require!(profile.open_predictions == 0, SentinelError::ProfileHasActivity);
+
+        if ctx.accounts.sentinel.reputation_tombstones {
+            let bump = ctx.bumps.tombstone.ok_or(SentinelError::TombstoneRequired)?;
+            let tombstone = ctx.accounts.tombstone.as_mut().ok_or(SentinelError::TombstoneRequired)?;
+            tombstone.user = profile.owner;
+            tombstone.reputation = profile.reputation;
+            tombstone.bump = bump;
+        }
+
+        // Profiles created before usernames were claimed have no claim to release
+        let claim = ctx.accounts.username_claim.to_account_info();
+        if held_username_claim(&claim, ctx.program_id, &profile.owner)?.is_some() {
+            let user = ctx.accounts.user.to_account_info();
+            **user.try_borrow_mut_lamports()? += claim.lamports();
+            **claim.try_borrow_mut_lamports()? = 0;
+            claim.assign(&System::id());
+            claim.resize(0)?;
+        }
+
+        evict_from_leaderboard(
+            &mut ctx.accounts.sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.program_id,
+            profile.owner,
+        )?;
+        emit_event(&mut ctx.accounts.sentinel, ProfileClosed {
+            user: profile.owner,
+            username: profile.username.clone(),
+        });
+        Ok(())
+    }
+
+    /// Charge an idle profile one reputation point for each full decay period
+    /// since it was last active or last decayed, down to the configured floor.
+    /// Decay does not count as activity. Callable by anyone.
+    pub fn decay_reputation(ctx: Context<DecayReputation>) -> Result<()> {
+        let sentinel = &mut ctx.accounts.sentinel;
+        let profile = &mut ctx.accounts.profile;
+        let now = Clock::get()?.unix_timestamp;
+        let since = profile.last_active.max(profile.decayed_through);
+        let periods = now.saturating_sub(since) / sentinel.reputation_decay_period;
+        if periods <= 0 {
+            msg!("Profile is not idle long enough to decay");
+            return Ok(());
+        }
+        profile.decayed_through = since + periods * sentinel.reputation_decay_period;
+
+        let old_reputation = profile.reputation;
+        if old_reputation > sentinel.reputation_floor {
+            profile.reputation = old_reputation
+                .saturating_sub(periods.min(u16::MAX as i64) as u16)
+                .max(sentinel.reputation_floor);
+        }
+
+        emit_event(sentinel, ReputationDecayed {
+            user: profile.owner,
+            old_reputation,
+            new_reputation: profile.reputation,
+        });
+        update_leaderboard(
+            sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.accounts.slot_hashes.as_ref(),
+            ctx.program_id,
+            profile,
+        )
+    }
+
+    /// Move a profile and all its stats to a new wallet. Both wallets sign; the
+    /// new owner funds the new account and the old one gets its rent back.
+    /// `Follow` edges stay keyed by the old wallet, so the new profile starts
+    /// with no followers or follows.
+    pub fn transfer_profile(ctx: Context<TransferProfile>) -> Result<()> {
+        let old = &ctx.accounts.profile;
+        require!(old.open_predictions == 0, SentinelError::HasOpenStakes);
+
+        let new_owner = ctx.accounts.new_owner.key();
+        let profile = &mut ctx.accounts.new_profile;
+        profile.owner = new_owner;
+        profile.username = old.username.clone();
+        profile.predictions_made = old.predictions_made;
+        profile.correct_predictions = old.correct_predictions;
+        profile.reputation = old.reputation;
+        profile.created_at = old.created_at;
+        profile.last_active = old.last_active;
+        profile.open_predictions = old.open_predictions;
+        profile.decayed_through = old.decayed_through;
+        profile.follower_count = 0;
+        profile.following_count = 0;
+        profile.bump = ctx.bumps.new_profile;
+        let claim = ctx.accounts.username_claim.to_account_info();
+        if let Some(mut held) = held_username_claim(&claim, ctx.program_id, &old.owner)? {
+            held.owner = new_owner;
+            let mut data = claim.try_borrow_mut_data()?;
+            let mut writer = &mut data[..];
+            held.try_serialize(&mut writer)?;
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, ProfileTransferred { from: old.owner, to: new_owner });
+        evict_from_leaderboard(&mut ctx.accounts.sentinel, &ctx.accounts.leaderboard, ctx.program_id, old.owner)?;
+        update_leaderboard(
+            &mut ctx.accounts.sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.accounts.slot_hashes.as_ref(),
+            ctx.program_id,
+            &ctx.accounts.new_profile,
+        )
+    }
+
+    /// Follow another profile. A second follow of the same user fails because
+    /// the `Follow` PDA already exists.
+    pub fn follow_user(ctx: Context<FollowUser>, followee: Pubkey) -> Result<()> {
+        let follower = ctx.accounts.user.key();
+        require!(follower != followee, SentinelError::CannotFollowSelf);
+
+        let follow = &mut ctx.accounts.follow;
+        follow.follower = follower;
+        follow.followee = followee;
+        follow.followed_at = Clock::get()?.unix_timestamp;
+        follow.bump = ctx.bumps.follow;
+
+        let profile = &mut ctx.accounts.profile;
+        profile.following_count = profile.following_count.saturating_add(1);
+        let followee_profile = &mut ctx.accounts.followee_profile;
+        followee_profile.follower_count = followee_profile.follower_count.saturating_add(1);
+
+        emit_event(&mut ctx.accounts.sentinel, UserFollowed { follower, followee });
+        Ok(())
+    }
+
+    /// Stop following `followee`, closing the `Follow` account and refunding
+    /// its rent to the follower. Omit `profile` or `followee_profile` if it has
+    /// been closed or transferred away.
+    pub fn unfollow_user(ctx: Context<UnfollowUser>, followee: Pubkey) -> Result<()> {
+        if let Some(profile) = ctx.accounts.profile.as_mut() {
+            profile.following_count = profile.following_count.saturating_sub(1);
+        }
+        if let Some(followee_profile) = ctx.accounts.followee_profile.as_mut() {
+            followee_profile.follower_count = followee_profile.follower_count.saturating_sub(1);
+        }
+
+        emit_event(&mut ctx.accounts.sentinel, UserUnfollowed {
+            follower: ctx.accounts.user.key(),
+            followee,
+        });
+        Ok(())
+    }
+
+    /// Subscribe to alerts on `symbol`. `volume_threshold` (0 = off) adds a
+    /// volume trigger next to the score one, and `cooldown` is the minimum
+    /// gap in seconds between two alerts.
+    pub fn subscribe_token(
+        ctx: Context<SubscribeToken>,
+        symbol: String,
+        direction: i8,
+        alert_threshold: u8,
+        volume_threshold: u32,
+        cooldown: i64,
+    ) -> Result<()> {
+        validate_subscription(&symbol, direction, alert_threshold)?;
+        require!(cooldown >= 0, SentinelError::InvalidCooldown);
+        ctx.accounts.subscription.bump = ctx.bumps.subscription;
+        ctx.accounts.subscription.volume_threshold = volume_threshold;
+        ctx.accounts.subscription.cooldown = cooldown;
+        open_subscription(
+            &mut ctx.accounts.sentinel,
+            &mut ctx.accounts.subscription,
+            ctx.accounts.user.key(),
+            symbol,
+            direction,
+            alert_threshold,
+        )
+    }
+
+    /// Change the minimum gap in seconds between two alerts on one of the
+    /// caller's subscriptions. Takes effect from the last alert sent.
+    pub fn set_cooldown(ctx: Context<SetCooldown>, cooldown: i64) -> Result<()> {
+        require!(cooldown >= 0, SentinelError::InvalidCooldown);
+        ctx.accounts.subscription.cooldown = cooldown;
+        emit_event(&mut ctx.accounts.sentinel, AlertCooldownSet {
+            user: ctx.accounts.user.key(),
+            symbol: ctx.accounts.subscription.symbol.clone(),
+            cooldown,
+        });
+        Ok(())
+    }
+
+    pub fn unsubscribe_token(ctx: Context<Unsubscribe>) -> Result<()> {
+        // Account is closed via the close constraint
+        emit_event(&mut ctx.accounts.sentinel, Unsubscribed {
+            user: ctx.accounts.user.key(),
+            symbol: ctx.accounts.subscription.symbol.clone(),
+        });
+        Ok(())
+    }
+
+    /// Watch `symbol` from the user's single `SubscriptionList`, created on
+    /// first use and grown one entry at a time, so many symbols cost one
+    /// account instead of one `Subscription` each.
+    pub fn add_watch(ctx: Context<AddWatch>, symbol: String, direction: i8, threshold: u8) -> Result<()> {
+        validate_subscription(&symbol, direction, threshold)?;
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_SUBSCRIBE)?;
+        let user = ctx.accounts.user.key();
+        let list = &mut ctx.accounts.sub_list;
+        list.user = user;
+        list.bump = ctx.bumps.sub_list;
+        require!(!list.subs.iter().any(|s| s.symbol == symbol), SentinelError::WatchAlreadyExists);
+        require!(list.subs.len() < MAX_SUBSCRIPTIONS, SentinelError::TooManySubscriptions);
+        list.subs.push(SymbolSub { symbol: symbol.clone(), direction, threshold });
+
+        resize_account(
+            &ctx.accounts.sub_list.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            SubscriptionList::space(ctx.accounts.sub_list.subs.len()),
+        )?;
+        emit_event(&mut ctx.accounts.sentinel, WatchAdded { user, symbol, direction, threshold });
+        Ok(())
+    }
+
+    /// Change the direction and threshold of a watched symbol.
+    pub fn update_watch(ctx: Context<ModifyWatch>, symbol: String, direction: i8, threshold: u8) -> Result<()> {
+        validate_subscription(&symbol, direction, threshold)?;
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_SUBSCRIBE)?;
+        let sub = ctx.accounts.sub_list.subs
+            .iter_mut()
+            .find(|s| s.symbol == symbol)
+            .ok_or(SentinelError::WatchNotFound)?;
+        sub.direction = direction;
+        sub.threshold = threshold;
+        emit_event(&mut ctx.accounts.sentinel, WatchUpdated {
+            user: ctx.accounts.user.key(),
+            symbol,
+            direction,
+            threshold,
+        });
+        Ok(())
+    }
+
+    /// Stop watching `symbol`, shrinking the list and refunding its rent.
+    /// Removing the last symbol closes the list.
+    pub fn remove_watch(ctx: Context<ModifyWatch>, symbol: String) -> Result<()> {
+        let list = &mut ctx.accounts.sub_list;
+        let index = list.subs.iter().position(|s| s.symbol == symbol).ok_or(SentinelError::WatchNotFound)?;
+        list.subs.remove(index);
+
+        if list.subs.is_empty() {
+            list.close(ctx.accounts.user.to_account_info())?;
+        } else {
+            resize_account(
+                &ctx.accounts.sub_list.to_account_info(),
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                SubscriptionList::space(ctx.accounts.sub_list.subs.len()),
+            )?;
+        }
+        emit_event(&mut ctx.accounts.sentinel, WatchRemoved { user: ctx.accounts.user.key(), symbol });
+        Ok(())
+    }
+
+    /// Permissionless crank: compare the symbol's current reading against a
+    /// subscription and emit `AlertTriggered` if the score (in the subscribed
+    /// direction) or the volume crosses its threshold. A triggered alert that
+    /// lands inside the subscription's cooldown emits `AlertSuppressed`
+    /// instead; does nothing when no trigger is met or the reading was already
+    /// alerted on.
+    pub fn check_alert(ctx: Context<CheckAlert>) -> Result<()> {
+        let sentiment = &ctx.accounts.sentiment;
+        let subscription = &mut ctx.accounts.subscription;
+        let now = Clock::get()?.unix_timestamp;
+        if sentiment.timestamp == 0 || sentiment.timestamp == subscription.alerted_reading {
+            return Ok(());
+        }
+        let reason = alert_reason(subscription, sentiment);
+        if reason == 0 {
+            return Ok(());
+        }
+
+        let cooldown_ends = subscription.last_alert.saturating_add(subscription.cooldown);
+        if subscription.last_alert > 0 && now < cooldown_ends {
+            emit_event(&mut ctx.accounts.sentinel, AlertSuppressed {
+                user: subscription.user,
+                symbol: subscription.symbol.clone(),
+                seconds_remaining: cooldown_ends - now,
+            });
+            return Ok(());
+        }
+        subscription.last_alert = now;
+        subscription.alerted_reading = sentiment.timestamp;
+        emit_event(&mut ctx.accounts.sentinel, AlertTriggered {
+            user: subscription.user,
+            symbol: subscription.symbol.clone(),
+            reason,
+        });
+        Ok(())
+    }
+
+    pub fn vote_sentiment(
+        ctx: Context<VoteSentiment>,
+        symbol: String,
+        score: i8,
+        confidence: u8,
+    ) -> Result<()> {
+        validate_vote(&symbol, score, confidence)?;
+        require_tracked(&ctx.accounts.sentinel, &ctx.accounts.sentiment, ctx.program_id)?;
+        require_gate_balance(
+            &ctx.accounts.sentinel,
+            ctx.accounts.gate_token_account.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+        ctx.accounts.vote.bump = ctx.bumps.vote;
+        ctx.accounts.aggregate.bump = ctx.bumps.aggregate;
+        cast_vote(
+            &mut ctx.accounts.sentinel,
+            &mut ctx.accounts.vote,
+            &mut ctx.accounts.profile,
+            &mut ctx.accounts.aggregate,
+            symbol,
+            score,
+            confidence,
+        )
+    }
+
+    /// Cast a first community vote and subscribe to the same symbol in one
+    /// transaction. Both inputs are validated up front, so either both
+    /// accounts are created or neither is.
+    pub fn store_and_subscribe(
+        ctx: Context<StoreAndSubscribe>,
+        symbol: String,
+        score: i8,
+        confidence: u8,
+        direction: i8,
+        alert_threshold: u8,
+    ) -> Result<()> {
+        validate_vote(&symbol, score, confidence)?;
+        validate_subscription(&symbol, direction, alert_threshold)?;
+        require_tracked(&ctx.accounts.sentinel, &ctx.accounts.sentiment, ctx.program_id)?;
+        require_gate_balance(
+            &ctx.accounts.sentinel,
+            ctx.accounts.gate_token_account.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+
+        ctx.accounts.vote.bump = ctx.bumps.vote;
+        ctx.accounts.aggregate.bump = ctx.bumps.aggregate;
+        cast_vote(
+            &mut ctx.accounts.sentinel,
+            &mut ctx.accounts.vote,
+            &mut ctx.accounts.profile,
+            &mut ctx.accounts.aggregate,
+            symbol.clone(),
+            score,
+            confidence,
+        )?;
+
+        ctx.accounts.subscription.bump = ctx.bumps.subscription;
+        open_subscription(
+            &mut ctx.accounts.sentinel,
+            &mut ctx.accounts.subscription,
+            ctx.accounts.user.key(),
+            symbol,
+            direction,
+            alert_threshold,
+        )
+    }
+
+    /// Admin override: rule a user's prediction correct or not and adjust
+    /// reputation. Structured predictions settle themselves through
+    /// `resolve_prediction`; this is for judgements the oracle can't make.
+    /// Passing the vote being judged records the outcome on it for per-symbol stats.
+    pub fn judge_prediction(
+        ctx: Context<JudgePrediction>,
+        correct: bool,
+    ) -> Result<()> {
+        if let Some(vote) = ctx.accounts.vote.as_mut() {
+            require!(!vote.resolved, SentinelError::VoteAlreadyResolved);
+            vote.resolved = true;
+            vote.correct = correct;
+        }
+
+        apply_resolution(&mut ctx.accounts.sentinel, &mut ctx.accounts.profile, correct);
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_JUDGE_PREDICTION,
+            ctx.accounts.authority.key(),
+            ctx.accounts.profile.owner.to_bytes(),
+        )?;
+        update_leaderboard(
+            &mut ctx.accounts.sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.accounts.slot_hashes.as_ref(),
+            ctx.program_id,
+            &ctx.accounts.profile,
+        )
+    }
+
+    /// Resolve a user's vote on `symbol` against the observed `actual_score`:
+    /// correct if the prediction is within `accuracy_tolerance` points.
+    pub fn resolve_prediction_against(
+        ctx: Context<ResolvePredictionAgainst>,
+        symbol: String,
+        actual_score: i8,
+    ) -> Result<()> {
+        require!((-100..=100).contains(&actual_score), SentinelError::InvalidScore);
+        let tolerance = ctx.accounts.sentinel.accuracy_tolerance;
+        let vote = &mut ctx.accounts.vote;
+        require!(!vote.resolved, SentinelError::VoteAlreadyResolved);
+
+        let error = (vote.score as i16 - actual_score as i16).unsigned_abs() as u8;
+        let correct = error <= tolerance;
+        vote.resolved = true;
+        vote.correct = correct;
+
+        emit_event(&mut ctx.accounts.sentinel, PredictionScored {
+            user: vote.voter,
+            symbol,
+            predicted: vote.score,
+            actual: actual_score,
+            error,
+            tolerance,
+            expired: false,
+        });
+        apply_resolution(&mut ctx.accounts.sentinel, &mut ctx.accounts.profile, correct);
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_JUDGE_PREDICTION,
+            ctx.accounts.authority.key(),
+            ctx.accounts.profile.owner.to_bytes(),
+        )?;
+        update_leaderboard(
+            &mut ctx.accounts.sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.accounts.slot_hashes.as_ref(),
+            ctx.program_id,
+            &ctx.accounts.profile,
+        )
+    }
+
+    /// Commit to `symbol` reading `target_score` once `resolve_after` has
+    /// passed. Settled by `resolve_prediction` against the live record.
+    pub fn open_prediction(
+        ctx: Context<OpenPrediction>,
+        symbol: String,
+        target_score: i8,
+        resolve_after: i64,
+    ) -> Result<()> {
+        validate_vote(&symbol, target_score, 0)?;
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_VOTE)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(resolve_after > now, SentinelError::InvalidTimestamp);
+        let profile = &mut ctx.accounts.profile;
+        require!(
+            now.saturating_sub(profile.created_at) >= ctx.accounts.sentinel.min_profile_age,
+            SentinelError::ProfileTooNew
+        );
+
+        let prediction = &mut ctx.accounts.prediction;
+        prediction.owner = profile.owner;
+        prediction.symbol = symbol.clone();
+        prediction.target_score = target_score;
+        prediction.opened_at = now;
+        prediction.resolve_after = resolve_after;
+        prediction.resolved = false;
+        prediction.correct = false;
+        prediction.bump = ctx.bumps.prediction;
+
+        profile.predictions_made = profile.predictions_made.saturating_add(1);
+        profile.open_predictions = profile.open_predictions.saturating_add(1);
+        profile.last_active = now;
+
+        emit_event(&mut ctx.accounts.sentinel, PredictionOpened {
+            user: profile.owner,
+            symbol,
+            target_score,
+            resolve_after,
+        });
+        Ok(())
+    }
+
+    /// Settle a matured prediction against the symbol's score: correct if
+    /// within `accuracy_tolerance` points of a reading timestamped at or after
+    /// `resolve_after`. It must be settled within `PREDICTION_SETTLE_WINDOW`
+    /// seconds of maturing; later it counts as a miss, so waiting for the
+    /// score to drift into tolerance doesn't pay. No judgement is involved,
+    /// so anyone may call it. The prediction is closed to its owner, who may
+    /// then open another on the symbol.
+    pub fn resolve_prediction(ctx: Context<ResolvePrediction>) -> Result<()> {
+        let prediction = &mut ctx.accounts.prediction;
+        require!(!prediction.resolved, SentinelError::VoteAlreadyResolved);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= prediction.resolve_after, SentinelError::PredictionNotMature);
+        let expired = now > prediction.resolve_after.saturating_add(PREDICTION_SETTLE_WINDOW);
+        if !expired {
+            require!(
+                ctx.accounts.sentiment.timestamp >= prediction.resolve_after,
+                SentinelError::NoMatureReading
+            );
+        }
+
+        let tolerance = ctx.accounts.sentinel.accuracy_tolerance;
+        let actual = ctx.accounts.sentiment.score;
+        let error = (prediction.target_score as i16 - actual as i16).unsigned_abs() as u8;
+        let correct = !expired && error <= tolerance;
+        prediction.resolved = true;
+        prediction.correct = correct;
+
+        emit_event(&mut ctx.accounts.sentinel, PredictionScored {
+            user: prediction.owner,
+            symbol: prediction.symbol.clone(),
+            predicted: prediction.target_score,
+            actual,
+            error,
+            tolerance,
+            expired,
+        });
+        apply_resolution(&mut ctx.accounts.sentinel, &mut ctx.accounts.profile, correct);
+        update_leaderboard(
+            &mut ctx.accounts.sentinel,
+            &ctx.accounts.leaderboard,
+            ctx.accounts.slot_hashes.as_ref(),
+            ctx.program_id,
+            &ctx.accounts.profile,
+        )
+    }
+
+    /// Create the top-predictor leaderboard. It starts empty and fills as
+    /// predictions are resolved.
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+        ctx.accounts.leaderboard.bump = ctx.bumps.leaderboard;
+        let authority = ctx.accounts.authority.key();
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_INIT_LEADERBOARD,
+            authority,
+            audit_value(LEADERBOARD_SIZE as u64),
+        )?;
+        emit_event(&mut ctx.accounts.sentinel, LeaderboardInitialized { authority });
+        Ok(())
+    }
+
+    /// Predictions a profile must have made before it can rank Expert or
+    /// Oracle, however high its reputation.
+    pub fn set_min_predictions_for_expert(ctx: Context<AdminAction>, min_predictions: u32) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.min_predictions_for_expert = min_predictions;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(min_predictions as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set the reputation a resolved prediction earns when correct, costs
+    /// when wrong, and the ceiling reputation can reach.
+    pub fn set_rep_params(
+        ctx: Context<AdminAction>,
+        reward_correct: u16,
+        penalty_wrong: u16,
+        rep_cap: u16,
+    ) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(rep_cap > 0, SentinelError::InvalidRepParams);
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.reward_correct = reward_correct;
+        sentinel.penalty_wrong = penalty_wrong;
+        sentinel.rep_cap = rep_cap;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(reward_correct as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set how many seconds `contribute_sentiment` readings are averaged
+    /// together before a new window starts.
+    pub fn set_contribution_window(ctx: Context<AdminAction>, window: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(window > 0, SentinelError::InvalidContributionWindow);
+        ctx.accounts.sentinel.contribution_window = window;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(window as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Reject Pyth prices published more than `max_age` seconds before the
+    /// `store_sentiment` that carries them.
+    pub fn set_pyth_max_age(ctx: Context<AdminAction>, max_age: i64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(max_age > 0, SentinelError::InvalidPriceMaxAge);
+        ctx.accounts.sentinel.pyth_max_age = max_age;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(max_age as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Require voters to hold at least `min_balance` of the SPL token `mint`
+    /// (raw units), passing their token account to the vote. `None` opens
+    /// voting to everyone. Balances are checked live, not snapshotted: the
+    /// same tokens can be moved from wallet to wallet to cast a vote from
+    /// each, so a gate raises the cost of sybil votes rather than ruling
+    /// them out.
+    pub fn set_vote_gate(ctx: Context<AdminAction>, mint: Option<Pubkey>, min_balance: u64) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        let sentinel = &mut ctx.accounts.sentinel;
+        sentinel.gate_mint = mint;
+        sentinel.min_gate_balance = min_balance;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            mint.unwrap_or_default().to_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Cap the reputation a single vote carries in the weighted consensus, so
+    /// one high-reputation voter can't dominate a symbol.
+    pub fn set_max_vote_weight(ctx: Context<AdminAction>, max_weight: u16) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(max_weight > 0, SentinelError::InvalidVoteWeight);
+        ctx.accounts.sentinel.max_vote_weight = max_weight;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(max_weight as u64),
+        )?;
+        Ok(())
+    }
+
+    /// When disabled, `store_sentiment` only creates records for symbols bound
+    /// to a mint with `register_symbol`.
+    pub fn set_permissionless_symbols(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.permissionless_symbols = enabled;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Only accept community votes on symbols that have a `SentimentRecord`.
+    pub fn set_require_tracked_symbol(ctx: Context<AdminAction>, enabled: bool) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        ctx.accounts.sentinel.require_tracked_symbol = enabled;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(enabled as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Set how many score points a prediction may miss by and still count as
+    /// correct in `resolve_prediction_against`.
+    pub fn set_accuracy_tolerance(ctx: Context<AdminAction>, tolerance: u8) -> Result<()> {
+        require_scope(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), SCOPE_CONFIG)?;
+        require!(tolerance <= 200, SentinelError::InvalidAccuracyTolerance);
+        ctx.accounts.sentinel.accuracy_tolerance = tolerance;
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_SET_CONFIG,
+            ctx.accounts.authority.key(),
+            audit_value(tolerance as u64),
+        )?;
+        Ok(())
+    }
+
+    /// Expire a vote left unresolved past the prediction horizon: penalize the
+    /// voter's reputation for the noise prediction, take it out of the symbol
+    /// aggregate and close the vote, returning its rent to the voter. Callable
+    /// by anyone.
+    pub fn expire_prediction(ctx: Context<ExpirePrediction>, voter: Pubkey, symbol: String) -> Result<()> {
+        let horizon = ctx.accounts.sentinel.prediction_horizon;
+        let vote = &mut ctx.accounts.vote;
+        require!(!vote.resolved, SentinelError::VoteAlreadyResolved);
+        require!(!vote.penalized, SentinelError::AlreadyPenalized);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            horizon > 0 && now.saturating_sub(vote.timestamp) >= horizon,
+            SentinelError::PredictionNotExpired
+        );
+        vote.penalized = true;
+
+        let penalty = ctx.accounts.sentinel.expiry_penalty;
+        let profile = &mut ctx.accounts.profile;
+        profile.open_predictions = profile.open_predictions.saturating_sub(1);
+        profile.reputation = profile.reputation.saturating_sub(penalty);
+        ctx.accounts.aggregate.remove_vote(vote.score, vote.confidence, vote.weight);
+
+        emit_event(&mut ctx.accounts.sentinel, PredictionExpired {
+            voter,
+            symbol,
+            penalty,
+            new_reputation: profile.reputation,
+        });
+        emit_consensus(&mut ctx.accounts.sentinel, &ctx.accounts.aggregate);
+        Ok(())
+    }
+
+    /// Fold `source_voter`'s open vote on `symbol` into the signer's after a
+    /// wallet consolidation: the surviving vote takes the confidence-weighted
+    /// score and mean confidence of the two, and the source vote is closed
+    /// with its rent refunded to the source wallet. Both wallets sign.
+    pub fn merge_votes(ctx: Context<MergeVotes>, symbol: String, source_voter: Pubkey) -> Result<()> {
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_VOTE)?;
+        require_gate_balance(
+            &ctx.accounts.sentinel,
+            ctx.accounts.gate_token_account.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+        require!(source_voter != ctx.accounts.user.key(), SentinelError::InvalidAccount);
+        let source = &ctx.accounts.source_vote;
+        let vote = &mut ctx.accounts.vote;
+        require!(vote.symbol == source.symbol, SentinelError::InvalidSymbol);
+        require!(!vote.resolved && !source.resolved, SentinelError::VoteAlreadyResolved);
+
+        let merged = aggregate_inputs(&[
+            SentimentInput { score: vote.score as i16, confidence: vote.confidence, volume: 0, timestamp: vote.timestamp },
+            SentimentInput { score: source.score as i16, confidence: source.confidence, volume: 0, timestamp: source.timestamp },
+        ]);
+        let merged_score = merged.score as i8;
+
+        // The aggregate loses one vote and swaps both contributions for the merged one
+        let aggregate = &mut ctx.accounts.aggregate;
+        // The surviving vote carries the keeper's current reputation
+        let weight = ctx.accounts.profile.reputation.min(ctx.accounts.sentinel.max_vote_weight);
+        aggregate.remove_vote(vote.score, vote.confidence, vote.weight);
+        aggregate.remove_vote(source.score, source.confidence, source.weight);
+        aggregate.add_vote(merged_score, merged.confidence, weight);
+
+        vote.score = merged_score;
+        vote.confidence = merged.confidence;
+        vote.weight = weight;
+        let source_profile = &mut ctx.accounts.source_profile;
+        source_profile.open_predictions = source_profile.open_predictions.saturating_sub(1);
+
+        emit_event(&mut ctx.accounts.sentinel, VotesMerged {
+            symbol,
+            voter: vote.voter,
+            source_voter,
+            score: merged_score,
+            confidence: merged.confidence,
+        });
+        emit_consensus(&mut ctx.accounts.sentinel, &ctx.accounts.aggregate);
+        Ok(())
+    }
+
+    /// Change an open vote in place. The symbol aggregate swaps the old
+    /// contribution for the new one; the profile's prediction counters are
+    /// left alone since this is still the same prediction.
+    pub fn update_vote(ctx: Context<UpdateVote>, score: i8, confidence: u8) -> Result<()> {
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_VOTE)?;
+        require_gate_balance(
+            &ctx.accounts.sentinel,
+            ctx.accounts.gate_token_account.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+        let vote = &mut ctx.accounts.vote;
+        validate_vote(&vote.symbol, score, confidence)?;
+        require!(!vote.resolved, SentinelError::VoteAlreadyResolved);
+
+        let old_score = vote.score;
+        let aggregate = &mut ctx.accounts.aggregate;
+        aggregate.remove_vote(vote.score, vote.confidence, vote.weight);
+        aggregate.add_vote(score, confidence, vote.weight);
+
+        vote.score = score;
+        vote.confidence = confidence;
+        vote.timestamp = Clock::get()?.unix_timestamp;
+
+        emit_event(&mut ctx.accounts.sentinel, VoteChanged {
+            voter: vote.voter,
+            symbol: vote.symbol.clone(),
+            old_score,
+            new_score: score,
+        });
+        emit_consensus(&mut ctx.accounts.sentinel, &ctx.accounts.aggregate);
+        Ok(())
+    }
+
+    /// Break a user's prediction performance down by token. Their resolved
+    /// `CommunityVote` accounts for `symbol` are passed as remaining accounts.
+    pub fn symbol_accuracy(
+        ctx: Context<ReadSymbolAccuracy>,
+        user: Pubkey,
+        symbol: String,
+    ) -> Result<()> {
+        let mut seen: Vec<Pubkey> = Vec::new();
+        let mut correct: u32 = 0;
+        let mut total: u32 = 0;
+
+        for account_info in ctx.remaining_accounts.iter() {
+            require!(account_info.owner == ctx.program_id, SentinelError::InvalidAccount);
+            require!(!seen.contains(account_info.key), SentinelError::InvalidAccount);
+            seen.push(account_info.key());
+
+            let data = account_info.try_borrow_data()?;
+            require!(&data[..8] == CommunityVote::DISCRIMINATOR, SentinelError::InvalidAccount);
+            let vote = CommunityVote::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(vote.voter, user, SentinelError::Unauthorized);
+            require!(vote.symbol == symbol, SentinelError::InvalidSymbol);
+
+            if vote.resolved {
+                total += 1;
+                if vote.correct {
+                    correct += 1;
+                }
+            }
+        }
+
+        emit_view_event(&ctx.accounts.sentinel, SymbolAccuracy { user, symbol, correct, total });
+        Ok(())
+    }
+
+    /// Earliest timestamp at which `symbol` may next be updated, returned via
+    /// return data. Without a configured interval an update is allowed now.
+    pub fn next_update_time(ctx: Context<ReadSentiment>, symbol: String) -> Result<i64> {
+        require_reader_allowed(&ctx.accounts.symbol_config, &ctx.accounts.instructions, ctx.program_id)?;
+        let interval = ctx.accounts.sentinel.min_update_interval;
+        let ts = if interval > 0 {
+            ctx.accounts.sentiment.timestamp.saturating_add(interval)
+        } else {
+            Clock::get()?.unix_timestamp
+        };
+        emit_view_event(&ctx.accounts.sentinel, NextUpdateAllowed { symbol, ts });
+        Ok(ts)
+    }
+
+    /// UI-ready view of a symbol's score, returned via return data so every
+    /// front-end scales and labels it the same way.
+    pub fn score_display(ctx: Context<ReadSentiment>, _symbol: String) -> Result<ScoreDisplay> {
+        require_reader_allowed(&ctx.accounts.symbol_config, &ctx.accounts.instructions, ctx.program_id)?;
+        let sentinel = &ctx.accounts.sentinel;
+        let sentiment = &ctx.accounts.sentiment;
+        let decimals = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?
+            .and_then(|c| c.decimals)
+            .unwrap_or(sentinel.display_decimals);
+        let label = if sentiment.score >= sentinel.bullish_threshold {
+            "Bullish"
+        } else if sentiment.score <= sentinel.bearish_threshold {
+            "Bearish"
+        } else {
+            "Neutral"
+        };
+        Ok(ScoreDisplay {
+            score: sentiment.score as i32 * 10i32.pow(decimals as u32),
+            decimals,
+            confidence: sentiment.confidence,
+            label: label.to_string(),
+        })
+    }
+
+    /// Score plus slot data for consumers that enforce their own freshness
+    /// bound (`current_slot - record_slot <= max`) instead of trusting
+    /// operator timestamps. Return data layout (Borsh): `score: i8`,
+    /// `confidence: u8`, `record_slot: u64`, `current_slot: u64`.
+    pub fn get_sentiment_with_slot(ctx: Context<ReadSentiment>, _symbol: String) -> Result<SentimentWithSlot> {
+        require_reader_allowed(&ctx.accounts.symbol_config, &ctx.accounts.instructions, ctx.program_id)?;
+        let sentiment = &ctx.accounts.sentiment;
+        Ok(SentimentWithSlot {
+            score: sentiment.score,
+            confidence: sentiment.confidence,
+            record_slot: sentiment.last_update_slot,
+            current_slot: Clock::get()?.slot,
+        })
+    }
+
+    /// The record's current reading for CPI consumers, returned via return
+    /// data: after the CPI, read it with `get_return_data` and Borsh-decode a
+    /// `SentimentView`. `stale` is set if the reading is more than `max_age`
+    /// seconds old.
+    pub fn get_sentiment(ctx: Context<ReadSentiment>, _symbol: String, max_age: i64) -> Result<SentimentView> {
+        require_reader_allowed(&ctx.accounts.symbol_config, &ctx.accounts.instructions, ctx.program_id)?;
+        let sentiment = &ctx.accounts.sentiment;
+        Ok(SentimentView {
+            score: sentiment.score,
+            confidence: sentiment.confidence,
+            volume: sentiment.volume,
+            timestamp: sentiment.timestamp,
+            stale: sentiment.is_stale(Clock::get()?.unix_timestamp, max_age),
+        })
+    }
+
+    /// Emit the reading with its confidence decayed for age, halving every
+    /// `half_life` seconds since its timestamp.
+    pub fn read_decayed(ctx: Context<ReadSentiment>, _symbol: String, half_life: i64) -> Result<()> {
+        require_reader_allowed(&ctx.accounts.symbol_config, &ctx.accounts.instructions, ctx.program_id)?;
+        require!(half_life > 0, SentinelError::InvalidHalfLife);
+        let sentiment = &ctx.accounts.sentiment;
+        emit_view_event(&ctx.accounts.sentinel, DecayedReading {
+            symbol: sentiment.symbol.clone(),
+            score: sentiment.score,
+            decayed_confidence: sentiment.decayed_confidence(Clock::get()?.unix_timestamp, half_life),
+        });
+        Ok(())
+    }
+
+    /// Fail with `SentimentStale` if the record's timestamp is more than
+    /// `max_age` seconds old. Meant as a CPI guard before trusting a reading.
+    pub fn assert_fresh(ctx: Context<AssertFresh>, max_age: i64) -> Result<()> {
+        require!(
+            !ctx.accounts.sentiment.is_stale(Clock::get()?.unix_timestamp, max_age),
+            SentinelError::SentimentStale
+        );
+        Ok(())
+    }
+
+    /// Summarize community consensus for several symbols in one
+    /// `AggregatesRead` event. Pass each symbol's `VoteAggregate` PDA as a
+    /// remaining account, in order; symbols nobody has voted on read as zero.
+    pub fn read_aggregates(ctx: Context<ReadAggregates>, symbols: Vec<String>) -> Result<()> {
+        require!(!symbols.is_empty(), SentinelError::EmptyBatch);
+        require!(symbols.len() <= MAX_BATCH_SIZE, SentinelError::BatchTooLarge);
+        require!(ctx.remaining_accounts.len() == symbols.len(), SentinelError::AccountMismatch);
+
+        let mut entries = Vec::with_capacity(symbols.len());
+        for (symbol, info) in symbols.into_iter().zip(ctx.remaining_accounts.iter()) {
+            let (expected, _) =
+                Pubkey::find_program_address(&[VOTE_AGG_SEED, symbol.as_bytes()], ctx.program_id);
+            require_keys_eq!(*info.key, expected, SentinelError::InvalidAccount);
+
+            let mut summary = AggregateSummary { symbol, mean_score: 0, vote_count: 0, confidence: 0 };
+            if info.owner == ctx.program_id && !info.data_is_empty() {
+                let data = info.try_borrow_data()?;
+                let aggregate = VoteAggregate::try_deserialize(&mut &data[..])?;
+                if aggregate.vote_count > 0 {
+                    let n = aggregate.vote_count as i64;
+                    summary.mean_score = (aggregate.sum_score / n) as i8;
+                    summary.confidence = (aggregate.sum_confidence / n as u64) as u8;
+                    summary.vote_count = aggregate.vote_count;
+                }
+            }
+            entries.push(summary);
+        }
+
+        emit_view_event(&ctx.accounts.sentinel, AggregatesRead { entries });
+        Ok(())
+    }
+
+    /// Snapshot every sentinel setting in one read so ops tooling can diff it
+    /// against the expected state. Returned via return data; the view always
+    /// fits in a single return buffer, so no chunking is needed.
+    pub fn get_config(ctx: Context<ReadConfig>) -> Result<SentinelConfigView> {
+        Ok(config_view(&ctx.accounts.sentinel))
+    }
+
+    /// Headline numbers for dashboards, returned via return data as a
+    /// `SentinelStats`.
+    pub fn get_stats(ctx: Context<ReadConfig>) -> Result<SentinelStats> {
+        let sentinel = &ctx.accounts.sentinel;
+        Ok(SentinelStats {
+            total_updates: sentinel.total_updates,
+            operator_count: sentinel.operators.len() as u8,
+            paused: sentinel.pause_flags != 0,
+            uptime_seconds: Clock::get()?.unix_timestamp.saturating_sub(sentinel.initialized_at),
+            event_seq: sentinel.event_seq,
+        })
+    }
+
+    /// Close a sentiment record and reclaim rent (admin only).
+    pub fn close_sentiment(ctx: Context<CloseSentiment>, _symbol: String) -> Result<()> {
+        emit_event(&mut ctx.accounts.sentinel, SentimentClosed { symbol: ctx.accounts.sentiment.symbol.clone() });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_CLOSE_ACCOUNT,
+            ctx.accounts.authority.key(),
+            audit_symbol(&ctx.accounts.sentiment.symbol),
+        )?;
+        Ok(())
+    }
+
+    /// Close a symbol's history ring buffer and reclaim rent (admin only).
+    /// The account isn't deserialized, so histories in any past layout can be
+    /// closed without migrating them first.
+    pub fn close_history(ctx: Context<CloseHistory>, symbol: String) -> Result<()> {
+        let history = ctx.accounts.history.to_account_info();
+        require!(history.owner == ctx.program_id, SentinelError::InvalidAccount);
+        let authority = ctx.accounts.authority.to_account_info();
+        **authority.try_borrow_mut_lamports()? += history.lamports();
+        **history.try_borrow_mut_lamports()? = 0;
+        history.assign(&System::id());
+        history.resize(0)?;
+
+        let detail = audit_symbol(&symbol);
+        emit_event(&mut ctx.accounts.sentinel, HistoryClosed { symbol });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_CLOSE_ACCOUNT,
+            ctx.accounts.authority.key(),
+            detail,
+        )?;
+        Ok(())
+    }
+
+    /// Close a symbol's community vote aggregate and reclaim rent (admin
+    /// only). Meant for dead symbols, so every vote must be gone first; a
+    /// later vote starts a fresh aggregate.
+    pub fn close_vote_aggregate(ctx: Context<CloseVoteAggregate>, _symbol: String) -> Result<()> {
+        require!(ctx.accounts.aggregate.vote_count == 0, SentinelError::VoteAggregateNotEmpty);
+        emit_event(&mut ctx.accounts.sentinel, VoteAggregateClosed { symbol: ctx.accounts.aggregate.symbol.clone() });
+        record_audit(
+            &mut ctx.accounts.sentinel,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            AUDIT_CLOSE_ACCOUNT,
+            ctx.accounts.authority.key(),
+            audit_symbol(&ctx.accounts.aggregate.symbol),
+        )?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn validate_sentiment_input(symbol: &str, score: i8, confidence: u8, timestamp: i64) -> Result<()> {
+    require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+    require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+    require!(symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
+    require!(score >= -100 && score <= 100, SentinelError::InvalidScore);
+    require!(confidence <= 100, SentinelError::InvalidConfidence);
+    require!(timestamp > 0, SentinelError::InvalidTimestamp);
+    Ok(())
+}
+
+fn validate_note(note: &str) -> Result<()> {
+    require!(note.len() <= MAX_NOTE_LEN, SentinelError::NoteTooLong);
+    require!(note.bytes().all(|b| (0x20..=0x7e).contains(&b)), SentinelError::InvalidNote);
+    Ok(())
+}
+
+fn validate_vote(symbol: &str, score: i8, confidence: u8) -> Result<()> {
+    require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+    require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+    require!(score >= -100 && score <= 100, SentinelError::InvalidScore);
+    require!(confidence <= 100, SentinelError::InvalidConfidence);
+    Ok(())
+}
+
+fn validate_subscription(symbol: &str, direction: i8, alert_threshold: u8) -> Result<()> {
+    require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
+    require!(!symbol.is_empty(), SentinelError::EmptySymbol);
+    require!(direction >= -1 && direction <= 1, SentinelError::InvalidDirection);
+    require!(alert_threshold <= 100, SentinelError::InvalidThreshold);
+    Ok(())
+}
+
+/// With a vote gate set, require `token_account` to be an unfrozen SPL token
+/// account of the gate mint, owned by `voter`, holding at least
+/// `min_gate_balance`.
+fn require_gate_balance(sentinel: &Sentinel, token_account: Option<&AccountInfo>, voter: &Pubkey) -> Result<()> {
+    let Some(gate_mint) = sentinel.gate_mint else {
+        return Ok(());
+    };
+    let account = token_account.ok_or(SentinelError::InsufficientGateBalance)?;
+    require!(
+        *account.owner == TOKEN_PROGRAM_ID || *account.owner == TOKEN_2022_PROGRAM_ID,
+        SentinelError::InvalidAccount
+    );
+    let data = account.try_borrow_data()?;
+    // Token account layout: mint, owner, u64 amount, COption delegate, then
+    // the state byte
+    require!(data.len() > TOKEN_ACCOUNT_STATE_OFFSET, SentinelError::InvalidAccount);
+    require!(data[0..32] == gate_mint.to_bytes(), SentinelError::InvalidAccount);
+    require!(data[32..64] == voter.to_bytes(), SentinelError::Unauthorized);
+    require!(
+        data[TOKEN_ACCOUNT_STATE_OFFSET] != TOKEN_ACCOUNT_FROZEN,
+        SentinelError::GateAccountFrozen
+    );
+    let amount = u64::from_le_bytes(data[64..72].try_into().unwrap());
+    require!(amount >= sentinel.min_gate_balance, SentinelError::InsufficientGateBalance);
+    Ok(())
+}
+
+/// Unless symbols are permissionless, require `symbol`'s `SymbolRegistry`
+/// entry to exist.
+fn require_registered(sentinel: &Sentinel, registry: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+    if !sentinel.permissionless_symbols {
+        require!(
+            registry.owner == program_id && !registry.data_is_empty(),
+            SentinelError::SymbolNotRegistered
+        );
+    }
+    Ok(())
+}
+
+/// When the sentinel only accepts votes on tracked symbols, require the
+/// symbol's `SentimentRecord` to exist.
+fn require_tracked(sentinel: &Sentinel, sentiment: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+    if sentinel.require_tracked_symbol {
+        require!(
+            sentiment.owner == program_id && !sentiment.data_is_empty(),
+            SentinelError::SymbolNotTracked
+        );
+    }
+    Ok(())
+}
+
+/// Fill a freshly created vote from `profile`'s owner and fold it into the
+/// symbol aggregate. Callers set the vote and aggregate bumps.
+fn cast_vote(
+    sentinel: &mut Sentinel,
+    vote: &mut CommunityVote,
+    profile: &mut UserProfile,
+    aggregate: &mut VoteAggregate,
+    symbol: String,
+    score: i8,
+    confidence: u8,
+) -> Result<()> {
+    require_unpaused(sentinel, PAUSE_VOTE)?;
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp.saturating_sub(profile.created_at) >= sentinel.min_profile_age,
+        SentinelError::ProfileTooNew
+    );
+
+    vote.voter = profile.owner;
+    vote.symbol = symbol.clone();
+    vote.score = score;
+    vote.confidence = confidence;
+    vote.timestamp = clock.unix_timestamp;
+    vote.resolved = false;
+    vote.correct = false;
+    vote.penalized = false;
+    vote.weight = profile.reputation.min(sentinel.max_vote_weight);
+
+    profile.predictions_made = profile.predictions_made.saturating_add(1);
+    profile.open_predictions = profile.open_predictions.saturating_add(1);
+    profile.last_active = clock.unix_timestamp;
+
+    if aggregate.symbol.is_empty() {
+        aggregate.symbol = symbol.clone();
+    }
+    aggregate.add_vote(score, confidence, vote.weight);
+
+    emit_event(sentinel, CommunityVoteEvent {
+        voter: vote.voter,
+        symbol,
+        score,
+        confidence,
+        timestamp: clock.unix_timestamp,
+    });
+    emit_consensus(sentinel, aggregate);
+    Ok(())
+}
+
+fn emit_consensus(sentinel: &mut Sentinel, aggregate: &VoteAggregate) {
+    emit_event(sentinel, ConsensusUpdated {
+        symbol: aggregate.symbol.clone(),
+        consensus_score: aggregate.consensus_score(),
+        weighted_consensus: aggregate.weighted_consensus(),
+        vote_count: aggregate.vote_count,
+    });
+}
+
+/// Resize a program-owned account to `new_len`, topping its rent up from
+/// `payer` or refunding the excess to it.
+fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let current = account.lamports();
+    if rent > current {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent - current,
+        )?;
+    } else if current > rent {
+        **account.try_borrow_mut_lamports()? -= current - rent;
+        **payer.try_borrow_mut_lamports()? += current - rent;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Create the PDA `account` with `space` bytes owned by `owner`, the way
+/// Anchor's `init` does: an account someone already sent lamports to is
+/// topped up, allocated and assigned instead of failing `create_account`.
+fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+    if current == 0 {
+        return anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            owner,
+        );
+    }
+
+    if rent > current {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent - current,
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Allocate { account_to_allocate: account.clone() },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Assign { account_to_assign: account.clone() },
+            &[signer_seeds],
+        ),
+        owner,
+    )
+}
+
+/// Fill a freshly created subscription. Callers set the bump.
+fn open_subscription(
+    sentinel: &mut Sentinel,
+    subscription: &mut Subscription,
+    user: Pubkey,
+    symbol: String,
+    direction: i8,
+    alert_threshold: u8,
+) -> Result<()> {
+    require_unpaused(sentinel, PAUSE_SUBSCRIBE)?;
+    subscription.user = user;
+    subscription.symbol = symbol.clone();
+    subscription.direction = direction;
+    subscription.alert_threshold = alert_threshold;
+    subscription.subscribed_at = Clock::get()?.unix_timestamp;
+    subscription.last_alert = 0;
+    subscription.alerted_reading = 0;
+
+    emit_event(sentinel, Subscribed {
+        user,
+        symbol,
+        direction,
+        alert_threshold,
+    });
+    Ok(())
+}
+
+/// `ALERT_REASON_*` bits for the thresholds `record` crosses. A score trigger
+/// needs a nonzero `alert_threshold`: direction 1 fires at or above it, -1 at
+/// or below its negation, 0 on either side.
+fn alert_reason(subscription: &Subscription, record: &SentimentRecord) -> u8 {
+    let mut reason = 0;
+    let threshold = subscription.alert_threshold as i16;
+    let score = record.score as i16;
+    let score_hit = match subscription.direction {
+        1 => score >= threshold,
+        -1 => score <= -threshold,
+        _ => score.abs() >= threshold,
+    };
+    if threshold > 0 && score_hit {
+        reason |= ALERT_REASON_SCORE;
+    }
+    if subscription.volume_threshold > 0 && record.volume >= subscription.volume_threshold {
+        reason |= ALERT_REASON_VOLUME;
+    }
+    reason
+}
+
+/// Close out one of `profile`'s open predictions and adjust its reputation.
+fn apply_resolution(sentinel: &mut Sentinel, profile: &mut UserProfile, correct: bool) {
+    profile.open_predictions = profile.open_predictions.saturating_sub(1);
+
+    if correct {
+        profile.correct_predictions = profile.correct_predictions.saturating_add(1);
+        profile.reputation = capped_reward(profile.reputation, sentinel.reward_correct, sentinel.rep_cap);
+    } else {
+        profile.reputation = profile.reputation.saturating_sub(sentinel.penalty_wrong);
+    }
+
+    emit_event(sentinel, PredictionResolved {
+        user: profile.owner,
+        correct,
+        new_reputation: profile.reputation,
+        tier: profile.tier(sentinel.min_predictions_for_expert),
+    });
+}
+
+/// `reputation` plus `reward`, capped at `cap`. Reputation already above the
+/// cap, earned before it was lowered, is kept rather than clawed back.
+fn capped_reward(reputation: u16, reward: u16, cap: u16) -> u16 {
+    if reputation < cap {
+        reputation.saturating_add(reward).min(cap)
+    } else {
+        reputation
+    }
+}
+
+/// Fold `profile`'s reputation into the leaderboard, if it has been created,
+/// emitting `LeaderboardChanged` when the board moves. With `random_tiebreak`
+/// on, ties are ordered by entropy from `slot_hashes`, which must be passed.
+fn update_leaderboard(
+    sentinel: &mut Sentinel,
+    info: &AccountInfo,
+    slot_hashes: Option<&UncheckedAccount>,
+    program_id: &Pubkey,
+    profile: &UserProfile,
+) -> Result<()> {
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(());
+    }
+    let entropy = if sentinel.random_tiebreak {
+        let slot_hashes = slot_hashes.ok_or(SentinelError::EntropyRequired)?;
+        Some(recent_entropy(slot_hashes)?)
+    } else {
+        None
+    };
+    let mut data = info.try_borrow_mut_data()?;
+    let mut board = Leaderboard::try_deserialize(&mut &data[..])?;
+    let Some(rank) = board.record(profile.owner, profile.reputation, entropy) else {
+        return Ok(());
+    };
+    let mut writer = &mut data[..];
+    board.try_serialize(&mut writer)?;
+
+    emit_event(sentinel, LeaderboardChanged {
+        user: profile.owner,
+        reputation: profile.reputation,
+        rank,
+    });
+    Ok(())
+}
+
+/// Remove `user` from the leaderboard, if it has been created and ranks them,
+/// emitting `LeaderboardEvicted`.
+fn evict_from_leaderboard(sentinel: &mut Sentinel, info: &AccountInfo, program_id: &Pubkey, user: Pubkey) -> Result<()> {
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(());
+    }
+    let mut data = info.try_borrow_mut_data()?;
+    let mut board = Leaderboard::try_deserialize(&mut &data[..])?;
+    if !board.remove(&user) {
+        return Ok(());
+    }
+    let mut writer = &mut data[..];
+    board.try_serialize(&mut writer)?;
+
+    emit_event(sentinel, LeaderboardEvicted { user });
+    Ok(())
+}
+
+/// The `UsernameClaim` at `info` if it exists and is held by `owner`. Profiles
+/// created before claims existed may have none, or share a name whose claim
+/// another profile took since.
+fn held_username_claim(info: &AccountInfo, program_id: &Pubkey, owner: &Pubkey) -> Result<Option<UsernameClaim>> {
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(None);
+    }
+    let claim = UsernameClaim::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok((claim.owner == *owner).then_some(claim))
+}
+
+fn config_view(sentinel: &Sentinel) -> SentinelConfigView {
+    SentinelConfigView {
+        schema_version: CONFIG_SCHEMA_VERSION,
+        authority: sentinel.authority,
+        pause_flags: sentinel.pause_flags,
+        operator_count: sentinel.operators.len() as u8,
+        authority_count: sentinel.authorities.len() as u8,
+        operators_enabled: sentinel.operators_enabled,
+        operator_probation: sentinel.operator_probation,
+        reputation_tombstones: sentinel.reputation_tombstones,
+        events_enabled: sentinel.events_enabled,
+        dispute_quorum: sentinel.dispute_quorum,
+        min_profile_age: sentinel.min_profile_age,
+        min_update_interval: sentinel.min_update_interval,
+        spread_penalty_factor: sentinel.spread_penalty_factor,
+        audit_enabled: sentinel.audit_enabled,
+        volume_anomaly_sigmas: sentinel.volume_anomaly_sigmas,
+        display_decimals: sentinel.display_decimals,
+        bullish_threshold: sentinel.bullish_threshold,
+        bearish_threshold: sentinel.bearish_threshold,
+        prediction_horizon: sentinel.prediction_horizon,
+        expiry_penalty: sentinel.expiry_penalty,
+        random_tiebreak: sentinel.random_tiebreak,
+        proposal_window: sentinel.proposal_window,
+        min_endorser_reputation: sentinel.min_endorser_reputation,
+        volatility_threshold: sentinel.volatility_threshold,
+        volatility_circuit: sentinel.volatility_circuit,
+        accuracy_tolerance: sentinel.accuracy_tolerance,
+        require_tracked_symbol: sentinel.require_tracked_symbol,
+        watchdog_timeout: sentinel.watchdog_timeout,
+        max_score_delta: sentinel.max_score_delta,
+        reputation_decay_period: sentinel.reputation_decay_period,
+        reputation_floor: sentinel.reputation_floor,
+        permissionless_symbols: sentinel.permissionless_symbols,
+        max_vote_weight: sentinel.max_vote_weight,
+        rate_limit_window: sentinel.rate_limit_window,
+        rate_limit_max: sentinel.rate_limit_max,
+        min_predictions_for_expert: sentinel.min_predictions_for_expert,
+        pyth_max_age: sentinel.pyth_max_age,
+        gate_mint: sentinel.gate_mint,
+        min_gate_balance: sentinel.min_gate_balance,
+        contribution_window: sentinel.contribution_window,
+        max_future_skew: sentinel.max_future_skew,
+        max_past_skew: sentinel.max_past_skew,
+        treasury: sentinel.treasury,
+        community_weight: sentinel.community_weight,
+        min_operator_rep: sentinel.min_operator_rep,
+        min_operator_predictions: sentinel.min_operator_predictions,
+        min_dispute_rep: sentinel.min_dispute_rep,
+        reward_correct: sentinel.reward_correct,
+        penalty_wrong: sentinel.penalty_wrong,
+        rep_cap: sentinel.rep_cap,
+        milestone_interval: sentinel.milestone_interval,
+    }
+}
+
+/// Every setting at its `initialize` default, shared with `migrate_sentinel`.
+fn set_default_settings(sentinel: &mut Sentinel) {
+    sentinel.pause_flags = 0;
+    sentinel.operators = Vec::new();
+    sentinel.authorities = Vec::new();
+    sentinel.operators_enabled = true;
+    sentinel.operator_probation = 0;
+    sentinel.milestone_interval = 0;
+    sentinel.reputation_tombstones = false;
+    sentinel.events_enabled = true;
+    sentinel.dispute_quorum = 0;
+    sentinel.min_profile_age = 0;
+    sentinel.min_update_interval = 0;
+    sentinel.spread_penalty_factor = 0;
+    sentinel.audit_enabled = false;
+    sentinel.volume_anomaly_sigmas = 0;
+    sentinel.display_decimals = 2;
+    sentinel.bullish_threshold = 20;
+    sentinel.bearish_threshold = -20;
+    sentinel.prediction_horizon = 0;
+    sentinel.expiry_penalty = 0;
+    sentinel.random_tiebreak = false;
+    sentinel.proposal_window = 86_400;
+    sentinel.min_endorser_reputation = SKILLED_REPUTATION; // above a fresh profile's 100
+    sentinel.volatility_threshold = 0;
+    sentinel.volatility_circuit = false;
+    sentinel.accuracy_tolerance = 10;
+    sentinel.require_tracked_symbol = false;
+    sentinel.pending_authority = None;
+    sentinel.watchdog_timeout = 0;
+    sentinel.max_score_delta = 0;
+    sentinel.reputation_decay_period = 30 * 86_400;
+    sentinel.reputation_floor = 50;
+    sentinel.permissionless_symbols = true;
+    sentinel.max_vote_weight = 500;
+    sentinel.rate_limit_window = 0;
+    sentinel.rate_limit_max = 0;
+    sentinel.min_predictions_for_expert = 20;
+    sentinel.pyth_max_age = 60;
+    sentinel.gate_mint = None;
+    sentinel.min_gate_balance = 0;
+    sentinel.contribution_window = 300;
+    sentinel.max_future_skew = 0;
+    sentinel.max_past_skew = 0;
+    sentinel.treasury = sentinel.authority; // callers set the authority first
+    sentinel.community_weight = 0;
+    sentinel.min_operator_rep = EXPERT_REPUTATION;
+    sentinel.min_operator_predictions = 50;
+    sentinel.min_dispute_rep = SKILLED_REPUTATION;
+    sentinel.reward_correct = 10;
+    sentinel.penalty_wrong = 5;
+    sentinel.rep_cap = 1000;
+    sentinel.min_operator_stake = 0;
+    sentinel.unstake_delay = DEFAULT_UNSTAKE_DELAY;
+}
+
+/// Emit `event` unless the authority has turned event emission off, in which
+/// case consumers rely on polling account state instead. Each event is
+/// followed by an `EventSequenced` carrying the next `event_seq`, so indexers
+/// can spot dropped logs as gaps.
+fn emit_event<E: anchor_lang::Event>(sentinel: &mut Sentinel, event: E) {
+    if sentinel.events_enabled {
+        emit!(event);
+        let seq = next_event_seq(sentinel);
+        emit!(EventSequenced { seq });
+    }
+}
+
+/// `emit_event` for read-only instructions, whose sentinel is not written
+/// back; these events carry no sequence number.
+fn emit_view_event<E: anchor_lang::Event>(sentinel: &Sentinel, event: E) {
+    if sentinel.events_enabled {
+        emit!(event);
+    }
+}
+
+/// Advance the event sequence and return the new value.
+fn next_event_seq(sentinel: &mut Sentinel) -> u64 {
+    sentinel.event_seq = sentinel.event_seq.wrapping_add(1);
+    sentinel.event_seq
+}
+
+/// Bump the global update counter and last-write time, emitting
+/// `MilestoneReached` once for every milestone crossed (multiples of
+/// `milestone_interval`, or powers of ten when unset).
+fn bump_total_updates(sentinel: &mut Sentinel, n: u64, now: i64) {
+    let old = sentinel.total_updates;
+    let new = old.saturating_add(n);
+    sentinel.total_updates = new;
+    sentinel.last_update_ts = now;
+
+    if sentinel.milestone_interval > 0 {
+        let interval = sentinel.milestone_interval;
+        for k in (old / interval + 1)..=(new / interval) {
+            emit_event(sentinel, MilestoneReached { total: k * interval });
+        }
+    } else {
+        let mut milestone: u64 = 1;
+        while milestone <= new {
+            if milestone > old {
+                emit_event(sentinel, MilestoneReached { total: milestone });
+            }
+            match milestone.checked_mul(10) {
+                Some(next) => milestone = next,
+                None => break,
+            }
+        }
+    }
+}
+
+fn snapshot_of(sentiment: &SentimentRecord, recorded_at: i64) -> HistoryEntry {
+    HistoryEntry {
+        score: sentiment.score,
+        confidence: sentiment.confidence,
+        volume: sentiment.volume,
+        timestamp: sentiment.timestamp,
+        recorded_at,
+    }
+}
+
+/// Write `entry` at `head` and advance the ring buffer.
+fn push_history(history: &mut SentimentHistory, entry: HistoryEntry) {
+    let idx = history.head as usize;
+    history.snapshots[idx] = entry;
+    history.head = ((idx + 1) % history.capacity()) as u16;
+    if (history.count as usize) < history.capacity() {
+        history.count += 1;
+    }
+}
+
+/// Deserialize a `SentimentRecord` passed as a remaining account, checking it
+/// is ours and is the canonical PDA for `symbol`.
+fn load_sentiment(account_info: &AccountInfo, symbol: &str, program_id: &Pubkey) -> Result<SentimentRecord> {
+    require!(account_info.owner == program_id, SentinelError::InvalidAccount);
+    let (expected, _) = Pubkey::find_program_address(&[SENTIMENT_SEED, symbol.as_bytes()], program_id);
+    require_keys_eq!(account_info.key(), expected, SentinelError::InvalidAccount);
+
+    let data = account_info.try_borrow_data()?;
+    require!(&data[..8] == SentimentRecord::DISCRIMINATOR, SentinelError::InvalidAccount);
+    SentimentRecord::try_deserialize(&mut &data[..])
+}
+
+/// Append a snapshot of `sentiment` to the history PDA passed as a remaining
+/// account, creating the account first if it does not exist yet.
+/// Returns the number of entries now held.
+fn append_history_snapshot<'info>(
+    history_info: &AccountInfo<'info>,
+    sentiment: &SentimentRecord,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    recorded_at: i64,
+) -> Result<u16> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[HISTORY_SEED, sentiment.symbol.as_bytes()], program_id);
+    require_keys_eq!(history_info.key(), expected, SentinelError::InvalidAccount);
+
+    let mut history = if history_info.owner == program_id {
+        let data = history_info.try_borrow_data()?;
+        require!(&data[..8] == SentimentHistory::DISCRIMINATOR, SentinelError::InvalidAccount);
+        let mut history = SentimentHistory::try_deserialize(&mut &data[..])?;
+        history.head %= history.capacity.max(1);
+        assert_history_invariant(&history)?;
+        history
+    } else {
+        let bump_seed = [bump];
+        let signer_seeds: &[&[u8]] = &[HISTORY_SEED, sentiment.symbol.as_bytes(), &bump_seed];
+        create_pda_account(history_info, payer, system_program, SentimentHistory::LEN, program_id, signer_seeds)?;
+        SentimentHistory {
+            symbol: sentiment.symbol.clone(),
+            head: 0,
+            count: 0,
+            capacity: MAX_HISTORY as u16,
+            snapshots: vec![HistoryEntry::default(); MAX_HISTORY],
+            bump,
+        }
+    };
+
+    push_history(&mut history, snapshot_of(sentiment, recorded_at));
+
+    let mut data = history_info.try_borrow_mut_data()?;
+    let mut writer = &mut data[..];
+    history.try_serialize(&mut writer)?;
+    Ok(history.count)
+}
+
+/// Check the ring-buffer invariants before reading or writing a history account.
+/// `head` is always a slot index, and until the buffer fills it advances in
+/// lockstep with `count`.
+fn assert_history_invariant(history: &SentimentHistory) -> Result<()> {
+    let count = history.count as usize;
+    let capacity = history.capacity as usize;
+    require!((1..=MAX_HISTORY_CAP).contains(&capacity), SentinelError::CorruptHistory);
+    require!(count <= capacity, SentinelError::CorruptHistory);
+    require!((history.head as usize) < capacity, SentinelError::CorruptHistory);
+    require!(history.snapshots.len() == capacity, SentinelError::CorruptHistory);
+    if count < capacity {
+        require!(history.head as usize == count, SentinelError::CorruptHistory);
+    }
+    Ok(())
+}
+
+/// Mean score in hundredths of chronologically ordered snapshots, each
+/// weighted by the gap to the next, and whether confidence weighting was
+/// applied. If no time elapsed between them the latest score wins.
+fn time_weighted_score(entries: &[HistoryEntry], confidence_weighted: bool) -> (i64, bool) {
+    let (mut time_sum, mut time_total) = (0i128, 0i128);
+    let (mut conf_sum, mut conf_total) = (0i128, 0i128);
+    for pair in entries.windows(2) {
+        let (entry, next) = (&pair[0], &pair[1]);
+        let span = (next.recorded_at - entry.recorded_at).max(0) as i128;
+        time_sum += entry.score as i128 * span;
+        time_total += span;
+        conf_sum += entry.score as i128 * span * entry.confidence as i128;
+        conf_total += span * entry.confidence as i128;
+    }
+
+    if confidence_weighted && conf_total > 0 {
+        ((conf_sum * 100 / conf_total) as i64, true)
+    } else if time_total > 0 {
+        ((time_sum * 100 / time_total) as i64, false)
+    } else {
+        (entries[entries.len() - 1].score as i64 * 100, false)
+    }
+}
+
+/// Combine readings into one: confidence-weighted score (plain mean if every
+/// confidence is zero), mean confidence, summed volume and the latest timestamp.
+fn aggregate_inputs(inputs: &[SentimentInput]) -> SentimentInput {
+    let n = inputs.len() as i64;
+    let total_confidence: i64 = inputs.iter().map(|i| i.confidence as i64).sum();
+    let score = if total_confidence > 0 {
+        inputs.iter().map(|i| i.score as i64 * i.confidence as i64).sum::<i64>() / total_confidence
+    } else {
+        inputs.iter().map(|i| i.score as i64).sum::<i64>() / n
+    };
+
+    SentimentInput {
+        score: score as i16,
+        confidence: (total_confidence / n) as u8,
+        volume: inputs.iter().fold(0u32, |acc, i| acc.saturating_add(i.volume)),
+        timestamp: inputs.iter().map(|i| i.timestamp).max().unwrap_or_default(),
+    }
+}
+
+/// Encode `next` as a delta from `prev`, or `None` if any field overflows its
+/// small delta type and a keyframe is needed instead.
+fn encode_history_delta(prev: &HistoryEntry, next: &HistoryEntry) -> Option<DeltaEntry> {
+    Some(DeltaEntry {
+        keyframe: false,
+        score: i8::try_from(next.score as i16 - prev.score as i16).ok()?,
+        confidence: i8::try_from(next.confidence as i16 - prev.confidence as i16).ok()?,
+        volume: i16::try_from(next.volume as i64 - prev.volume as i64).ok()?,
+        timestamp: u16::try_from(next.timestamp.checked_sub(prev.timestamp)?).ok()?,
+        recorded_at: u16::try_from(next.recorded_at.checked_sub(prev.recorded_at)?).ok()?,
+    })
+}
+
+/// Reconstruct the absolute entry following `prev` from its delta.
+fn apply_history_delta(prev: &HistoryEntry, delta: &DeltaEntry) -> HistoryEntry {
+    HistoryEntry {
+        score: (prev.score as i16 + delta.score as i16) as i8,
+        confidence: (prev.confidence as i16 + delta.confidence as i16) as u8,
+        volume: (prev.volume as i64 + delta.volume as i64) as u32,
+        timestamp: prev.timestamp + delta.timestamp as i64,
+        recorded_at: prev.recorded_at + delta.recorded_at as i64,
+    }
+}
+
+/// Append `next` to `out` as a compact delta from `prev`: wrapping `i8` score
+/// and confidence deltas, then zigzag varints for volume and both timestamps.
+fn encode_compact_delta(out: &mut Vec<u8>, prev: &HistoryEntry, next: &HistoryEntry) {
+    out.push(next.score.wrapping_sub(prev.score) as u8);
+    out.push(next.confidence.wrapping_sub(prev.confidence));
+    write_varint(out, zigzag(next.volume as i64 - prev.volume as i64));
+    write_varint(out, zigzag(next.timestamp.wrapping_sub(prev.timestamp)));
+    write_varint(out, zigzag(next.recorded_at.wrapping_sub(prev.recorded_at)));
+}
+
+/// Reconstruct the entry following `prev` from the compact delta at `*pos`,
+/// advancing `*pos` past it.
+fn decode_compact_delta(data: &[u8], pos: &mut usize, prev: &HistoryEntry) -> HistoryEntry {
+    let score = prev.score.wrapping_add(data[*pos] as i8);
+    let confidence = prev.confidence.wrapping_add(data[*pos + 1]);
+    *pos += 2;
+    HistoryEntry {
+        score,
+        confidence,
+        volume: (prev.volume as i64 + unzigzag(read_varint(data, pos))) as u32,
+        timestamp: prev.timestamp.wrapping_add(unzigzag(read_varint(data, pos))),
+        recorded_at: prev.recorded_at.wrapping_add(unzigzag(read_varint(data, pos))),
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// LEB128: seven bits per byte, high bit set on every byte but the last.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> u64 {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = data[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+/// Whether `signer` is the root authority or an enabled operator holding
+/// every bit of `perms` (0 admits any operator).
+fn is_authority_or_operator(sentinel: &Sentinel, signer: &Pubkey, perms: u8) -> bool {
+    sentinel.authority == *signer
+        || (sentinel.operators_enabled
+            && sentinel.operators.iter().any(|o| o.key == *signer && o.perms & perms == perms))
+}
+
+/// Whether `key` may still write the record: an admin under the current
+/// authority set, a member of the operator set (whether or not operators are
+/// currently enabled), or the holder of `scope`, its `OperatorScope` PDA for
+/// the record's symbol.
+fn is_current_updater(sentinel: &Sentinel, key: &Pubkey, scope: &AccountInfo) -> bool {
+    is_admin(sentinel, key)
+        || sentinel.operators.iter().any(|o| o.key == *key)
+        || holds_operator_scope(scope)
+}
+
+/// Whether `signer` is a member of the operator set while a minimum stake is set.
+fn must_stake(sentinel: &Sentinel, signer: &Pubkey) -> bool {
+    sentinel.min_operator_stake > 0
+        && !is_admin(sentinel, signer)
+        && sentinel.operators.iter().any(|o| o.key == *signer)
+}
+
+/// Whether `signer` clears the minimum operator stake. Only members of the
+/// operator set are staked; admins and per-symbol grants pass. `stake` is the
+/// signer's `OperatorStake` PDA, checked by seeds in the accounts struct.
+fn meets_min_stake(sentinel: &Sentinel, signer: &Pubkey, stake: &AccountInfo) -> bool {
+    if !must_stake(sentinel, signer) {
+        return true;
+    }
+    if stake.owner != &crate::ID {
+        return false;
+    }
+    let Ok(data) = stake.try_borrow_data() else {
+        return false;
+    };
+    OperatorStake::try_deserialize(&mut &data[..])
+        .is_ok_and(|s| s.remaining() >= sentinel.min_operator_stake)
+}
+
+/// `meets_min_stake` for an earlier approver, whose `OperatorStake` PDA is
+/// looked up among `stakes` by address. A staked operator whose PDA wasn't
+/// passed doesn't clear it.
+fn approval_meets_min_stake(sentinel: &Sentinel, approver: &Pubkey, stakes: &[AccountInfo]) -> bool {
+    if !must_stake(sentinel, approver) {
+        return true;
+    }
+    let (address, _) = Pubkey::find_program_address(&[OPERATOR_STAKE_SEED, approver.as_ref()], &crate::ID);
+    stakes.iter()
+        .find(|s| *s.key == address)
+        .is_some_and(|s| meets_min_stake(sentinel, approver, s))
+}
+
+/// Whether `scope` is a live `OperatorScope` PDA. Its seeds are checked by
+/// the accounts struct, so existence alone proves the grant.
+fn holds_operator_scope(scope: &AccountInfo) -> bool {
+    scope.owner == &crate::ID && !scope.data_is_empty()
+}
+
+/// The root authority or any scoped authority.
+fn is_admin(sentinel: &Sentinel, signer: &Pubkey) -> bool {
+    sentinel.authority == *signer || sentinel.authorities.iter().any(|a| a.key == *signer)
+}
+
+/// Require that `signer` holds every bit of `scope`. The root authority holds all scopes.
+fn require_scope(sentinel: &Sentinel, signer: &Pubkey, scope: u16) -> Result<()> {
+    let allowed = sentinel.authority == *signer
+        || sentinel.authorities.iter().any(|a| a.key == *signer && a.scope_flags & scope == scope);
+    require!(allowed, SentinelError::InsufficientAuthorityScope);
+    Ok(())
+}
+
+/// Append `operator` to the operator set with default limits.
+fn push_operator(sentinel: &mut Sentinel, operator: Pubkey, perms: u8) -> Result<()> {
+    require!(perms & !CAN_ALL == 0, SentinelError::InvalidOperatorPerms);
+    require!(sentinel.operators.len() < MAX_OPERATORS, SentinelError::TooManyOperators);
+    require!(
+        !sentinel.operators.iter().any(|o| o.key == operator),
+        SentinelError::OperatorAlreadyExists
+    );
+    let on_probation = sentinel.operator_probation > 0;
+    sentinel.operators.push(OperatorEntry {
+        key: operator,
+        added_at: Clock::get()?.unix_timestamp,
+        on_probation,
+        max_confidence: 100,
+        scale_factor: SCALE_FACTOR_ONE,
+        score_offset: 0,
+        perms,
+    });
+    Ok(())
+}
+
+/// Shared body of `endorse_operator` and `object_operator`.
+fn review_operator_proposal(ctx: Context<ReviewOperatorProposal>, endorse: bool) -> Result<()> {
+    let profile = &ctx.accounts.profile;
+    require!(
+        profile.reputation >= ctx.accounts.sentinel.min_endorser_reputation,
+        SentinelError::InsufficientReputation
+    );
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        Clock::get()?.unix_timestamp < proposal.closes_at,
+        SentinelError::ProposalClosed
+    );
+    let voter = ctx.accounts.user.key();
+    require!(!proposal.voters.contains(&voter), SentinelError::AlreadyVoted);
+    require!(proposal.voters.len() < MAX_PROPOSAL_VOTERS, SentinelError::ProposalFull);
+    proposal.voters.push(voter);
+    if endorse {
+        proposal.endorsements += 1;
+    } else {
+        proposal.objections += 1;
+    }
+
+    emit_event(&mut ctx.accounts.sentinel, OperatorProposalReviewed {
+        candidate: proposal.candidate,
+        voter,
+        endorse,
+        reputation: profile.reputation,
+    });
+    Ok(())
+}
+
+/// Highest confidence `signer` may store; the authority is never capped.
+fn operator_confidence_cap(sentinel: &Sentinel, signer: &Pubkey) -> u8 {
+    if sentinel.authority == *signer {
+        return 100;
+    }
+    sentinel.operators.iter()
+        .find(|o| o.key == *signer)
+        .map_or(100, |o| o.max_confidence)
+}
+
+/// Map `raw` onto the canonical scale with the signer's operator offset and
+/// scale factor, falling back to the symbol's scale factor. Untransformed
+/// submissions (including the authority's) pass through untouched so
+/// out-of-range scores are still rejected rather than clamped; callers check
+/// the range before narrowing to `i8`.
+fn normalize_score(sentinel: &Sentinel, signer: &Pubkey, raw: i16, symbol_scale: i16) -> i16 {
+    (normalize_score_bps(sentinel, signer, raw as i32 * 100, symbol_scale) / 100) as i16
+}
+
+/// `normalize_score` for a score in basis points.
+fn normalize_score_bps(sentinel: &Sentinel, signer: &Pubkey, raw_bps: i32, symbol_scale: i16) -> i32 {
+    let (factor, offset) = score_transform(sentinel, signer, symbol_scale);
+    if factor == SCALE_FACTOR_ONE && offset == 0 {
+        return raw_bps;
+    }
+    // Widened so an extreme raw score times a large factor clamps instead of wrapping
+    let scaled = (raw_bps as i64 - offset as i64 * 100) * factor as i64 / SCALE_FACTOR_ONE as i64;
+    scaled.clamp(-10_000, 10_000) as i32
+}
+
+/// The signer's operator scale factor and offset if either is set, else
+/// `symbol_scale` with no offset.
+fn score_transform(sentinel: &Sentinel, signer: &Pubkey, symbol_scale: i16) -> (i16, i16) {
+    sentinel.operators.iter()
+        .find(|o| o.key == *signer && (o.scale_factor != SCALE_FACTOR_ONE || o.score_offset != 0))
+        .map_or((symbol_scale, 0), |o| (o.scale_factor, o.score_offset))
+}
+
+/// A listed, non-probationary operator while the operator subsystem is enabled.
+fn is_active_operator(sentinel: &Sentinel, key: &Pubkey) -> bool {
+    sentinel.operators_enabled
+        && sentinel.operators.iter().any(|o| o.key == *key && !o.on_probation)
+}
+
+/// Whether `key`'s approval counts toward a consensus threshold: the root
+/// authority, or an active operator with update rights.
+fn counts_as_approver(sentinel: &Sentinel, key: &Pubkey) -> bool {
+    sentinel.authority == *key
+        || (is_active_operator(sentinel, key) && is_authority_or_operator(sentinel, key, CAN_UPDATE))
+}
+
+/// Operator scheduled for turn `seed` in the rotation over active operators.
+/// Purely a function of account state, so every validator agrees.
+fn next_operator(sentinel: &Sentinel, seed: u64) -> Option<Pubkey> {
+    let active: Vec<&OperatorEntry> = sentinel.operators.iter().filter(|o| !o.on_probation).collect();
+    if !sentinel.operators_enabled || active.is_empty() {
+        return None;
+    }
+    Some(active[(seed % active.len() as u64) as usize].key)
+}
+
+/// Whether `signer` updated `record` out of turn, then hand the next turn to
+/// the following operator in the rotation. Out-of-turn updates are still accepted.
+fn take_turn(sentinel: &Sentinel, record: &mut SentimentRecord, signer: &Pubkey) -> bool {
+    let out_of_turn = record.preferred_updater != Pubkey::default() && record.preferred_updater != *signer;
+    // Offset by the symbol so different records don't all start on the same operator
+    let offset = record.symbol.bytes().fold(0u64, |acc, b| acc.wrapping_add(b as u64));
+    let seed = offset.wrapping_add(record.update_count as u64);
+    record.preferred_updater = next_operator(sentinel, seed).unwrap_or_default();
+    out_of_turn
+}
+
+/// Entropy for ranking tiebreaks: the most recent hash in the SlotHashes
+/// sysvar, folded to 64 bits. Unknown to anyone choosing keys in advance.
+pub fn recent_entropy(slot_hashes: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(*slot_hashes.key, SLOT_HASHES_ID, SentinelError::InvalidAccount);
+    let data = slot_hashes.try_borrow_data()?;
+    // Layout: u64 entry count, then (u64 slot, [u8; 32] hash) newest first
+    require!(data.len() >= 8 + 8 + 32, SentinelError::InvalidAccount);
+    Ok(data[16..48]
+        .chunks_exact(8)
+        .fold(0u64, |acc, chunk| acc ^ u64::from_le_bytes(chunk.try_into().unwrap())))
+}
+
+/// Order two tied ranking entries: lexically by key by default, or by a
+/// per-refresh pseudo-random value derived from `entropy` and each key.
+pub fn tie_order(a: &Pubkey, b: &Pubkey, entropy: Option<u64>) -> std::cmp::Ordering {
+    match entropy {
+        None => a.cmp(b),
+        Some(entropy) => tiebreak_value(entropy, a).cmp(&tiebreak_value(entropy, b)).then(a.cmp(b)),
+    }
+}
+
+/// splitmix64 over the key's words, seeded with `entropy`.
+fn tiebreak_value(entropy: u64, key: &Pubkey) -> u64 {
+    key.as_ref().chunks_exact(8).fold(entropy, |acc, chunk| {
+        let mut z = (acc ^ u64::from_le_bytes(chunk.try_into().unwrap())).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    })
+}
+
+/// Votes needed to resolve a value dispute: the configured quorum, or a
+/// simple majority of the current operators when unset.
+fn dispute_quorum(sentinel: &Sentinel) -> usize {
+    match sentinel.dispute_quorum {
+        0 => sentinel.operators.len() / 2 + 1,
+        q => q as usize,
+    }
+}
+
+/// Median of a non-empty set of scores; even-sized sets average the middle pair.
+fn median_score(values: &mut [i8]) -> i8 {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        ((values[mid - 1] as i16 + values[mid] as i16) / 2) as i8
+    } else {
+        values[mid]
+    }
+}
+
+/// Read a symbol's config from its (possibly uncreated) PDA.
+fn load_symbol_config(info: &AccountInfo, program_id: &Pubkey) -> Result<Option<SymbolConfig>> {
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(SymbolConfig::try_deserialize(&mut &data[..])?))
+}
+
+/// Read a symbol's community vote aggregate from its (possibly uncreated) PDA.
+fn load_vote_aggregate(info: &AccountInfo, program_id: &Pubkey) -> Result<Option<VoteAggregate>> {
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(VoteAggregate::try_deserialize(&mut &data[..])?))
+}
+
+/// Enforce a symbol's reader allowlist on CPI reads. Top-level calls are not
+/// CPI reads and are always allowed. The instructions sysvar only names the
+/// top-level program, which is the direct caller only one CPI deep; deeper
+/// reads can't be attributed and are rejected.
+fn require_reader_allowed(config: &AccountInfo, instructions: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+    let Some(config) = load_symbol_config(config, program_id)? else {
+        return Ok(());
+    };
+    let height = anchor_lang::solana_program::instruction::get_stack_height();
+    if config.reader_allowlist.is_empty() || height <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+    require!(height == TRANSACTION_LEVEL_STACK_HEIGHT + 1, SentinelError::NestedReaderCpi);
+    let caller = anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(0, instructions)?
+        .program_id;
+    require!(config.reader_allowlist.contains(&caller), SentinelError::ReaderNotAllowed);
+    Ok(())
+}
+
+/// The symbol `symbol` stands for: its alias target if `alias` is a live
+/// `SymbolAlias` PDA, otherwise `symbol` itself.
+fn resolve_symbol(alias: &AccountInfo, symbol: &str) -> String {
+    if alias.owner != &crate::ID || alias.data_is_empty() {
+        return symbol.to_string();
+    }
+    let Ok(data) = alias.try_borrow_data() else {
+        return symbol.to_string();
+    };
+    SymbolAlias::try_deserialize(&mut &data[..]).map_or_else(|_| symbol.to_string(), |a| a.target)
+}
+
+/// Reject reading timestamps further from the on-chain clock than the
+/// configured skews allow.
+fn require_timestamp_in_range(sentinel: &Sentinel, timestamp: i64, now: i64) -> Result<()> {
+    if sentinel.max_future_skew > 0 {
+        require!(timestamp - now <= sentinel.max_future_skew, SentinelError::TimestampOutOfRange);
+    }
+    if sentinel.max_past_skew > 0 {
+        require!(now - timestamp <= sentinel.max_past_skew, SentinelError::TimestampOutOfRange);
+    }
+    Ok(())
+}
+
+/// Pay a symbol's write fee from the signer to the treasury. Free symbols
+/// don't need the treasury account passed.
+fn charge_write_fee<'info>(
+    fee: u64,
+    payer: &Signer<'info>,
+    treasury: Option<&UncheckedAccount<'info>>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    let treasury = treasury.ok_or(SentinelError::TreasuryRequired)?;
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        fee,
+    )
+}
+
+/// Reject updates outside the symbol's active UTC hours. Symbols without a
+/// config account are always open.
+fn require_market_open(config: &AccountInfo, program_id: &Pubkey, now: i64) -> Result<()> {
+    let Some(config) = load_symbol_config(config, program_id)? else {
+        return Ok(());
+    };
+    let hour = now.rem_euclid(86_400) / 3_600;
+    require!(config.active_hours & (1 << hour) != 0, SentinelError::MarketClosed);
+    Ok(())
+}
+
+/// Confidence-weighted score, `score * confidence / 100` rounded half away
+/// from zero so equal and opposite scores adjust to exactly opposite values.
+fn adjusted_score(score: i8, confidence: u8) -> i16 {
+    let product = score as i16 * confidence as i16;
+    (product + product.signum() * 50) / 100
+}
+
+/// Update `record`'s volume statistics with an incoming canonical reading,
+/// emitting `VolumeAnomaly` first if it stands out from the running average.
+fn observe_volume(sentinel: &mut Sentinel, record: &mut SentimentRecord, volume: u32) {
+    if record.is_volume_anomaly(volume, sentinel.volume_anomaly_sigmas) {
+        emit_event(sentinel, VolumeAnomaly {
+            symbol: record.symbol.clone(),
+            volume,
+            volume_ema: record.volume_ema,
+            volume_stddev: record.volume_stddev,
+        });
+    }
+    record.track_volume(volume);
+
+    let volatility = record.volatility_pct();
+    let tripped = sentinel.volatility_threshold > 0 && volatility > sentinel.volatility_threshold as u64;
+    if tripped && !record.volatility_tripped {
+        emit_event(sentinel, VolatilityCircuitTripped {
+            symbol: record.symbol.clone(),
+            volatility,
+            threshold: sentinel.volatility_threshold,
+            enforced: sentinel.volatility_circuit,
+        });
+    }
+    record.volatility_tripped = tripped;
+}
+
+/// Shared body of `store_sentiment` and `store_sentiment_precise`, with the
+/// score in basis points.
+fn store_reading(
+    ctx: Context<StoreSentiment>,
+    symbol: String,
+    raw_bps: i32,
+    confidence: u8,
+    volume: u32,
+    timestamp: i64,
+    note: String,
+) -> Result<()> {
+    let raw_score = (raw_bps / 100) as i16;
+    let symbol_config = load_symbol_config(&ctx.accounts.symbol_config, ctx.program_id)?;
+    let symbol_scale = symbol_config.as_ref().map_or(SCALE_FACTOR_ONE, |c| c.scale_factor);
+    let score_bps = normalize_score_bps(&ctx.accounts.sentinel, &ctx.accounts.authority.key(), raw_bps, symbol_scale);
+    require!((-10_000..=10_000).contains(&score_bps), SentinelError::InvalidScore);
+    let score_bps = score_bps as i16;
+    let score = (score_bps / 100) as i8;
+    validate_sentiment_input(&symbol, score, confidence, timestamp)?;
+    validate_note(&note)?;
+    let sentinel = &ctx.accounts.sentinel;
+    require_unpaused(sentinel, PAUSE_STORE)?;
+
+    let authority_key = ctx.accounts.authority.key();
+    let now = Clock::get()?.unix_timestamp;
+    require_timestamp_in_range(&ctx.accounts.sentinel, timestamp, now)?;
+    charge_rate_limit(&mut ctx.accounts.sentinel, &mut ctx.accounts.operator_stats, authority_key, ctx.bumps.operator_stats, now)?;
+    charge_write_fee(
+        symbol_config.as_ref().map_or(0, |c| c.write_fee),
+        &ctx.accounts.authority,
+        ctx.accounts.treasury.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let price = match ctx.accounts.price_feed.as_ref() {
+        Some(feed) => Some(read_pyth_price(
+            &ctx.accounts.sentinel,
+            &ctx.accounts.symbol_registry,
+            feed,
+            ctx.program_id,
+            now,
+        )?),
+        None => None,
+    };
+    let provisional = operator_on_probation(&mut ctx.accounts.sentinel, &authority_key, now);
+    let submitted_confidence = confidence;
+    let confidence = confidence.min(operator_confidence_cap(&ctx.accounts.sentinel, &authority_key));
+
+    let sentiment = &mut ctx.accounts.sentiment;
+    // Re-storing an existing symbol is an update: it keeps counting and
+    // folds into the running volume statistics rather than resetting them
+    let existing = !sentiment.symbol.is_empty();
+    if existing {
+        require_unpaused(&ctx.accounts.sentinel, PAUSE_UPDATE)?;
+        require!(
+            is_authority_or_operator(&ctx.accounts.sentinel, &authority_key, CAN_UPDATE)
+                || holds_operator_scope(&ctx.accounts.operator_scope),
+            SentinelError::OperatorNotScoped
+        );
+        require!(timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+        require_direct_write(sentiment)?;
+        require_update_interval(&ctx.accounts.sentinel, sentiment, timestamp)?;
+        require_calm(&ctx.accounts.sentinel, sentiment, &authority_key)?;
+        require_score_delta(&mut ctx.accounts.sentinel, sentiment, score)?;
+    } else {
+        require_registered(&ctx.accounts.sentinel, &ctx.accounts.symbol_registry, ctx.program_id)?;
+    }
+    sentiment.symbol = symbol.clone();
+    sentiment.bump = ctx.bumps.sentiment;
+    if provisional {
+        // The record exists but carries no canonical reading until a trusted update
+        let sentinel = &mut ctx.accounts.sentinel;
+        record_provisional(sentinel, sentiment, score, confidence, volume, timestamp, authority_key);
+        return Ok(());
+    }
+
+    sentiment.score = score;
+    sentiment.score_bps = score_bps;
+    sentiment.confidence = confidence;
+    sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+    sentiment.last_update_slot = Clock::get()?.slot;
+    sentiment.volume = volume;
+    sentiment.timestamp = timestamp;
+    sentiment.updater = ctx.accounts.authority.key();
+    sentiment.last_idempotency_key = [0; 16];
+    sentiment.note = note.clone();
+    // A store without a feed clears the price rather than pairing an old one
+    // with the new reading
+    let (price, expo, publish_time) = price.unwrap_or_default();
+    sentiment.price = price;
+    sentiment.price_expo = expo;
+    sentiment.price_publish_time = publish_time;
+    if existing {
+        sentiment.update_count = sentiment.update_count.saturating_add(1);
+        observe_volume(&mut ctx.accounts.sentinel, sentiment, volume);
+    } else {
+        sentiment.update_count = 0;
+        sentiment.volume_ema = (volume as u64) << VOLUME_FP_SHIFT;
+        sentiment.volume_stddev = 0;
+    }
+    let hash = sentiment.advance_hash_chain();
+    let out_of_turn = take_turn(&ctx.accounts.sentinel, sentiment, &authority_key);
+
+    bump_total_updates(&mut ctx.accounts.sentinel, 1, now);
+
+    emit_event(&mut ctx.accounts.sentinel, SentimentUpdated {
+        symbol,
+        score,
+        raw_score,
+        confidence,
+        submitted_confidence,
+        volume,
+        timestamp,
+        updater: ctx.accounts.authority.key(),
+        note,
+        out_of_turn,
+        adjusted_score: sentiment.adjusted_score,
+        hash,
+    });
+
+    Ok(())
+}
+
+/// Reject a direct write to a record that only changes through operator
+/// consensus.
+fn require_direct_write(record: &SentimentRecord) -> Result<()> {
+    require!(record.consensus_threshold == 0, SentinelError::ConsensusRequired);
+    Ok(())
+}
+
+/// Apply an approved `PendingUpdate` as the record's new canonical reading.
+fn commit_pending_update(
+    sentinel: &mut Sentinel,
+    sentiment: &mut SentimentRecord,
+    pending: &PendingUpdate,
+    symbol_config: &AccountInfo,
+    history: Option<&mut Account<SentimentHistory>>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    // The reading must still pass everything a direct update would
+    require_market_open(symbol_config, program_id, now)?;
+    require_timestamp_in_range(sentinel, pending.timestamp, now)?;
+    require!(pending.timestamp > sentiment.timestamp, SentinelError::StaleTimestamp);
+    require_update_interval(sentinel, sentiment, pending.timestamp)?;
+    require_calm(sentinel, sentiment, &pending.proposer)?;
+    require_score_delta(sentinel, sentiment, pending.score)?;
+
+    observe_volume(sentinel, sentiment, pending.volume);
+    sentiment.score = pending.score;
+    sentiment.score_bps = pending.score as i16 * 100;
+    sentiment.confidence = pending.confidence;
+    sentiment.adjusted_score = adjusted_score(sentiment.score, sentiment.confidence);
+    sentiment.last_update_slot = Clock::get()?.slot;
+    sentiment.volume = pending.volume;
+    sentiment.timestamp = pending.timestamp;
+    sentiment.updater = pending.proposer;
+    sentiment.update_count = sentiment.update_count.saturating_add(1);
+    sentiment.note.clear();
+    let hash = sentiment.advance_hash_chain();
+    take_turn(sentinel, sentiment, &pending.proposer);
+    bump_total_updates(sentinel, 1, now);
+
+    if let Some(history) = history.filter(|_| sentiment.auto_history) {
+        history.head %= history.capacity.max(1);
+        assert_history_invariant(history)?;
+        push_history(history, snapshot_of(sentiment, now));
+        emit_event(sentinel, HistoryRecorded {
+            symbol: history.symbol.clone(),
+            entries: history.count,
+        });
+    }
+
+    emit_event(sentinel, UpdateCommitted {
+        symbol: sentiment.symbol.clone(),
+        score: sentiment.score,
+        confidence: sentiment.confidence,
+        timestamp: sentiment.timestamp,
+        approvals: pending.approvals.len() as u8,
+        hash,
+    });
+    Ok(())
+}
+
+/// Reject a reading timestamped less than `min_update_interval` after the
+/// record's current one (0 = unlimited).
+fn require_update_interval(sentinel: &Sentinel, record: &SentimentRecord, timestamp: i64) -> Result<()> {
+    let interval = sentinel.min_update_interval.max(record.cooldown);
+    require!(
+        interval == 0 || timestamp.saturating_sub(record.timestamp) >= interval,
+        SentinelError::UpdateTooFrequent
+    );
+    Ok(())
+}
+
+/// `(price, expo, publish_time)` of the aggregate price in a Pyth price
+/// account. The account must be owned by the Pyth program, be the symbol's
+/// registered feed, be trading and have published within `pyth_max_age`
+/// seconds of `now`.
+fn read_pyth_price(
+    sentinel: &Sentinel,
+    registry: &AccountInfo,
+    feed: &AccountInfo,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<(i64, i32, i64)> {
+    require!(
+        registry.owner == program_id && !registry.data_is_empty(),
+        SentinelError::SymbolNotRegistered
+    );
+    let registered = SymbolRegistry::try_deserialize(&mut &registry.try_borrow_data()?[..])?;
+    require!(
+        registered.price_feed != Pubkey::default() && registered.price_feed == *feed.key,
+        SentinelError::InvalidPriceFeed
+    );
+    require_keys_eq!(*feed.owner, PYTH_PROGRAM_ID, SentinelError::InvalidPriceFeed);
+
+    let data = feed.try_borrow_data()?;
+    require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, SentinelError::InvalidPriceFeed);
+    let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let i64_at = |offset: usize| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    require!(
+        u32_at(0) == PYTH_MAGIC && u32_at(8) == PYTH_ACCOUNT_TYPE_PRICE,
+        SentinelError::InvalidPriceFeed
+    );
+    let expo = u32_at(20) as i32;
+    let publish_time = i64_at(96);
+    let price = i64_at(208); // aggregate price
+    require!(u32_at(224) == PYTH_STATUS_TRADING, SentinelError::PriceFeedNotTrading);
+    require!(
+        now.saturating_sub(publish_time) <= sentinel.pyth_max_age,
+        SentinelError::PriceFeedStale
+    );
+    Ok((price, expo, publish_time))
+}
+
+/// Reject with `OraclePaused` while any of the `flags` pause bits is set.
+fn require_unpaused(sentinel: &Sentinel, flags: u8) -> Result<()> {
+    require!(sentinel.pause_flags & flags == 0, SentinelError::OraclePaused);
+    Ok(())
+}
+
+/// Count one write against `operator`'s rate-limit window, starting a fresh
+/// window once the current one has elapsed. Over the limit, emits
+/// `OperatorRateLimited` into the failed transaction's logs and rejects.
+fn charge_rate_limit(
+    sentinel: &mut Sentinel,
+    stats: &mut OperatorStats,
+    operator: Pubkey,
+    bump: u8,
+    now: i64,
+) -> Result<()> {
+    stats.operator = operator;
+    stats.bump = bump;
+    if sentinel.rate_limit_window == 0 {
+        return Ok(());
+    }
+    if now >= stats.window_start.saturating_add(sentinel.rate_limit_window) {
+        stats.window_start = now;
+        stats.count_in_window = 0;
+    }
+    if stats.count_in_window >= sentinel.rate_limit_max {
+        emit_event(sentinel, OperatorRateLimited {
+            operator,
+            window_start: stats.window_start,
+            count_in_window: stats.count_in_window,
+        });
+        return err!(SentinelError::OperatorRateLimited);
+    }
+    stats.count_in_window += 1;
+    Ok(())
+}
+
+/// Reject a score that moves more than `max_score_delta` points from the
+/// record's canonical score (0 = unlimited), emitting `CircuitBreakerTripped`
+/// into the failed transaction's logs for monitoring. Records without a
+/// canonical reading yet have nothing to compare against.
+fn require_score_delta(sentinel: &mut Sentinel, record: &SentimentRecord, score: i8) -> Result<()> {
+    if sentinel.max_score_delta == 0 || record.timestamp == 0 {
+        return Ok(());
+    }
+    let delta = (score as i16 - record.score as i16).unsigned_abs();
+    if delta > sentinel.max_score_delta as u16 {
+        emit_event(sentinel, CircuitBreakerTripped {
+            symbol: record.symbol.clone(),
+            old_score: record.score,
+            attempted_score: score,
+        });
+        return err!(SentinelError::ScoreDeviationTooLarge);
+    }
+    Ok(())
+}
+
+/// While a record's volatility circuit is tripped and enforced, only the root
+/// authority may write to it.
+fn require_calm(sentinel: &Sentinel, record: &SentimentRecord, signer: &Pubkey) -> Result<()> {
+    require!(
+        !(sentinel.volatility_circuit && record.volatility_tripped) || sentinel.authority == *signer,
+        SentinelError::TooVolatile
+    );
+    Ok(())
+}
+
+/// Integer square root (floor).
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Lower `confidence` in proportion to the spread of `values` (max - min, out of
+/// 200). A single value has no spread and is never penalized.
+fn spread_penalty(confidence: u8, values: &[i8], factor: u8) -> u8 {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return confidence;
+    };
+    let spread = (*max as i16 - *min as i16) as u32;
+    let reduction = confidence as u32 * spread * factor as u32 / (200 * 100);
+    confidence - reduction as u8
+}
+
+/// Left-aligned little-endian encoding of a numeric audit detail.
+fn audit_value(value: u64) -> [u8; 32] {
+    let mut detail = [0u8; 32];
+    detail[..8].copy_from_slice(&value.to_le_bytes());
+    detail
+}
+
+/// Audit detail naming a symbol: its bytes, zero-padded.
+fn audit_symbol(symbol: &str) -> [u8; 32] {
+    let mut detail = [0u8; 32];
+    detail[..symbol.len()].copy_from_slice(symbol.as_bytes());
+    detail
+}
+
+/// Append an admin action to the audit log when auditing is enabled. The log
+/// PDA is expected as the first remaining account.
+fn record_audit(
+    sentinel: &mut Sentinel,
+    remaining: &[AccountInfo],
+    program_id: &Pubkey,
+    action_code: u8,
+    actor: Pubkey,
+    detail: [u8; 32],
+) -> Result<()> {
+    if !sentinel.audit_enabled {
+        return Ok(());
+    }
+    let info = remaining.first().ok_or(SentinelError::AuditLogRequired)?;
+    let (expected, _) = Pubkey::find_program_address(&[AUDIT_LOG_SEED], program_id);
+    require_keys_eq!(*info.key, expected, SentinelError::AuditLogRequired);
+    require!(info.owner == program_id && info.is_writable, SentinelError::InvalidAccount);
+
+    let mut data = info.try_borrow_mut_data()?;
+    let mut log = AuditLog::try_deserialize(&mut &data[..])?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    log.push(AuditEntry { action_code, actor, timestamp, detail });
+    let mut writer = &mut data[..];
+    log.try_serialize(&mut writer)?;
+
+    emit_event(sentinel, AuditEntryRecorded { action_code, actor, timestamp });
+    Ok(())
+}
+
+/// Whether `signer` is an operator still inside its probation window. Once the
+/// window has elapsed the operator is promoted and `OperatorProbationEnded` is emitted.
+fn operator_on_probation(sentinel: &mut Sentinel, signer: &Pubkey, now: i64) -> bool {
+    if sentinel.authority == *signer {
+        return false;
+    }
+    let probation = sentinel.operator_probation;
+    let Some(op) = sentinel.operators.iter_mut().find(|o| o.key == *signer) else {
+        return false;
+    };
+    if !op.on_probation {
+        return false;
+    }
+    if now.saturating_sub(op.added_at) < probation {
+        return true;
+    }
+    op.on_probation = false;
+    emit_event(sentinel, OperatorProbationEnded { operator: *signer });
+    false
+}
+
+/// Record a probationary operator's reading alongside, not over, the canonical one.
+fn record_provisional(
+    sentinel: &mut Sentinel,
+    record: &mut SentimentRecord,
+    score: i8,
+    confidence: u8,
+    volume: u32,
+    timestamp: i64,
+    updater: Pubkey,
+) {
+    record.provisional_score = score;
+    record.provisional_confidence = confidence;
+    record.provisional_volume = volume;
+    record.provisional_timestamp = timestamp;
+    record.provisional_updater = updater;
+
+    emit_event(sentinel, ProvisionalSentimentRecorded {
+        symbol: record.symbol.clone(),
+        score,
+        confidence,
+        volume,
+        timestamp,
+        updater,
+    });
+}
+
+// ============================================================================
+// Data types
+// ============================================================================
+
+/// Return value of `score_display`. `score` is a percentage scaled by
+/// `10^decimals` (e.g. 42 with 2 decimals is 4200).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ScoreDisplay {
+    pub score: i32,
+    pub decimals: u8,
+    pub confidence: u8,
+    pub label: String, // Bullish / Neutral / Bearish
+}
+
+/// Return value of `get_sentiment_with_slot`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SentimentWithSlot {
+    pub score: i8,
+    pub confidence: u8,
+    pub record_slot: u64, // slot of the record's last canonical write
+    pub current_slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SentimentView {
+    pub score: i8,
+    pub confidence: u8,
+    pub volume: u32,
+    pub timestamp: i64,
+    pub stale: bool, // older than the caller's max_age
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SentinelStats {
+    pub total_updates: u64,
+    pub operator_count: u8,
+    pub paused: bool, // any pause flag set
+    pub uptime_seconds: i64,
+    pub event_seq: u64, // last sequence number emitted
+}
+
+/// Return value of `get_config`. Decode according to `schema_version`
+/// (`CONFIG_SCHEMA_VERSION`); operator and authority lists are summarized as
+/// counts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SentinelConfigView {
+    pub schema_version: u8,
+    pub authority: Pubkey,
+    pub pause_flags: u8,
+    pub operator_count: u8,
+    pub authority_count: u8,
+    pub operators_enabled: bool,
+    pub operator_probation: i64,
+    pub reputation_tombstones: bool,
+    pub events_enabled: bool,
+    pub dispute_quorum: u8,
+    pub min_profile_age: i64,
+    pub min_update_interval: i64,
+    pub spread_penalty_factor: u8,
+    pub audit_enabled: bool,
+    pub volume_anomaly_sigmas: u8,
+    pub display_decimals: u8,
+    pub bullish_threshold: i8,
+    pub bearish_threshold: i8,
+    pub prediction_horizon: i64,
+    pub expiry_penalty: u16,
+    pub random_tiebreak: bool,
+    pub proposal_window: i64,
+    pub min_endorser_reputation: u16,
+    pub volatility_threshold: u16,
+    pub volatility_circuit: bool,
+    pub accuracy_tolerance: u8,
+    pub require_tracked_symbol: bool,
+    pub watchdog_timeout: i64,
+    pub max_score_delta: u8,
+    pub reputation_decay_period: i64,
+    pub reputation_floor: u16,
+    pub permissionless_symbols: bool,
+    pub max_vote_weight: u16,
+    pub rate_limit_window: i64,
+    pub rate_limit_max: u16,
+    pub min_predictions_for_expert: u32,
+    pub pyth_max_age: i64,
+    pub gate_mint: Option<Pubkey>,
+    pub min_gate_balance: u64,
+    pub contribution_window: i64,
+    pub max_future_skew: i64,
+    pub max_past_skew: i64,
+    pub treasury: Pubkey,
+    pub community_weight: u8,
+    pub min_operator_rep: u16,
+    pub min_operator_predictions: u32,
+    pub min_dispute_rep: u16,
+    pub reward_correct: u16,
+    pub penalty_wrong: u16,
+    pub rep_cap: u16,
+    pub milestone_interval: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SymbolConfigInput {
+    pub active_hours: u32,
+    pub decimals: Option<u8>,
+    pub scale_factor: i16,
+    pub metadata: String,
+    pub write_fee: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AggregateSummary {
+    pub symbol: String,
+    pub mean_score: i8,
+    pub vote_count: u32,
+    pub confidence: u8, // mean voter confidence
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SentimentInput {
+    pub score: i16, // native to the submitter, before its offset and scale factor
+    pub confidence: u8,
+    pub volume: u32,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OperatorEntry {
+    pub key: Pubkey,
+    pub added_at: i64,
+    pub on_probation: bool,
+    pub max_confidence: u8, // submitted confidence is clamped to this
+    pub scale_factor: i16,  // raw score multiplier, SCALE_FACTOR_ONE = 1.0
+    pub score_offset: i16,  // subtracted from raw scores before scaling
+    pub perms: u8,          // CAN_* bits
+}
+
+impl OperatorEntry {
+    pub const LEN: usize = 32 + 8 + 1 + 1 + 2 + 2 + 1; // 47
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AuthorityEntry {
+    pub key: Pubkey,
+    pub scope_flags: u16, // SCOPE_* bits
+}
+
+impl AuthorityEntry {
+    pub const LEN: usize = 32 + 2; // 34
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct AuditEntry {
+    pub action_code: u8, // AUDIT_* constant
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub detail: [u8; 32], // affected key, or a little-endian value
+}
+
+impl AuditEntry {
+    pub const LEN: usize = 1 + 32 + 8 + 32; // 73
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DisputeVote {
+    pub operator: Pubkey,
+    pub value: i8,
+}
+
+impl DisputeVote {
+    pub const LEN: usize = 32 + 1; // 33
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct HistoryEntry {
+    pub score: i8,
+    pub confidence: u8,
+    pub volume: u32,
+    pub timestamp: i64,
+    pub recorded_at: i64,
+}
+
+impl HistoryEntry {
+    pub const LEN: usize = 1 + 1 + 4 + 8 + 8; // 22
+}
+
+/// Borsh shape of one account field, enough to find where an older layout of
+/// the account ends. Each account `migrate_account` can grow lists its fields
+/// as `FIELDS`, in declaration order and without `bump`.
+#[derive(Clone, Copy, Debug)]
+pub enum FieldShape {
+    Fixed(usize),       // integers, bools, keys and arrays
+    Bytes(usize),       // String, with its max length
+    List(usize, usize), // Vec: element size, max elements
+    Optional(usize),    // Option of a fixed-size value
+}
+
+use FieldShape::*;
+
+impl FieldShape {
+    /// Bytes the field reserves at its largest.
+    pub const fn max_len(self) -> usize {
+        match self {
+            Fixed(n) => n,
+            Bytes(max) => 4 + max,
+            List(size, max) => 4 + size * max,
+            Optional(n) => 1 + n,
+        }
+    }
+
+    /// Bytes the field takes serialized at its zero value (0, empty, `None`).
+    pub const fn zero_len(self) -> usize {
+        match self {
+            Fixed(n) => n,
+            Bytes(_) | List(..) => 4,
+            Optional(_) => 1,
+        }
+    }
+
+    /// Bytes the field takes serialized at the start of `data`.
+    pub fn encoded_len(self, data: &[u8]) -> Result<usize> {
+        let prefix = || -> Result<usize> {
+            let bytes = data.get(..4).ok_or(SentinelError::InvalidAccount)?;
+            Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        };
+        Ok(match self {
+            Fixed(n) => n,
+            Bytes(max) => {
+                let len = prefix()?;
+                require!(len <= max, SentinelError::InvalidAccount);
+                4 + len
+            }
+            List(size, max) => {
+                let len = prefix()?;
+                require!(len <= max, SentinelError::InvalidAccount);
+                4 + size * len
+            }
+            Optional(n) => match data.first() {
+                Some(0) => 1,
+                Some(1) => 1 + n,
+                _ => return err!(SentinelError::InvalidAccount),
+            },
+        })
+    }
+}
+
+/// The current `FIELDS` and size of the account type with `discriminator`,
+/// if `migrate_account` can grow it.
+fn migratable_layout(discriminator: &[u8]) -> Option<(&'static [FieldShape], usize)> {
+    [
+        (SentimentRecord::DISCRIMINATOR, SentimentRecord::FIELDS, SentimentRecord::LEN),
+        (UserProfile::DISCRIMINATOR, UserProfile::FIELDS, UserProfile::LEN),
+        (Subscription::DISCRIMINATOR, Subscription::FIELDS, Subscription::LEN),
+        (CommunityVote::DISCRIMINATOR, CommunityVote::FIELDS, CommunityVote::LEN),
+        (VoteAggregate::DISCRIMINATOR, VoteAggregate::FIELDS, VoteAggregate::LEN),
+        (SymbolRegistry::DISCRIMINATOR, SymbolRegistry::FIELDS, SymbolRegistry::LEN),
+        (SymbolConfig::DISCRIMINATOR, SymbolConfig::FIELDS, SymbolConfig::LEN),
+        (OperatorStake::DISCRIMINATOR, OperatorStake::FIELDS, OperatorStake::LEN),
+    ]
+    .into_iter()
+    .find(|(d, ..)| *d == discriminator)
+    .map(|(_, fields, len)| (fields, len))
+}
+
+/// Account size of a layout holding `fields` between its discriminator and
+/// `bump`.
+pub const fn layout_len(fields: &[FieldShape]) -> usize {
+    let mut len = 8 + 1;
+    let mut i = 0;
+    while i < fields.len() {
+        len += fields[i].max_len();
+        i += 1;
+    }
+    len
+}
+
+// Each `FIELDS` list must describe its account's whole current layout
+const _: () = {
+    assert!(layout_len(SentimentRecord::FIELDS) == SentimentRecord::LEN);
+    assert!(layout_len(UserProfile::FIELDS) == UserProfile::LEN);
+    assert!(layout_len(Subscription::FIELDS) == Subscription::LEN);
+    assert!(layout_len(CommunityVote::FIELDS) == CommunityVote::LEN);
+    assert!(layout_len(VoteAggregate::FIELDS) == VoteAggregate::LEN);
+    assert!(layout_len(SymbolRegistry::FIELDS) == SymbolRegistry::LEN);
+    assert!(layout_len(SymbolConfig::FIELDS) == SymbolConfig::LEN);
+    assert!(layout_len(OperatorStake::FIELDS) == OperatorStake::LEN);
+};
+
+/// A snapshot stored relative to the previous one. Keyframe slots carry no
+/// delta; their absolute value lives in `DeltaHistory::keyframes`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DeltaEntry {
+    pub keyframe: bool,
+    pub score: i8,
+    pub confidence: i8,
+    pub volume: i16,
+    pub timestamp: u16,
+    pub recorded_at: u16,
+}
+
+impl DeltaEntry {
+    pub const LEN: usize = 1 + 1 + 1 + 2 + 2 + 2; // 9
+}
+
+// ============================================================================
+// Accounts
+// ============================================================================
+
+#[account]
+pub struct Sentinel {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>, // nominated, awaiting accept_authority
+    pub total_updates: u64,
+    pub pause_flags: u8, // PAUSE_* bits
+    pub operators: Vec<OperatorEntry>,  // up to MAX_OPERATORS
+    pub authorities: Vec<AuthorityEntry>, // scoped admins, up to MAX_AUTHORITIES
+    pub operators_enabled: bool,
+    pub operator_probation: i64, // seconds new operators stay provisional
+    pub reputation_tombstones: bool,
+    pub events_enabled: bool,
+    pub dispute_quorum: u8,      // 0 = simple majority of operators
+    pub min_profile_age: i64,    // seconds before a new profile may vote
+    pub min_update_interval: i64, // seconds between updates of a symbol, 0 = unlimited
+    pub spread_penalty_factor: u8, // percent, 0 = no disagreement penalty
+    pub audit_enabled: bool,
+    pub volume_anomaly_sigmas: u8, // 0 = no VolumeAnomaly events
+    pub display_decimals: u8,      // score_display precision
+    pub bullish_threshold: i8,     // score_display labels: >= bullish, <= bearish
+    pub bearish_threshold: i8,
+    pub prediction_horizon: i64, // seconds before an unresolved vote may expire, 0 = never
+    pub expiry_penalty: u16,     // reputation lost when a vote expires unresolved
+    pub random_tiebreak: bool,   // rank ties by tie_order entropy instead of key order
+    pub proposal_window: i64,    // seconds an operator proposal stays open for review
+    pub min_endorser_reputation: u16, // reputation needed to endorse or object
+    pub volatility_threshold: u16, // volume stddev as % of EMA that trips the circuit, 0 = off
+    pub volatility_circuit: bool,  // reject non-root writes while tripped (else only emit)
+    pub accuracy_tolerance: u8,    // score points a prediction may miss by and still be correct
+    pub require_tracked_symbol: bool, // votes need an existing SentimentRecord
+    pub watchdog_timeout: i64,   // seconds without any write before check_watchdog pauses, 0 = off
+    pub last_update_ts: i64,     // last canonical write to any symbol, or watchdog re-arm
+    pub max_score_delta: u8,     // largest allowed score move per reading, 0 = unlimited
+    pub reputation_decay_period: i64, // idle seconds per reputation point lost
+    pub reputation_floor: u16,   // decay stops here
+    pub permissionless_symbols: bool, // store_sentiment may create unregistered symbols
+    pub max_vote_weight: u16,    // reputation cap per vote in the weighted consensus
+    pub rate_limit_window: i64,  // seconds per operator rate-limit window, 0 = unlimited
+    pub rate_limit_max: u16,     // sentiment writes allowed per operator per window
+    pub min_predictions_for_expert: u32, // sample size needed for the Expert and Oracle tiers
+    pub pyth_max_age: i64,       // seconds a Pyth price may lag the store carrying it
+    pub gate_mint: Option<Pubkey>, // SPL token voters must hold, None = open voting
+    pub min_gate_balance: u64,   // raw token units of gate_mint needed to vote
+    pub contribution_window: i64, // seconds contribute_sentiment readings are averaged over
+    pub max_future_skew: i64,    // seconds a reading may be dated ahead of the clock, 0 = unchecked
+    pub max_past_skew: i64,      // seconds a reading may be dated behind the clock, 0 = unchecked
+    pub initialized_at: i64,     // unix time of initialize (or migrate_sentinel)
+    pub treasury: Pubkey,        // receives slashed operator stake
+    pub community_weight: u8,    // percent of publish_blended taken from the community consensus
+    pub event_seq: u64,          // seq of the last EventSequenced emitted
+    pub min_operator_rep: u16,   // reputation a profile needs for nominate_operator_from_profile
+    pub min_operator_predictions: u32, // predictions a profile needs for nominate_operator_from_profile
+    pub min_dispute_rep: u16,    // reputation a profile needs to open_dispute
+    pub reward_correct: u16,     // reputation gained per correct prediction
+    pub penalty_wrong: u16,      // reputation lost per wrong prediction
+    pub rep_cap: u16,            // reputation ceiling for prediction and dispute rewards
+    pub milestone_interval: u64, // 0 = powers of ten
+    pub min_operator_stake: u64, // unslashed lamports an operator needs staked to write, 0 = none
+    pub unstake_delay: i64,      // seconds between request_unstake and withdraw_stake
+    pub bump: u8,
+}
+
+impl Sentinel {
+    pub const LEN: usize = 8  // discriminator
+        + 32                   // authority
+        + 1 + 32               // pending_authority
+        + 8                    // total_updates
+        + 1                    // pause_flags
+        + 4 + (OperatorEntry::LEN * MAX_OPERATORS)  // operators vec
+        + 4 + (AuthorityEntry::LEN * MAX_AUTHORITIES)  // authorities vec
+        + 1                    // operators_enabled
+        + 8                    // operator_probation
+        + 1                    // reputation_tombstones
+        + 1                    // events_enabled
+        + 1                    // dispute_quorum
+        + 8                    // min_profile_age
+        + 8                    // min_update_interval
+        + 1                    // spread_penalty_factor
+        + 1                    // audit_enabled
+        + 1                    // volume_anomaly_sigmas
+        + 1 + 1 + 1            // display_decimals, bullish/bearish thresholds
+        + 8                    // prediction_horizon
+        + 2                    // expiry_penalty
+        + 1                    // random_tiebreak
+        + 8                    // proposal_window
+        + 2                    // min_endorser_reputation
+        + 2                    // volatility_threshold
+        + 1                    // volatility_circuit
+        + 1                    // accuracy_tolerance
+        + 1                    // require_tracked_symbol
+        + 8                    // watchdog_timeout
+        + 8                    // last_update_ts
+        + 1                    // max_score_delta
+        + 8                    // reputation_decay_period
+        + 2                    // reputation_floor
+        + 1                    // permissionless_symbols
+        + 2                    // max_vote_weight
+        + 8                    // rate_limit_window
+        + 2                    // rate_limit_max
+        + 4                    // min_predictions_for_expert
+        + 8                    // pyth_max_age
+        + 1 + 32               // gate_mint
+        + 8                    // min_gate_balance
+        + 8                    // contribution_window
+        + 8 + 8                // max_future_skew, max_past_skew
+        + 8                    // initialized_at
+        + 32                   // treasury
+        + 1                    // community_weight
+        + 8                    // event_seq
+        + 2 + 4                // min_operator_rep, min_operator_predictions
+        + 2                    // min_dispute_rep
+        + 2 + 2 + 2            // reward_correct, penalty_wrong, rep_cap
+        + 8                    // milestone_interval
+        + 8                    // min_operator_stake
+        + 8                    // unstake_delay
+        + 1;                   // bump
+}
+
+#[account]
+pub struct SentimentRecord {
+    pub symbol: String,
+    pub score: i8,
+    pub confidence: u8,
+    pub volume: u32,
+    pub timestamp: i64,
+    pub updater: Pubkey,
+    pub update_count: u32,
+    pub last_idempotency_key: [u8; 16], // zeroed when the last update had no key
+    // Latest reading from an operator on probation; never affects the fields above
+    pub provisional_score: i8,
+    pub provisional_confidence: u8,
+    pub provisional_volume: u32,
+    pub provisional_timestamp: i64,
+    pub provisional_updater: Pubkey,
+    pub note: String, // free-form operator note, printable ASCII, empty by default
+    pub volume_ema: u64,    // fixed point, VOLUME_FP_SHIFT fraction bits
+    pub volume_stddev: u64, // fixed point, VOLUME_FP_SHIFT fraction bits
+    pub preferred_updater: Pubkey, // operator expected to submit next, default = anyone
+    pub adjusted_score: i16,       // score * confidence / 100, rounded half away from zero
+    pub volatility_tripped: bool,  // volatility_pct above the sentinel threshold as of the last write
+    pub last_update_slot: u64,     // slot of the last canonical write
+    pub score_bps: i16,            // score in basis points; `score` is score_bps / 100
+    pub consensus_threshold: u8,   // operator approvals a new reading needs, 0 = direct writes
+    pub price: i64,                // Pyth price passed with the last store, 0 = none
+    pub price_expo: i32,
+    // Confidence-weighted mean of contribute_sentiment readings in the current window
+    pub source_count: u8,
+    pub window_start: i64,
+    pub contrib_score_sum: i64,  // sum of score * weight
+    pub contrib_weight_sum: u32, // sum of weights (confidence, at least 1)
+    pub cooldown: i64, // minimum seconds between readings of this symbol, 0 = global interval only
+    pub blended_score: i8, // oracle/community blend as of the last publish_blended
+    pub expected_interval: i64, // seconds between readings check_liveness expects, 0 = unmonitored
+    pub disputed: bool,         // a community dispute is open against the current reading
+    pub auto_history: bool,     // update_sentiment snapshots into the history ring when it's passed
+    pub prev_hash: [u8; 32],    // head of the hash chain over canonical readings, see advance_hash_chain
+    pub price_publish_time: i64, // when Pyth published `price`
+    pub contributors: Vec<Pubkey>, // keys in the current contribution window, at most MAX_CONTRIBUTORS
+    pub bump: u8,
+}
+
+impl SentimentRecord {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 4 + 8 + 32 + 4 + 16
+        + 1 + 1 + 4 + 8 + 32 // provisional reading
+        + 4 + MAX_NOTE_LEN   // note
+        + 8 + 8              // volume_ema, volume_stddev
+        + 32                 // preferred_updater
+        + 2                  // adjusted_score
+        + 1                  // volatility_tripped
+        + 8                  // last_update_slot
+        + 2                  // score_bps
+        + 1                  // consensus_threshold
+        + 8 + 4              // price, price_expo
+        + 1 + 8 + 8 + 4      // contribution window
+        + 8                  // cooldown
+        + 1                  // blended_score
+        + 8                  // expected_interval
+        + 1                  // disputed
+        + 1                  // auto_history
+        + 32                 // prev_hash
+        + 8                  // price_publish_time
+        + 4 + 32 * MAX_CONTRIBUTORS // contributors
+        + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Bytes(MAX_SYMBOL_LEN), Fixed(1), Fixed(1), Fixed(4), Fixed(8), Fixed(32), Fixed(4),
+        Fixed(16),                                      // last_idempotency_key
+        Fixed(1), Fixed(1), Fixed(4), Fixed(8), Fixed(32), // provisional reading
+        Bytes(MAX_NOTE_LEN),                            // note
+        Fixed(8), Fixed(8),                             // volume_ema, volume_stddev
+        Fixed(32),                                      // preferred_updater
+        Fixed(2),                                       // adjusted_score
+        Fixed(1),                                       // volatility_tripped
+        Fixed(8),                                       // last_update_slot
+        Fixed(2),                                       // score_bps
+        Fixed(1),                                       // consensus_threshold
+        Fixed(8), Fixed(4),                             // price, price_expo
+        Fixed(1), Fixed(8), Fixed(8), Fixed(4),         // contribution window
+        Fixed(8),                                       // cooldown
+        Fixed(1),                                       // blended_score
+        Fixed(8),                                       // expected_interval
+        Fixed(1),                                       // disputed
+        Fixed(1),                                       // auto_history
+        Fixed(32),                                      // prev_hash
+        Fixed(8),                                       // price_publish_time
+        List(32, MAX_CONTRIBUTORS),                     // contributors
+    ];
+
+    /// Chain the current reading onto the previous one and store the result:
+    /// `hash(prev_hash || score || confidence || volume || timestamp)`, with
+    /// integers little-endian. Called after every change to the canonical
+    /// reading so auditors can replay the chain from the emitted events.
+    pub fn advance_hash_chain(&mut self) -> [u8; 32] {
+        let mut preimage = Vec::with_capacity(32 + 1 + 1 + 4 + 8);
+        preimage.extend_from_slice(&self.prev_hash);
+        preimage.push(self.score as u8);
+        preimage.push(self.confidence);
+        preimage.extend_from_slice(&self.volume.to_le_bytes());
+        preimage.extend_from_slice(&self.timestamp.to_le_bytes());
+        self.prev_hash = hash(&preimage).to_bytes();
+        self.prev_hash
+    }
+
+    /// Whether `volume` exceeds the EMA by more than `threshold_sigmas`
+    /// standard deviations. Never true before any spread has been observed.
+    pub fn is_volume_anomaly(&self, volume: u32, threshold_sigmas: u8) -> bool {
+        if threshold_sigmas == 0 || self.volume_stddev == 0 {
+            return false;
+        }
+        let volume_fp = (volume as u128) << VOLUME_FP_SHIFT;
+        volume_fp > self.volume_ema as u128 + threshold_sigmas as u128 * self.volume_stddev as u128
+    }
+
+    /// Whether the reading is more than `max_age` seconds older than `now`.
+    pub fn is_stale(&self, now: i64, max_age: i64) -> bool {
+        now.saturating_sub(self.timestamp) > max_age
+    }
+
+    /// Confidence halved for every `half_life` seconds between the reading's
+    /// timestamp and `now`, interpolated linearly within a half-life. Future
+    /// timestamps don't decay; `half_life` must be positive.
+    pub fn decayed_confidence(&self, now: i64, half_life: i64) -> u8 {
+        let age = now.saturating_sub(self.timestamp).max(0);
+        let halvings = age / half_life;
+        if halvings >= 8 {
+            return 0;
+        }
+        let start = (self.confidence >> halvings) as i64;
+        let lost = (start - start / 2) * (age % half_life) / half_life;
+        (start - lost) as u8
+    }
+
+    /// Volume standard deviation as a percentage of its EMA (0 before any volume).
+    pub fn volatility_pct(&self) -> u64 {
+        if self.volume_ema == 0 {
+            return 0;
+        }
+        (self.volume_stddev as u128 * 100 / self.volume_ema as u128) as u64
+    }
+
+    /// Fold `volume` into the exponentially weighted mean and variance.
+    pub fn track_volume(&mut self, volume: u32) {
+        let x = ((volume as u64) << VOLUME_FP_SHIFT) as i128;
+        let ema = self.volume_ema as i128;
+        let diff = x - ema;
+        let variance = self.volume_stddev as i128 * self.volume_stddev as i128;
+        // EWMV: var' = (1 - a) * (var + a * diff^2), with a = 1 / 2^VOLUME_EMA_SHIFT
+        let variance = ((variance + ((diff * diff) >> VOLUME_EMA_SHIFT))
+            * ((1 << VOLUME_EMA_SHIFT) - 1))
+            >> VOLUME_EMA_SHIFT;
+        self.volume_ema = (ema + (diff >> VOLUME_EMA_SHIFT)) as u64;
+        self.volume_stddev = isqrt(variance as u128) as u64;
+    }
+}
+
+#[account]
+pub struct SentimentHistory {
+    pub symbol: String,
+    pub head: u16,  // next slot to write, always < capacity
+    pub count: u16,
+    pub capacity: u16, // ring-buffer size, fixed at creation, up to MAX_HISTORY_CAP
+    pub snapshots: Vec<HistoryEntry>,
+    pub bump: u8,
+}
+
+impl SentimentHistory {
+    /// Size of a history created with the default `MAX_HISTORY` capacity.
+    pub const LEN: usize = Self::space(MAX_HISTORY);
+
+    pub const fn space(capacity: usize) -> usize {
+        8
+        + 4 + MAX_SYMBOL_LEN     // symbol
+        + 2                       // head
+        + 2                       // count
+        + 2                       // capacity
+        + 4 + (HistoryEntry::LEN * capacity) // snapshots vec
+        + 1                       // bump
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    /// Recorded snapshots, oldest first. Assumes the ring-buffer invariant holds.
+    pub fn chronological(&self) -> Vec<HistoryEntry> {
+        let count = self.count as usize;
+        let capacity = self.capacity();
+        let start = (self.head as usize + capacity - count) % capacity;
+        (0..count).map(|i| self.snapshots[(start + i) % capacity].clone()).collect()
+    }
+}
+
+/// Ring buffer of admin actions, written only while `Sentinel::audit_enabled`.
+#[account]
+pub struct AuditLog {
+    pub head: u16, // next slot to write
+    pub count: u16,
+    pub entries: Vec<AuditEntry>,
+    pub bump: u8,
+}
+
+impl AuditLog {
+    pub const LEN: usize = 8
+        + 2                       // head
+        + 2                       // count
+        + 4 + (AuditEntry::LEN * MAX_AUDIT_ENTRIES) // entries vec
+        + 1;                      // bump
+
+    pub fn push(&mut self, entry: AuditEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = ((self.head as usize + 1) % MAX_AUDIT_ENTRIES) as u16;
+        if (self.count as usize) < MAX_AUDIT_ENTRIES {
+            self.count += 1;
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub reputation: u16,
+}
+
+impl LeaderboardEntry {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// The `LEADERBOARD_SIZE` highest-reputation predictors, best first. Ties
+/// are ranked by `tie_order`: key order, or fresh entropy on every refresh
+/// when `random_tiebreak` is on.
+#[account]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    pub const LEN: usize = 8
+        + 4 + (LeaderboardEntry::LEN * LEADERBOARD_SIZE) // entries vec
+        + 1;                      // bump
+
+    /// Record `user`'s current reputation: update its entry in place, or
+    /// insert it if the board has room or it beats the lowest entry. Returns
+    /// the user's new rank, or `None` if the board is unchanged.
+    pub fn record(&mut self, user: Pubkey, reputation: u16, entropy: Option<u64>) -> Option<u8> {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.user == user) {
+            if entry.reputation == reputation {
+                return None;
+            }
+            entry.reputation = reputation;
+        } else if self.entries.len() < LEADERBOARD_SIZE {
+            self.entries.push(LeaderboardEntry { user, reputation });
+        } else {
+            let lowest = self.entries.last_mut()?;
+            if reputation <= lowest.reputation {
+                return None;
+            }
+            *lowest = LeaderboardEntry { user, reputation };
+        }
+        self.entries.sort_by(|a, b| {
+            b.reputation.cmp(&a.reputation).then_with(|| tie_order(&a.user, &b.user, entropy))
+        });
+        self.entries.iter().position(|e| e.user == user).map(|rank| rank as u8)
+    }
+
+    /// Drop `user`'s entry, returning whether it was on the board.
+    pub fn remove(&mut self, user: &Pubkey) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.user != *user);
+        self.entries.len() != before
+    }
+}
+
+/// Delta-encoded ring buffer of snapshots. Entries are grouped into runs, each
+/// starting with a keyframe; the oldest run is evicted whole when space runs out.
+#[account]
+pub struct DeltaHistory {
+    pub symbol: String,
+    pub head: u16,               // next slot to write
+    pub count: u16,
+    pub kf_head: u16,            // next keyframe to write
+    pub kf_count: u16,
+    pub since_keyframe: u16,     // entries in the current run
+    pub last: HistoryEntry,      // most recent absolute entry, base for the next delta
+    pub slots: Vec<DeltaEntry>,
+    pub keyframes: Vec<HistoryEntry>,
+    pub bump: u8,
+}
+
+impl DeltaHistory {
+    pub const LEN: usize = 8
+        + 4 + MAX_SYMBOL_LEN     // symbol
+        + 2 + 2 + 2 + 2 + 2       // head, count, kf_head, kf_count, since_keyframe
+        + HistoryEntry::LEN       // last
+        + 4 + (DeltaEntry::LEN * MAX_DELTA_HISTORY)      // slots vec
+        + 4 + (HistoryEntry::LEN * MAX_DELTA_KEYFRAMES)  // keyframes vec
+        + 1;                      // bump
+
+    /// Append a snapshot, returning whether it was stored as a keyframe.
+    pub fn push(&mut self, entry: HistoryEntry) -> bool {
+        let delta = if self.count == 0 || self.since_keyframe >= DELTA_KEYFRAME_INTERVAL {
+            None
+        } else {
+            encode_history_delta(&self.last, &entry)
+        };
+
+        let keyframe = delta.is_none();
+        match delta {
+            Some(delta) => {
+                // Runs are much shorter than the buffer, so this never evicts the current run
+                if self.count as usize == MAX_DELTA_HISTORY {
+                    self.evict_oldest_run();
+                }
+                self.write_slot(delta);
+                self.since_keyframe += 1;
+            }
+            None => {
+                if self.count as usize == MAX_DELTA_HISTORY
+                    || self.kf_count as usize == MAX_DELTA_KEYFRAMES
+                {
+                    self.evict_oldest_run();
+                }
+                self.keyframes[self.kf_head as usize] = entry.clone();
+                self.kf_head = ((self.kf_head as usize + 1) % MAX_DELTA_KEYFRAMES) as u16;
+                self.kf_count += 1;
+                self.write_slot(DeltaEntry { keyframe: true, ..Default::default() });
+                self.since_keyframe = 1;
+            }
+        }
+
+        self.last = entry;
+        keyframe
+    }
+
+    /// Reconstruct all stored snapshots in chronological order.
+    pub fn decode(&self) -> Vec<HistoryEntry> {
+        let mut entries = Vec::with_capacity(self.count as usize);
+        let mut kf = self.keyframe_tail();
+        let mut current = HistoryEntry::default();
+        for i in 0..self.count as usize {
+            let slot = &self.slots[(self.tail() + i) % MAX_DELTA_HISTORY];
+            current = if slot.keyframe {
+                let entry = self.keyframes[kf].clone();
+                kf = (kf + 1) % MAX_DELTA_KEYFRAMES;
+                entry
+            } else {
+                apply_history_delta(&current, slot)
+            };
+            entries.push(current.clone());
+        }
+        entries
+    }
+
+    fn tail(&self) -> usize {
+        (self.head as usize + MAX_DELTA_HISTORY - self.count as usize) % MAX_DELTA_HISTORY
+    }
+
+    fn keyframe_tail(&self) -> usize {
+        (self.kf_head as usize + MAX_DELTA_KEYFRAMES - self.kf_count as usize) % MAX_DELTA_KEYFRAMES
+    }
+
+    fn write_slot(&mut self, slot: DeltaEntry) {
+        self.slots[self.head as usize] = slot;
+        self.head = ((self.head as usize + 1) % MAX_DELTA_HISTORY) as u16;
+        self.count += 1;
+    }
+
+    /// Drop the oldest keyframe and every delta that depends on it.
+    fn evict_oldest_run(&mut self) {
+        self.count -= 1;
+        self.kf_count -= 1;
+        while self.count > 0 && !self.slots[self.tail()].keyframe {
+            self.count -= 1;
+        }
+    }
+}
+
+/// History packed as a base entry followed by variable-width deltas. Score and
+/// confidence deltas are one byte each; volume and timestamp deltas are zigzag
+/// varints, so small steps cost a byte and large ones only a few more.
+#[account]
+pub struct CompactHistory {
+    pub symbol: String,
+    pub count: u16,              // entries including the base
+    pub base: HistoryEntry,      // oldest retained entry
+    pub last: HistoryEntry,      // newest entry, base for the next delta
+    pub data: Vec<u8>,           // encoded deltas, oldest first
+    pub bump: u8,
+}
+
+impl CompactHistory {
+    pub const LEN: usize = 8
+        + 4 + MAX_SYMBOL_LEN               // symbol
+        + 2                                // count
+        + HistoryEntry::LEN * 2            // base, last
+        + 4 + MAX_COMPACT_HISTORY_BYTES    // data vec
+        + 1;                               // bump
+
+    /// Append a snapshot, folding the oldest deltas into `base` until it fits.
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self.count == 0 {
+            self.base = entry.clone();
+        } else {
+            let mut encoded = Vec::new();
+            encode_compact_delta(&mut encoded, &self.last, &entry);
+            while self.data.len() + encoded.len() > MAX_COMPACT_HISTORY_BYTES {
+                let mut pos = 0;
+                self.base = decode_compact_delta(&self.data, &mut pos, &self.base);
+                self.data.drain(..pos);
+                self.count -= 1;
+            }
+            self.data.extend_from_slice(&encoded);
+        }
+        self.count += 1;
+        self.last = entry;
+    }
+
+    /// Reconstruct all stored snapshots in chronological order.
+    pub fn decode(&self) -> Vec<HistoryEntry> {
+        let mut entries = Vec::with_capacity(self.count as usize);
+        if self.count == 0 {
+            return entries;
+        }
+        entries.push(self.base.clone());
+        let mut pos = 0;
+        while pos < self.data.len() {
+            let next = decode_compact_delta(&self.data, &mut pos, &entries[entries.len() - 1]);
+            entries.push(next);
+        }
+        entries
+    }
+}
+
+#[account]
+pub struct OperatorDispute {
+    pub symbol: String,
+    pub opener: Pubkey,
+    pub opened_at: i64,
+    pub open: bool,
+    pub votes: Vec<DisputeVote>, // one per operator
+    pub bump: u8,
+}
+
+impl OperatorDispute {
+    pub const LEN: usize = 8
+        + 4 + MAX_SYMBOL_LEN     // symbol
+        + 32                      // opener
+        + 8                       // opened_at
+        + 1                       // open
+        + 4 + (DisputeVote::LEN * MAX_OPERATORS) // votes vec
+        + 1;                      // bump
+}
+
+/// A community challenge to a symbol's reading, reused for later disputes
+/// once resolved.
+#[account]
+pub struct Dispute {
+    pub symbol: String,
+    pub disputer: Pubkey,
+    pub reason_code: u8, // client-defined
+    pub disputed_score: i8,
+    pub opened_at: i64,
+    pub open: bool,
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 32 + 1 + 1 + 8 + 1 + 1;
+}
+
+/// Community review of a proposed operator, closed by `finalize_operator`.
+#[account]
+pub struct OperatorProposal {
+    pub candidate: Pubkey,
+    pub proposer: Pubkey,
+    pub opened_at: i64,
+    pub closes_at: i64,
+    pub endorsements: u16,
+    pub objections: u16,
+    pub voters: Vec<Pubkey>, // profiles that endorsed or objected, up to MAX_PROPOSAL_VOTERS
+    pub bump: u8,
+}
+
+impl OperatorProposal {
+    pub const LEN: usize = 8
+        + 32                      // candidate
+        + 32                      // proposer
+        + 8                       // opened_at
+        + 8                       // closes_at
+        + 2 + 2                   // endorsements, objections
+        + 4 + (32 * MAX_PROPOSAL_VOTERS) // voters vec
+        + 1;                      // bump
+}
+
+/// Grants one operator write access to a single symbol.
+#[account]
+pub struct OperatorScope {
+    pub operator: Pubkey,
+    pub symbol: String,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+impl OperatorScope {
+    pub const LEN: usize = 8
+        + 32                      // operator
+        + 4 + MAX_SYMBOL_LEN      // symbol
+        + 32                      // granted_by
+        + 8                       // granted_at
+        + 1;                      // bump
+}
+
+/// Per-writer sentiment write count within the current rate-limit window.
+#[account]
+pub struct OperatorStats {
+    pub operator: Pubkey,
+    pub window_start: i64,
+    pub count_in_window: u16,
+    pub bump: u8,
+}
+
+impl OperatorStats {
+    pub const LEN: usize = 8 + 32 + 8 + 2 + 1;
+}
+
+/// Lamports an operator has put at stake; held on this account on top of
+/// its rent reserve.
+#[account]
+pub struct OperatorStake {
+    pub operator: Pubkey,
+    pub staked: u64,        // total ever posted
+    pub slashed_total: u64, // total moved to the treasury
+    pub unstake_requested_at: i64, // when request_unstake started the delay, 0 = not requested
+    pub bump: u8,
+}
+
+impl OperatorStake {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Fixed(32), Fixed(8), Fixed(8),
+        Fixed(8), // unstake_requested_at
+    ];
+
+    /// Stake still available to slash.
+    pub fn remaining(&self) -> u64 {
+        self.staked - self.slashed_total
+    }
+}
+
+#[account]
+pub struct UserProfile {
+    pub owner: Pubkey,
+    pub username: String,
+    pub predictions_made: u32,
+    pub correct_predictions: u32,
+    pub reputation: u16,
+    pub created_at: i64,
+    pub last_active: i64,
+    pub open_predictions: u32, // votes cast but not yet resolved
+    pub decayed_through: i64,  // idle time up to here already charged by decay_reputation
+    pub follower_count: u32,
+    pub following_count: u32,
+    pub bump: u8,
+}
+
+impl UserProfile {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_USERNAME_LEN + 4 + 4 + 2 + 8 + 8 + 4 + 8 + 4 + 4 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Fixed(32), Bytes(MAX_USERNAME_LEN), Fixed(4), Fixed(4), Fixed(2), Fixed(8), Fixed(8),
+        Fixed(4), // open_predictions
+        Fixed(8), // decayed_through
+        Fixed(4), Fixed(4), // follower_count, following_count
+    ];
+
+    /// Percent of predictions made that were resolved correct, capped at 100.
+    pub fn accuracy(&self) -> u8 {
+        if self.predictions_made == 0 {
+            return 0;
+        }
+        (self.correct_predictions as u64 * 100 / self.predictions_made as u64).min(100) as u8
+    }
+
+    /// Tier from reputation, with Expert and above also needing
+    /// `min_predictions` predictions made and Oracle a high accuracy.
+    pub fn tier(&self, min_predictions: u32) -> ReputationTier {
+        let proven = self.predictions_made >= min_predictions;
+        if proven && self.reputation >= ORACLE_REPUTATION && self.accuracy() >= ORACLE_MIN_ACCURACY {
+            ReputationTier::Oracle
+        } else if proven && self.reputation >= EXPERT_REPUTATION {
+            ReputationTier::Expert
+        } else if self.reputation >= SKILLED_REPUTATION {
+            ReputationTier::Skilled
+        } else {
+            ReputationTier::Novice
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReputationTier {
+    Novice,
+    Skilled,
+    Expert,
+    Oracle,
+}
+
+/// Left behind when a profile closes (if enabled) so a recreated profile
+/// cannot start above the reputation it had.
+#[account]
+pub struct ReputationTombstone {
+    pub user: Pubkey,
+    pub reputation: u16,
+    pub bump: u8,
+}
+
+impl ReputationTombstone {
+    pub const LEN: usize = 8 + 32 + 2 + 1;
+}
+
+/// Reserves a username, keyed by its lowercase form. Closed with the profile
+/// that holds it.
+#[account]
+pub struct UsernameClaim {
+    pub owner: Pubkey,
+    pub username: String, // as the owner spelled it
+    pub bump: u8,
+}
+
+impl UsernameClaim {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_USERNAME_LEN + 1;
+}
+
+/// One profile following another.
+#[account]
+pub struct Follow {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+    pub followed_at: i64,
+    pub bump: u8,
+}
+
+impl Follow {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Optional per-symbol settings; a missing account means defaults.
+#[account]
+pub struct SymbolConfig {
+    pub symbol: String,
+    pub active_hours: u32,     // bit h set = updates allowed during UTC hour h
+    pub decimals: Option<u8>,  // score_display precision, None = Sentinel default
+    pub scale_factor: i16,     // raw score multiplier for unscaled operators
+    pub metadata: String,      // free-form, printable ASCII
+    pub reader_allowlist: Vec<Pubkey>, // programs allowed to CPI-read, empty = public
+    pub write_fee: u64,        // lamports per write paid to the treasury, 0 = free
+    pub bump: u8,
+}
+
+impl SymbolConfig {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 4 + 2 + 2 + 4 + MAX_METADATA_LEN
+        + 4 + 32 * MAX_READERS // reader_allowlist
+        + 8                    // write_fee
+        + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Bytes(MAX_SYMBOL_LEN), Fixed(4),
+        Optional(1), Fixed(2), Bytes(MAX_METADATA_LEN), // decimals, scale_factor, metadata
+        List(32, MAX_READERS),                          // reader_allowlist
+        Fixed(8),                                       // write_fee
+    ];
+
+    pub fn set_defaults(&mut self, symbol: String, bump: u8) {
+        self.symbol = symbol;
+        self.active_hours = ALL_HOURS;
+        self.decimals = None;
+        self.scale_factor = SCALE_FACTOR_ONE;
+        self.metadata = String::new();
+        self.reader_allowlist = Vec::new();
+        self.write_fee = 0;
+        self.bump = bump;
+    }
+}
+
+/// Binds a canonical symbol to its SPL mint.
+#[account]
+pub struct SymbolRegistry {
+    pub symbol: String,
+    pub mint: Pubkey,
+    pub registered_by: Pubkey,
+    pub registered_at: i64,
+    pub price_feed: Pubkey, // Pyth price account, default = none
+    pub bump: u8,
+}
+
+impl SymbolRegistry {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 32 + 32 + 8 + 32 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Bytes(MAX_SYMBOL_LEN), Fixed(32), Fixed(32), Fixed(8),
+        Fixed(32), // price_feed
+    ];
+}
+
+/// Maps a renamed symbol onto the registered symbol whose records it reads.
+#[account]
+pub struct SymbolAlias {
+    pub alias: String,  // new spelling
+    pub target: String, // canonical symbol the data lives under
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl SymbolAlias {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 4 + MAX_SYMBOL_LEN + 8 + 1;
+}
+
+#[account]
+pub struct Subscription {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub direction: i8,
+    pub alert_threshold: u8,
+    pub subscribed_at: i64,
+    pub last_alert: i64,
+    pub volume_threshold: u32, // alert when volume reaches this, 0 = off
+    pub cooldown: i64,         // minimum seconds between alerts
+    pub alerted_reading: i64,  // timestamp of the reading last alerted on
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 8 + 8 + 4 + 8 + 8 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Fixed(32), Bytes(MAX_SYMBOL_LEN), Fixed(1), Fixed(1), Fixed(8), Fixed(8),
+        Fixed(4), Fixed(8), // volume_threshold, cooldown
+        Fixed(8),           // alerted_reading
+    ];
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SymbolSub {
+    pub symbol: String,
+    pub direction: i8,
+    pub threshold: u8,
+}
+
+impl SymbolSub {
+    pub const LEN: usize = 4 + MAX_SYMBOL_LEN + 1 + 1;
+}
+
+/// Every symbol a user watches, in one account sized to its entries.
+#[account]
+pub struct SubscriptionList {
+    pub user: Pubkey,
+    pub subs: Vec<SymbolSub>, // up to MAX_SUBSCRIPTIONS
+    pub bump: u8,
+}
+
+impl SubscriptionList {
+    /// Size of a list holding `count` entries.
+    pub const fn space(count: usize) -> usize {
+        8 + 32 + 4 + SymbolSub::LEN * count + 1
+    }
+}
+
+#[account]
+pub struct CommunityVote {
+    pub voter: Pubkey,
+    pub symbol: String,
+    pub score: i8,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub resolved: bool,
+    pub correct: bool,
+    pub penalized: bool, // expired unresolved and already penalized
+    pub weight: u16,     // voter reputation when cast, capped at max_vote_weight
+    pub bump: u8,
+}
+
+impl CommunityVote {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 8 + 1 + 1 + 1 + 2 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Fixed(32), Bytes(MAX_SYMBOL_LEN), Fixed(1), Fixed(1), Fixed(8),
+        Fixed(1), Fixed(1), // resolved, correct
+        Fixed(1),           // penalized
+        Fixed(2),           // weight
+    ];
+}
+
+/// A user's call that `symbol` will read `target_score` at `resolve_after`.
+#[account]
+pub struct Prediction {
+    pub owner: Pubkey,
+    pub symbol: String,
+    pub target_score: i8,
+    pub opened_at: i64,
+    pub resolve_after: i64,
+    pub resolved: bool,
+    pub correct: bool,
+    pub bump: u8,
+}
+
+impl Prediction {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_SYMBOL_LEN + 1 + 8 + 8 + 1 + 1 + 1;
+}
+
+/// A reading of a consensus-gated symbol awaiting operator approvals.
+#[account]
+pub struct PendingUpdate {
+    pub symbol: String,
+    pub proposer: Pubkey,
+    pub score: i8,
+    pub confidence: u8,
+    pub volume: u32,
+    pub timestamp: i64,
+    pub deadline: i64,            // approvals after this are rejected
+    pub approvals: Vec<Pubkey>,   // distinct approvers, proposer first
+    pub bump: u8,
+}
+
+impl PendingUpdate {
+    pub const LEN: usize = 8
+        + 4 + MAX_SYMBOL_LEN      // symbol
+        + 32                      // proposer
+        + 1 + 1 + 4               // score, confidence, volume
+        + 8                       // timestamp
+        + 8                       // deadline
+        + 4 + (32 * MAX_APPROVERS) // approvals vec
+        + 1;                      // bump
+}
+
+/// Running totals of community votes for one symbol.
+#[account]
+pub struct VoteAggregate {
+    pub symbol: String,
+    pub sum_score: i64,
+    pub sum_confidence: u64,
+    pub vote_count: u32,
+    pub sum_weighted_score: i64, // score * confidence
+    pub sum_rep_score: i64,      // score * voter weight
+    pub sum_rep_weight: u64,     // voter weights (reputation, capped at max_vote_weight)
+    pub bump: u8,
+}
+
+impl VoteAggregate {
+    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 8 + 8 + 4 + 8 + 8 + 8 + 1;
+
+    pub const FIELDS: &'static [FieldShape] = &[
+        Bytes(MAX_SYMBOL_LEN), Fixed(8), Fixed(8), Fixed(4),
+        Fixed(8),           // sum_weighted_score
+        Fixed(8), Fixed(8), // sum_rep_score, sum_rep_weight
+    ];
+
+    pub fn add_vote(&mut self, score: i8, confidence: u8, weight: u16) {
+        self.sum_score = self.sum_score.saturating_add(score as i64);
+        self.sum_confidence = self.sum_confidence.saturating_add(confidence as u64);
+        self.sum_weighted_score = self.sum_weighted_score.saturating_add(score as i64 * confidence as i64);
+        self.sum_rep_score = self.sum_rep_score.saturating_add(score as i64 * weight as i64);
+        self.sum_rep_weight = self.sum_rep_weight.saturating_add(weight as u64);
+        self.vote_count = self.vote_count.saturating_add(1);
+    }
+
+    /// Take back a vote previously passed to `add_vote`.
+    pub fn remove_vote(&mut self, score: i8, confidence: u8, weight: u16) {
+        self.sum_score = self.sum_score.saturating_sub(score as i64);
+        self.sum_confidence = self.sum_confidence.saturating_sub(confidence as u64);
+        self.sum_weighted_score = self.sum_weighted_score.saturating_sub(score as i64 * confidence as i64);
+        self.sum_rep_score = self.sum_rep_score.saturating_sub(score as i64 * weight as i64);
+        self.sum_rep_weight = self.sum_rep_weight.saturating_sub(weight as u64);
+        self.vote_count = self.vote_count.saturating_sub(1);
+    }
+
+    /// Reputation-weighted mean score in hundredths of a point. Zero while no
+    /// vote carries any weight.
+    pub fn weighted_consensus(&self) -> i64 {
+        if self.sum_rep_weight == 0 {
+            return 0;
+        }
+        self.sum_rep_score * 100 / self.sum_rep_weight as i64
+    }
+
+    /// Confidence-weighted mean score in hundredths of a point, falling back
+    /// to the plain mean if every vote has zero confidence. Zero with no votes.
+    pub fn consensus_score(&self) -> i64 {
+        if self.sum_confidence > 0 {
+            self.sum_weighted_score * 100 / self.sum_confidence as i64
+        } else if self.vote_count > 0 {
+            self.sum_score * 100 / self.vote_count as i64
+        } else {
+            0
+        }
+    }
+}
+
+// ============================================================================
+// Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Sentinel::LEN,
+        seeds = [SENTINEL_SEED],
+        bump
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateSentinel<'info> {
+    /// CHECK: slim v2 layout, which `Account<Sentinel>` can't decode; owner,
+    /// size and discriminator are checked in `migrate_sentinel`
+    #[account(mut, seeds = [SENTINEL_SEED], bump)]
+    pub sentinel: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Admin instructions open to the root or any scoped authority; each handler
+/// checks the scope it needs.
+#[derive(Accounts)]
+pub struct AdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuditEnabled<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SetSymbolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolConfig::LEN,
+        seeds = [SYMBOL_CONFIG_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_config: Account<'info, SymbolConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RegisterSymbolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolConfig::LEN,
+        seeds = [SYMBOL_CONFIG_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_config: Account<'info, SymbolConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RegisterSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SymbolRegistry::LEN,
+        seeds = [SYMBOL_REGISTRY_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(old_symbol: String, new_symbol: String)]
+pub struct AliasSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SymbolAlias::LEN,
+        seeds = [SYMBOL_ALIAS_SEED, new_symbol.as_bytes()],
+        bump
+    )]
+    pub alias: Account<'info, SymbolAlias>,
+
+    // Only registered symbols can be aliased to
+    #[account(
+        seeds = [SYMBOL_REGISTRY_SEED, old_symbol.as_bytes()],
+        bump = target_registry.bump,
+    )]
+    pub target_registry: Account<'info, SymbolRegistry>,
+
+    /// CHECK: PDA verified by seeds; must be empty, so aliases never chain
+    #[account(seeds = [SYMBOL_ALIAS_SEED, old_symbol.as_bytes()], bump)]
+    pub target_alias: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; must be empty, so a live symbol isn't shadowed
+    #[account(seeds = [SYMBOL_REGISTRY_SEED, new_symbol.as_bytes()], bump)]
+    pub new_registry: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; must be empty, so a live symbol isn't shadowed
+    #[account(seeds = [SENTIMENT_SEED, new_symbol.as_bytes()], bump)]
+    pub new_sentiment: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SYMBOL_REGISTRY_SEED, symbol.as_bytes()],
+        bump = symbol_registry.bump,
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RootAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckWatchdog<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.pending_authority == Some(candidate.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    pub candidate: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct StoreSentiment<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.operators_enabled || sentinel.authority == authority.key() @ SentinelError::Unauthorized,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_STORE)
+            || holds_operator_scope(&operator_scope) @ SentinelError::OperatorNotScoped,
+        constraint = meets_min_stake(&sentinel, &authority.key(), &operator_stake) @ SentinelError::OperatorStakeTooLow
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SentimentRecord::LEN,
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the symbol was pre-registered.
+    #[account(seeds = [SYMBOL_CONFIG_SEED, symbol.as_bytes()], bump)]
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only read if the signer holds a grant for this symbol.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, authority.key().as_ref(), symbol.as_bytes()], bump)]
+    pub operator_scope: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only checked for existence when symbols
+    /// must be registered.
+    #[account(seeds = [SYMBOL_REGISTRY_SEED, symbol.as_bytes()], bump)]
+    pub symbol_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth price account, validated against the symbol's registered
+    /// feed by `read_pyth_price`. Omit to store without a price, clearing any
+    /// earlier one.
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: only receives lamports; pinned to the configured treasury.
+    /// Required when the symbol charges a write fee.
+    #[account(mut, address = sentinel.treasury @ SentinelError::InvalidAccount)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorStats::LEN,
+        seeds = [OPERATOR_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub operator_stats: Account<'info, OperatorStats>,
+
+    /// CHECK: PDA verified by seeds; only read while a minimum operator stake is set.
+    #[account(seeds = [OPERATOR_STAKE_SEED, authority.key().as_ref()], bump)]
+    pub operator_stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSentiment<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.operators_enabled || sentinel.authority == authority.key() @ SentinelError::Unauthorized,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_UPDATE)
+            || holds_operator_scope(&operator_scope) @ SentinelError::OperatorNotScoped,
+        constraint = meets_min_stake(&sentinel, &authority.key(), &operator_stake) @ SentinelError::OperatorStakeTooLow
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, sentiment.symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the symbol has a config.
+    #[account(seeds = [SYMBOL_CONFIG_SEED, sentiment.symbol.as_bytes()], bump)]
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only read if the signer holds a grant for this symbol.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, authority.key().as_ref(), sentiment.symbol.as_bytes()], bump)]
+    pub operator_scope: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorStats::LEN,
+        seeds = [OPERATOR_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub operator_stats: Account<'info, OperatorStats>,
+
+    /// CHECK: PDA verified by seeds; only read while a minimum operator stake is set.
+    #[account(seeds = [OPERATOR_STAKE_SEED, authority.key().as_ref()], bump)]
+    pub operator_stake: UncheckedAccount<'info>,
+
+    /// Only written when the record has `auto_history` on. Matched by symbol
+    /// rather than seeds so clients that never pass it aren't made to.
+    #[account(
+        mut,
+        constraint = history.symbol == sentiment.symbol @ SentinelError::InvalidAccount
+    )]
+    pub history: Option<Account<'info, SentimentHistory>>,
+
+    /// CHECK: only receives lamports; pinned to the configured treasury.
+    /// Required when the symbol charges a write fee.
+    #[account(mut, address = sentinel.treasury @ SentinelError::InvalidAccount)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct SetConsensusThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct SetSymbolCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the signer holds a grant for this symbol.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, authority.key().as_ref(), _symbol.as_bytes()], bump)]
+    pub operator_scope: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct SetExpectedInterval<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct SetAutoHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_HISTORY) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct CheckLiveness<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ProposeUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &proposer.key(), CAN_UPDATE) @ SentinelError::Unauthorized,
+        constraint = meets_min_stake(&sentinel, &proposer.key(), &operator_stake) @ SentinelError::OperatorStakeTooLow
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the symbol has a config.
+    #[account(seeds = [SYMBOL_CONFIG_SEED, symbol.as_bytes()], bump)]
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// Snapshotted on commit when the record has `auto_history` on.
+    #[account(
+        mut,
+        constraint = history.symbol == sentiment.symbol @ SentinelError::InvalidAccount
+    )]
+    pub history: Option<Account<'info, SentimentHistory>>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = PendingUpdate::LEN,
+        seeds = [PENDING_UPDATE_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub pending: Account<'info, PendingUpdate>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = OperatorStats::LEN,
+        seeds = [OPERATOR_STATS_SEED, proposer.key().as_ref()],
+        bump
+    )]
+    pub operator_stats: Account<'info, OperatorStats>,
+
+    /// CHECK: PDA verified by seeds; only read while a minimum operator stake is set.
+    #[account(seeds = [OPERATOR_STAKE_SEED, proposer.key().as_ref()], bump)]
+    pub operator_stake: UncheckedAccount<'info>,
+
+    /// CHECK: only receives lamports; pinned to the configured treasury.
+    /// Required when the symbol charges a write fee.
+    #[account(mut, address = sentinel.treasury @ SentinelError::InvalidAccount)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ApproveUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &approver.key(), CAN_UPDATE) @ SentinelError::Unauthorized,
+        constraint = meets_min_stake(&sentinel, &approver.key(), &operator_stake) @ SentinelError::OperatorStakeTooLow
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the symbol has a config.
+    #[account(seeds = [SYMBOL_CONFIG_SEED, symbol.as_bytes()], bump)]
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// Snapshotted on commit when the record has `auto_history` on.
+    #[account(
+        mut,
+        constraint = history.symbol == sentiment.symbol @ SentinelError::InvalidAccount
+    )]
+    pub history: Option<Account<'info, SentimentHistory>>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_UPDATE_SEED, symbol.as_bytes()],
+        bump = pending.bump,
+    )]
+    pub pending: Account<'info, PendingUpdate>,
+
+    /// CHECK: receives the proposal's rent on commit; pinned to its proposer.
+    #[account(mut, address = pending.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only read while a minimum operator stake is set.
+    #[account(seeds = [OPERATOR_STAKE_SEED, approver.key().as_ref()], bump)]
+    pub operator_stake: UncheckedAccount<'info>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct RefreshSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; a live account is `sentiment.updater`'s
+    /// grant for this symbol. Pinned so callers can't hide a grant.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, sentiment.updater.as_ref(), _symbol.as_bytes()], bump)]
+    pub updater_scope: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; as `updater_scope`, for `provisional_updater`.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, sentiment.provisional_updater.as_ref(), _symbol.as_bytes()], bump)]
+    pub provisional_scope: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; as `updater_scope`, for `preferred_updater`.
+    #[account(seeds = [OPERATOR_SCOPE_SEED, sentiment.preferred_updater.as_ref(), _symbol.as_bytes()], bump)]
+    pub preferred_scope: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct PublishBlended<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; may not exist before the first vote.
+    #[account(seeds = [VOTE_AGG_SEED, _symbol.as_bytes()], bump)]
+    pub aggregate: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchUpdateSentiments<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_UPDATE | CAN_BATCH) @ SentinelError::Unauthorized,
+        constraint = meets_min_stake(&sentinel, &authority.key(), &operator_stake) @ SentinelError::OperatorStakeTooLow
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorStats::LEN,
+        seeds = [OPERATOR_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub operator_stats: Account<'info, OperatorStats>,
+
+    /// CHECK: PDA verified by seeds; only read while a minimum operator stake is set.
+    #[account(seeds = [OPERATOR_STAKE_SEED, authority.key().as_ref()], bump)]
+    pub operator_stake: UncheckedAccount<'info>,
+
+    /// CHECK: only receives lamports; pinned to the configured treasury.
+    /// Required when any entry's symbol charges a write fee.
+    #[account(mut, address = sentinel.treasury @ SentinelError::InvalidAccount)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Sentiment records and their symbol configs are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String, capacity: u16)]
+pub struct RecordHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_HISTORY) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SentimentHistory::space(capacity as usize),
+        seeds = [HISTORY_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub history: Account<'info, SentimentHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BatchRecordHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_HISTORY | CAN_BATCH) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // (sentiment, history) pairs are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RepairHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, symbol.as_bytes()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, SentimentHistory>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct MigrateHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    /// CHECK: pre-capacity layout, which `Account<SentimentHistory>` can't
+    /// decode; owner, size and discriminator are checked in `migrate_history`
+    #[account(mut, seeds = [HISTORY_SEED, _symbol.as_bytes()], bump)]
+    pub history: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    /// CHECK: an older layout its type can't decode; owner, discriminator and
+    /// size are checked in `migrate_account`
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// History reads accept a symbol or its alias; `history` must be the
+/// resolved symbol's history PDA.
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct ReadHistory<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    /// CHECK: PDA verified by seeds; only read if it holds a SymbolAlias.
+    #[account(seeds = [SYMBOL_ALIAS_SEED, _symbol.as_bytes()], bump)]
+    pub alias: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [HISTORY_SEED, resolve_symbol(&alias, &_symbol).as_bytes()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, SentimentHistory>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct ReadDeltaHistory<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [DELTA_HISTORY_SEED, _symbol.as_bytes()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, DeltaHistory>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RecordDeltaHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_HISTORY) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DeltaHistory::LEN,
+        seeds = [DELTA_HISTORY_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub history: Account<'info, DeltaHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct ReadCompactHistory<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [COMPACT_HISTORY_SEED, _symbol.as_bytes()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, CompactHistory>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RecordCompactHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), CAN_HISTORY) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CompactHistory::LEN,
+        seeds = [COMPACT_HISTORY_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub history: Account<'info, CompactHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PauseAndSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // (sentiment, history) pairs are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct OpenValueDispute<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), 0) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorDispute::LEN,
+        seeds = [OPERATOR_DISPUTE_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub dispute: Account<'info, OperatorDispute>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OperatorDisputeVote<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [OPERATOR_DISPUTE_SEED, dispute.symbol.as_bytes()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, OperatorDispute>,
+
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ResolveValueDispute<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_authority_or_operator(&sentinel, &authority.key(), 0) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        mut,
+        seeds = [OPERATOR_DISPUTE_SEED, symbol.as_bytes()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, OperatorDispute>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct OpenDispute<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Dispute::LEN,
+        seeds = [DISPUTE_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SENTIMENT_SEED, dispute.symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, dispute.symbol.as_bytes()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: the disputer's profile PDA, verified by seeds; may have been
+    /// closed since, in which case no reputation changes
+    #[account(mut, seeds = [USER_PROFILE_SEED, dispute.disputer.as_ref()], bump)]
+    pub profile: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(candidate: Pubkey)]
+pub struct ProposeOperator<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OperatorProposal::LEN,
+        seeds = [OPERATOR_PROPOSAL_SEED, candidate.as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, OperatorProposal>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct NominateOperatorFromProfile<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [USER_PROFILE_SEED, profile.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewOperatorProposal<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [OPERATOR_PROPOSAL_SEED, proposal.candidate.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, OperatorProposal>,
+
+    #[account(
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+/// Anyone may finalize once the window has closed; rent returns to the proposer.
+#[derive(Accounts)]
+pub struct FinalizeOperator<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [OPERATOR_PROPOSAL_SEED, proposal.candidate.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, OperatorProposal>,
+
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: SystemAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey, symbol: String)]
+pub struct GrantSymbolOperator<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OperatorScope::LEN,
+        seeds = [OPERATOR_SCOPE_SEED, operator.as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub operator_scope: Account<'info, OperatorScope>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey, symbol: String)]
+pub struct RevokeSymbolOperator<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [OPERATOR_SCOPE_SEED, operator.as_ref(), symbol.as_bytes()],
+        bump = operator_scope.bump,
+    )]
+    pub operator_scope: Account<'info, OperatorScope>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct CreateProfile<'info> {
+    // Checked first: an over-long name would overflow the claim's seed
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = username.len() <= MAX_USERNAME_LEN @ SentinelError::UsernameTooLong
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = user,
+        space = UsernameClaim::LEN,
+        seeds = [USERNAME_SEED, username.to_lowercase().as_bytes()],
+        bump
+    )]
+    pub username_claim: Account<'info, UsernameClaim>,
+
+    /// CHECK: PDA verified by seeds; only read if a closed profile left a tombstone.
+    #[account(seeds = [TOMBSTONE_SEED, user.key().as_ref()], bump)]
+    pub tombstone: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DecayReputation<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, profile.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// CHECK: PDA verified by seeds; closed only if it exists and is held by this profile.
+    #[account(
+        mut,
+        seeds = [USERNAME_SEED, profile.username.to_lowercase().as_bytes()],
+        bump,
+    )]
+    pub username_claim: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ReputationTombstone::LEN,
+        seeds = [TOMBSTONE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Option<Account<'info, ReputationTombstone>>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferProfile<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_profile: Account<'info, UserProfile>,
+
+    /// CHECK: PDA verified by seeds; moved only if it exists and is held by this profile.
+    #[account(
+        mut,
+        seeds = [USERNAME_SEED, profile.username.to_lowercase().as_bytes()],
+        bump,
+    )]
+    pub username_claim: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(followee: Pubkey)]
+pub struct FollowUser<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Follow::LEN,
+        seeds = [FOLLOW_SEED, user.key().as_ref(), followee.as_ref()],
+        bump
+    )]
+    pub follow: Account<'info, Follow>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, followee.as_ref()],
+        bump = followee_profile.bump,
+    )]
+    pub followee_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(followee: Pubkey)]
+pub struct UnfollowUser<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [FOLLOW_SEED, user.key().as_ref(), followee.as_ref()],
+        bump = follow.bump,
+    )]
+    pub follow: Account<'info, Follow>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Option<Account<'info, UserProfile>>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, followee.as_ref()],
+        bump = followee_profile.bump,
+    )]
+    pub followee_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SubscribeToken<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct StoreAndSubscribe<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = CommunityVote::LEN,
+        seeds = [VOTE_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// CHECK: PDA verified by seeds; only checked for existence when votes
+    /// require a tracked symbol.
+    #[account(seeds = [SENTIMENT_SEED, symbol.as_bytes()], bump)]
+    pub sentiment: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = VoteAggregate::LEN,
+        seeds = [VOTE_AGG_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// CHECK: the voter's SPL token account for the vote gate, parsed by
+    /// `require_gate_balance`. Only needed while a gate mint is set.
+    pub gate_token_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unsubscribe<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = user,
+        constraint = subscription.user == user.key() @ SentinelError::Unauthorized
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        constraint = subscription.user == user.key() @ SentinelError::Unauthorized
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddWatch<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SubscriptionList::space(0),
+        seeds = [SUB_LIST_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub sub_list: Account<'info, SubscriptionList>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyWatch<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SUB_LIST_SEED, user.key().as_ref()],
+        bump = sub_list.bump,
+    )]
+    pub sub_list: Account<'info, SubscriptionList>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckAlert<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.user.as_ref(), subscription.symbol.as_bytes()],
+        bump = subscription.bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, subscription.symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct VoteSentiment<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = CommunityVote::LEN,
+        seeds = [VOTE_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    /// CHECK: PDA verified by seeds; only checked for existence when votes
+    /// require a tracked symbol.
+    #[account(seeds = [SENTIMENT_SEED, symbol.as_bytes()], bump)]
+    pub sentiment: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = VoteAggregate::LEN,
+        seeds = [VOTE_AGG_SEED, symbol.as_bytes()],
+        bump
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    /// CHECK: the voter's SPL token account for the vote gate, parsed by
+    /// `require_gate_balance`. Only needed while a gate mint is set.
+    pub gate_token_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JudgePrediction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(mut)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = vote.voter == profile.owner @ SentinelError::Unauthorized
+    )]
+    pub vote: Option<Account<'info, CommunityVote>>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ResolvePredictionAgainst<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, profile.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, profile.owner.as_ref(), symbol.as_bytes()],
+        bump = vote.bump,
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct OpenPrediction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Prediction::LEN,
+        seeds = [PREDICTION_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub prediction: Account<'info, Prediction>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolvePrediction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PREDICTION_SEED, prediction.owner.as_ref(), prediction.symbol.as_bytes()],
+        bump = prediction.bump,
+    )]
+    pub prediction: Account<'info, Prediction>,
+
+    #[account(mut, address = prediction.owner @ SentinelError::Unauthorized)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, prediction.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, prediction.symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only updated once `init_leaderboard` has created it.
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    /// CHECK: the SlotHashes sysvar, read for tiebreak entropy. Required
+    /// while `random_tiebreak` is on.
+    #[account(address = SLOT_HASHES_ID @ SentinelError::InvalidAccount)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = is_admin(&sentinel, &authority.key()) @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Leaderboard::LEN,
+        seeds = [LEADERBOARD_SEED],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey, symbol: String)]
+pub struct ExpirePrediction<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = voter_wallet,
+        seeds = [VOTE_SEED, voter.as_ref(), symbol.as_bytes()],
+        bump = vote.bump,
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_AGG_SEED, symbol.as_bytes()],
+        bump = aggregate.bump,
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, voter.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, address = voter @ SentinelError::Unauthorized)]
+    pub voter_wallet: SystemAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String, source_voter: Pubkey)]
+pub struct MergeVotes<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, user.key().as_ref(), symbol.as_bytes()],
+        bump = vote.bump,
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    #[account(
+        mut,
+        close = source_wallet,
+        seeds = [VOTE_SEED, source_voter.as_ref(), symbol.as_bytes()],
+        bump = source_vote.bump,
+    )]
+    pub source_vote: Account<'info, CommunityVote>,
+
+    #[account(
+        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, source_voter.as_ref()],
+        bump = source_profile.bump,
+    )]
+    pub source_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_AGG_SEED, symbol.as_bytes()],
+        bump = aggregate.bump,
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    /// CHECK: the voter's SPL token account for the vote gate, parsed by
+    /// `require_gate_balance`. Only needed while a gate mint is set.
+    pub gate_token_account: Option<UncheckedAccount<'info>>,
+
+    pub user: Signer<'info>,
+
+    #[account(mut, address = source_voter @ SentinelError::Unauthorized)]
+    pub source_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVote<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, user.key().as_ref(), vote.symbol.as_bytes()],
+        bump = vote.bump,
+        constraint = vote.voter == user.key() @ SentinelError::Unauthorized
+    )]
+    pub vote: Account<'info, CommunityVote>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_AGG_SEED, vote.symbol.as_bytes()],
+        bump = aggregate.bump,
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    /// CHECK: the voter's SPL token account for the vote gate, parsed by
+    /// `require_gate_balance`. Only needed while a gate mint is set.
+    pub gate_token_account: Option<UncheckedAccount<'info>>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostStake<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.operators.iter().any(|o| o.key == operator.key()) @ SentinelError::OperatorNotFound
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = OperatorStake::LEN,
+        seeds = [OPERATOR_STAKE_SEED, operator.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, OperatorStake>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [OPERATOR_STAKE_SEED, operator.key().as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Account<'info, OperatorStake>,
+
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = operator,
+        seeds = [OPERATOR_STAKE_SEED, operator.key().as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Account<'info, OperatorStake>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOperator<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        seeds = [OPERATOR_STAKE_SEED, stake.operator.as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Account<'info, OperatorStake>,
+
+    /// CHECK: only receives lamports; pinned to the configured treasury
+    #[account(mut, address = sentinel.treasury @ SentinelError::InvalidAccount)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReadConfig<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+}
+
+#[derive(Accounts)]
+pub struct ReadSymbolAccuracy<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+    // The user's CommunityVote accounts are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct ReadAggregates<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+    // VoteAggregate accounts are passed as remaining_accounts
+}
+
+/// Record reads accept a symbol or its alias; `sentiment` and
+/// `symbol_config` must be the resolved symbol's PDAs.
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReadSentiment<'info> {
+    #[account(
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        seeds = [SENTIMENT_SEED, resolve_symbol(&alias, &symbol).as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    /// CHECK: PDA verified by seeds; only read if the symbol has a config.
+    #[account(seeds = [SYMBOL_CONFIG_SEED, resolve_symbol(&alias, &symbol).as_bytes()], bump)]
+    pub symbol_config: UncheckedAccount<'info>,
+
+    /// CHECK: the instructions sysvar, used to identify CPI callers.
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified by seeds; only read if it holds a SymbolAlias.
+    #[account(seeds = [SYMBOL_ALIAS_SEED, symbol.as_bytes()], bump)]
+    pub alias: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AssertFresh<'info> {
+    #[account(
+        seeds = [SENTIMENT_SEED, sentiment.symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+}
+
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct CloseSentiment<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
+        bump = sentiment.bump,
+    )]
+    pub sentiment: Account<'info, SentimentRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-// ============================================================================
-// Helpers
-// ============================================================================
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct CloseHistory<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
 
-fn validate_sentiment_input(symbol: &str, score: i8, confidence: u8, timestamp: i64) -> Result<()> {
-    require!(!symbol.is_empty(), SentinelError::EmptySymbol);
-    require!(symbol.len() <= MAX_SYMBOL_LEN, SentinelError::SymbolTooLong);
-    require!(symbol.chars().all(|c| c.is_ascii_alphanumeric()), SentinelError::InvalidSymbol);
-    require!(score >= -100 && score <= 100, SentinelError::InvalidScore);
-    require!(confidence <= 100, SentinelError::InvalidConfidence);
-    require!(timestamp > 0, SentinelError::InvalidTimestamp);
-    Ok(())
+    /// CHECK: the history PDA for `symbol`, closed by hand so any layout works
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, symbol.as_bytes()],
+        bump,
+    )]
+    pub history: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-fn is_authority_or_operator(sentinel: &Sentinel, signer: &Pubkey) -> bool {
-    sentinel.authority == *signer || sentinel.operators.contains(signer)
+#[derive(Accounts)]
+#[instruction(_symbol: String)]
+pub struct CloseVoteAggregate<'info> {
+    #[account(
+        mut,
+        seeds = [SENTINEL_SEED],
+        bump = sentinel.bump,
+        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
+    )]
+    pub sentinel: Account<'info, Sentinel>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [VOTE_AGG_SEED, _symbol.as_bytes()],
+        bump = aggregate.bump,
+    )]
+    pub aggregate: Account<'info, VoteAggregate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
 // ============================================================================
-// Data types
+// Events
 // ============================================================================
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct SentimentInput {
-    pub score: i8,
-    pub confidence: u8,
+/// Follows every event emitted by a state-changing instruction. `seq` rises
+/// by one per event, so a gap means a log was missed.
+#[event]
+pub struct EventSequenced {
+    pub seq: u64,
+}
+
+#[event]
+pub struct SentimentUpdated {
+    pub symbol: String,
+    pub score: i8,                // normalized onto -100..100
+    pub raw_score: i16,           // as submitted, before the operator's offset and scale factor
+    pub confidence: u8,           // stored, after any operator cap
+    pub submitted_confidence: u8,
     pub volume: u32,
     pub timestamp: i64,
+    pub updater: Pubkey,
+    pub note: String,
+    pub out_of_turn: bool, // updater was not the record's preferred_updater
+    pub adjusted_score: i16,
+    pub hash: [u8; 32],    // record's prev_hash after this reading
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
-pub struct HistoryEntry {
+#[event]
+pub struct UpdateProposed {
+    pub symbol: String,
+    pub proposer: Pubkey,
     pub score: i8,
     pub confidence: u8,
-    pub volume: u32,
-    pub timestamp: i64,
-    pub recorded_at: i64,
+    pub deadline: i64,
 }
 
-impl HistoryEntry {
-    pub const LEN: usize = 1 + 1 + 4 + 8 + 8; // 22
+#[event]
+pub struct UpdateApproved {
+    pub symbol: String,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
 }
 
-// ============================================================================
-// Accounts
-// ============================================================================
+#[event]
+pub struct UpdateCommitted {
+    pub symbol: String,
+    pub score: i8,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub approvals: u8,
+    pub hash: [u8; 32],
+}
 
-#[account]
-pub struct Sentinel {
-    pub authority: Pubkey,
-    pub total_updates: u64,
-    pub paused: bool,
-    pub operators: Vec<Pubkey>,  // up to MAX_OPERATORS
-    pub bump: u8,
+#[event]
+pub struct ConfidenceUpdated {
+    pub symbol: String,
+    pub old_confidence: u8,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub updater: Pubkey,
+    pub hash: [u8; 32],
 }
 
-impl Sentinel {
-    pub const LEN: usize = 8  // discriminator
-        + 32                   // authority
-        + 8                    // total_updates
-        + 1                    // paused
-        + 4 + (32 * MAX_OPERATORS)  // operators vec
-        + 1;                   // bump
+#[event]
+pub struct SentimentContributed {
+    pub symbol: String,
+    pub contributor: Pubkey,
+    pub running_score: i8,
+    pub source_count: u8,
+    pub hash: [u8; 32],
 }
 
-#[account]
-pub struct SentimentRecord {
+#[event]
+pub struct CanonicalRefreshed {
+    pub symbol: String,
+    pub score: i8,
+    pub confidence: u8,
+    pub dropped_canonical: bool,   // canonical reading came from a removed operator
+    pub dropped_provisional: bool, // provisional reading came from a removed operator
+    pub hash: [u8; 32],
+}
+
+#[event]
+pub struct ProvisionalSentimentRecorded {
     pub symbol: String,
     pub score: i8,
     pub confidence: u8,
     pub volume: u32,
     pub timestamp: i64,
     pub updater: Pubkey,
-    pub update_count: u32,
-    pub bump: u8,
 }
 
-impl SentimentRecord {
-    pub const LEN: usize = 8 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 4 + 8 + 32 + 4 + 1;
+#[event]
+pub struct CommunityVoteEvent {
+    pub voter: Pubkey,
+    pub symbol: String,
+    pub score: i8,
+    pub confidence: u8,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct SentimentHistory {
-    pub symbol: String,
-    pub head: u16,
-    pub count: u16,
-    pub snapshots: Vec<HistoryEntry>,
-    pub bump: u8,
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
 }
 
-impl SentimentHistory {
-    pub const LEN: usize = 8
-        + 4 + MAX_SYMBOL_LEN     // symbol
-        + 2                       // head
-        + 2                       // count
-        + 4 + (HistoryEntry::LEN * MAX_HISTORY) // snapshots vec
-        + 1;                      // bump
+#[event]
+pub struct AuthorityNominated {
+    pub authority: Pubkey,
+    pub candidate: Pubkey,
 }
 
-#[account]
-pub struct UserProfile {
-    pub owner: Pubkey,
-    pub username: String,
-    pub predictions_made: u32,
-    pub correct_predictions: u32,
+#[event]
+pub struct AuthorityNominationCancelled {
+    pub candidate: Pubkey,
+}
+
+#[event]
+pub struct AuthorityScopeSet {
+    pub key: Pubkey,
+    pub scope_flags: u16,
+}
+
+#[event]
+pub struct AuthorityScopeRemoved {
+    pub key: Pubkey,
+}
+
+#[event]
+pub struct AuditEntryRecorded {
+    pub action_code: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseToggled {
+    pub paused: bool, // any pause bit set
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct PausedWithSnapshot {
+    pub count: u8,
+}
+
+#[event]
+pub struct BatchHistoryRecorded {
+    pub count: u8,
+}
+
+#[event]
+pub struct OperatorAdded {
+    pub operator: Pubkey,
+    pub perms: u8,
+}
+
+#[event]
+pub struct OperatorPromoted {
+    pub profile_owner: Pubkey,
     pub reputation: u16,
-    pub created_at: i64,
-    pub last_active: i64,
-    pub bump: u8,
 }
 
-impl UserProfile {
-    pub const LEN: usize = 8 + 32 + 4 + MAX_USERNAME_LEN + 4 + 4 + 2 + 8 + 8 + 1;
+#[event]
+pub struct OperatorPermsSet {
+    pub operator: Pubkey,
+    pub perms: u8,
+}
+
+#[event]
+pub struct StakePosted {
+    pub operator: Pubkey,
+    pub amount: u64,
+    pub remaining: u64, // unslashed stake after this deposit
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub operator: Pubkey,
+    pub available_at: i64, // earliest withdraw_stake
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub operator: Pubkey,
+    pub amount: u64, // unslashed stake returned, rent not included
+}
+
+#[event]
+pub struct OperatorSlashed {
+    pub operator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OperatorProposed {
+    pub candidate: Pubkey,
+    pub proposer: Pubkey,
+    pub closes_at: i64,
+}
+
+#[event]
+pub struct OperatorProposalReviewed {
+    pub candidate: Pubkey,
+    pub voter: Pubkey,
+    pub endorse: bool,
+    pub reputation: u16,
+}
+
+#[event]
+pub struct OperatorProposalFinalized {
+    pub candidate: Pubkey,
+    pub endorsements: u16,
+    pub objections: u16,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct OperatorRemoved {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct SymbolOperatorGranted {
+    pub operator: Pubkey,
+    pub symbol: String,
+}
+
+#[event]
+pub struct SymbolOperatorRevoked {
+    pub operator: Pubkey,
+    pub symbol: String,
+}
+
+/// The operator set is now empty while the subsystem is still enabled.
+#[event]
+pub struct LastOperatorRemoved {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct OperatorProbationEnded {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct WatchdogTripped {
+    pub last_update_ts: i64,
+    pub timeout: i64,
+    pub tripped_at: i64,
+}
+
+#[event]
+pub struct OperatorsToggled {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct MilestoneReached {
+    pub total: u64,
+}
+
+#[event]
+pub struct BatchUpdateCompleted {
+    pub count: u8,
+}
+
+#[event]
+pub struct HistoryRecorded {
+    pub symbol: String,
+    pub entries: u16,
+}
+
+#[event]
+pub struct HistoryCorrected {
+    pub symbol: String,
+    pub index: u16, // chronological, 0 = oldest
 }
 
-#[account]
-pub struct Subscription {
-    pub user: Pubkey,
+#[event]
+pub struct HistoryStats {
     pub symbol: String,
-    pub direction: i8,
-    pub alert_threshold: u8,
-    pub subscribed_at: i64,
-    pub last_alert: i64,
-    pub bump: u8,
+    pub min_score: i8,
+    pub max_score: i8,
+    pub avg_score: i8, // truncated toward zero
+    pub min_vol: u32,
+    pub max_vol: u32,
 }
 
-impl Subscription {
-    pub const LEN: usize = 8 + 32 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 8 + 8 + 1;
+#[event]
+pub struct TwapComputed {
+    pub symbol: String,
+    pub twap_score: i64, // hundredths of a score point
+    pub sample_count: u16,
+    pub window_seconds: i64,
+    pub confidence_weighted: bool, // mode applied; false after a zero-confidence fallback
 }
 
-#[account]
-pub struct CommunityVote {
-    pub voter: Pubkey,
+#[event]
+pub struct HistorySnapshotEvent {
     pub symbol: String,
+    pub position: u16, // snapshots back from the newest, 0 = newest
     pub score: i8,
     pub confidence: u8,
+    pub volume: u32,
     pub timestamp: i64,
-    pub bump: u8,
+    pub recorded_at: i64,
 }
 
-impl CommunityVote {
-    pub const LEN: usize = 8 + 32 + 4 + MAX_SYMBOL_LEN + 1 + 1 + 8 + 1;
+#[event]
+pub struct HistoryRepaired {
+    pub symbol: String,
+    pub recovered_entries: u16,
 }
 
-// ============================================================================
-// Contexts
-// ============================================================================
+#[event]
+pub struct DeltaHistoryRecorded {
+    pub symbol: String,
+    pub entries: u16,
+    pub keyframe: bool,
+}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = Sentinel::LEN,
-        seeds = [SENTINEL_SEED],
-        bump
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct CompactHistoryRecorded {
+    pub symbol: String,
+    pub entries: u16,
+    pub bytes: u16,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct ValueDisputeOpened {
+    pub symbol: String,
+    pub opener: Pubkey,
+    pub disputed_score: i8,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct DisputeOpened {
+    pub symbol: String,
+    pub disputer: Pubkey,
+    pub reason_code: u8,
+    pub disputed_score: i8,
 }
 
-#[derive(Accounts)]
-pub struct AdminAction<'info> {
-    #[account(
-        mut,
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct DisputeResolved {
+    pub symbol: String,
+    pub disputer: Pubkey,
+    pub upheld: bool,
+    pub disputer_reputation: Option<u16>, // None if the profile was closed
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct OperatorDisputeVoted {
+    pub symbol: String,
+    pub operator: Pubkey,
+    pub value: i8,
 }
 
-#[derive(Accounts)]
-#[instruction(symbol: String)]
-pub struct StoreSentiment<'info> {
-    #[account(
-        mut,
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = is_authority_or_operator(&sentinel, &authority.key()) @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct VolumeAnomaly {
+    pub symbol: String,
+    pub volume: u32,
+    pub volume_ema: u64,    // before this reading
+    pub volume_stddev: u64, // before this reading
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = SentimentRecord::LEN,
-        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
-        bump
-    )]
-    pub sentiment: Account<'info, SentimentRecord>,
+#[event]
+pub struct CircuitBreakerTripped {
+    pub symbol: String,
+    pub old_score: i8,
+    pub attempted_score: i8,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct OperatorRateLimited {
+    pub operator: Pubkey,
+    pub window_start: i64,
+    pub count_in_window: u16,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VolatilityCircuitTripped {
+    pub symbol: String,
+    pub volatility: u64, // volume stddev as % of EMA
+    pub threshold: u16,
+    pub enforced: bool,
 }
 
-#[derive(Accounts)]
-pub struct UpdateSentiment<'info> {
-    #[account(
-        mut,
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = is_authority_or_operator(&sentinel, &authority.key()) @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct ValueDisputeResolved {
+    pub symbol: String,
+    pub old_score: i8,
+    pub new_score: i8,
+    pub votes: u8,
+    pub confidence_before: u8,
+    pub confidence_after: u8, // after the operator-disagreement penalty
+    pub hash: [u8; 32],
+}
 
-    #[account(
-        mut,
-        seeds = [SENTIMENT_SEED, sentiment.symbol.as_bytes()],
-        bump = sentiment.bump,
-    )]
-    pub sentiment: Account<'info, SentimentRecord>,
+#[event]
+pub struct Subscribed {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub direction: i8,
+    pub alert_threshold: u8,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct Unsubscribed {
+    pub user: Pubkey,
+    pub symbol: String,
 }
 
-#[derive(Accounts)]
-pub struct BatchUpdateSentiments<'info> {
-    #[account(
-        mut,
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = is_authority_or_operator(&sentinel, &authority.key()) @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+/// A symbol was added to, or changed in, a user's `SubscriptionList`.
+#[event]
+pub struct WatchAdded {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub direction: i8,
+    pub threshold: u8,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    // Sentiment accounts are passed as remaining_accounts
+#[event]
+pub struct WatchUpdated {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub direction: i8,
+    pub threshold: u8,
 }
 
-#[derive(Accounts)]
-#[instruction(symbol: String)]
-pub struct RecordHistory<'info> {
-    #[account(
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = is_authority_or_operator(&sentinel, &authority.key()) @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct WatchRemoved {
+    pub user: Pubkey,
+    pub symbol: String,
+}
 
-    #[account(
-        seeds = [SENTIMENT_SEED, symbol.as_bytes()],
-        bump = sentiment.bump,
-    )]
-    pub sentiment: Account<'info, SentimentRecord>,
+#[event]
+pub struct AlertTriggered {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub reason: u8, // ALERT_REASON_* bits
+}
 
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = SentimentHistory::LEN,
-        seeds = [HISTORY_SEED, symbol.as_bytes()],
-        bump
-    )]
-    pub history: Account<'info, SentimentHistory>,
+/// `check_alert` found a trigger window still cooling down.
+#[event]
+pub struct AlertSuppressed {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub seconds_remaining: i64,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct AlertCooldownSet {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub cooldown: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PredictionResolved {
+    pub user: Pubkey,
+    pub correct: bool,
+    pub new_reputation: u16,
+    pub tier: ReputationTier,
 }
 
-#[derive(Accounts)]
-pub struct CreateProfile<'info> {
-    #[account(
-        init,
-        payer = user,
-        space = UserProfile::LEN,
-        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
-        bump
-    )]
-    pub profile: Account<'info, UserProfile>,
+#[event]
+pub struct LeaderboardChanged {
+    pub user: Pubkey,
+    pub reputation: u16,
+    pub rank: u8, // 0 = top
+}
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+#[event]
+pub struct LeaderboardEvicted {
+    pub user: Pubkey,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct LeaderboardInitialized {
+    pub authority: Pubkey,
 }
 
-#[derive(Accounts)]
-#[instruction(symbol: String)]
-pub struct SubscribeToken<'info> {
-    #[account(
-        init,
-        payer = user,
-        space = Subscription::LEN,
-        seeds = [SUBSCRIPTION_SEED, user.key().as_ref(), symbol.as_bytes()],
-        bump
-    )]
-    pub subscription: Account<'info, Subscription>,
+#[event]
+pub struct VoteChanged {
+    pub voter: Pubkey,
+    pub symbol: String,
+    pub old_score: i8,
+    pub new_score: i8,
+}
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+#[event]
+pub struct VotesMerged {
+    pub symbol: String,
+    pub voter: Pubkey,        // surviving vote's owner
+    pub source_voter: Pubkey, // closed vote's owner
+    pub score: i8,
+    pub confidence: u8,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PredictionOpened {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub target_score: i8,
+    pub resolve_after: i64,
+}
+
+#[event]
+pub struct PredictionScored {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub predicted: i8,
+    pub actual: i8,
+    pub error: u8, // |predicted - actual|
+    pub tolerance: u8,
+    pub expired: bool, // settled after its window, a miss regardless of error
+}
+
+#[event]
+pub struct PredictionExpired {
+    pub voter: Pubkey,
+    pub symbol: String,
+    pub penalty: u16,
+    pub new_reputation: u16,
 }
 
-#[derive(Accounts)]
-pub struct Unsubscribe<'info> {
-    #[account(
-        mut,
-        close = user,
-        constraint = subscription.user == user.key() @ SentinelError::Unauthorized
-    )]
-    pub subscription: Account<'info, Subscription>,
-
-    #[account(mut)]
-    pub user: Signer<'info>,
+#[event]
+pub struct ProfileTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
 }
 
-#[derive(Accounts)]
-#[instruction(symbol: String)]
-pub struct VoteSentiment<'info> {
-    #[account(
-        init,
-        payer = user,
-        space = CommunityVote::LEN,
-        seeds = [VOTE_SEED, user.key().as_ref(), symbol.as_bytes()],
-        bump
-    )]
-    pub vote: Account<'info, CommunityVote>,
+#[event]
+pub struct UserFollowed {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+}
 
-    #[account(
-        mut,
-        seeds = [USER_PROFILE_SEED, user.key().as_ref()],
-        bump = profile.bump,
-        constraint = profile.owner == user.key() @ SentinelError::Unauthorized
-    )]
-    pub profile: Account<'info, UserProfile>,
+#[event]
+pub struct UserUnfollowed {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+}
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+#[event]
+pub struct UsernameClaimed {
+    pub user: Pubkey,
+    pub username: String,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ProfileClosed {
+    pub user: Pubkey,
+    pub username: String,
 }
 
-#[derive(Accounts)]
-pub struct ResolvePrediction<'info> {
-    #[account(
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct ReputationDecayed {
+    pub user: Pubkey,
+    pub old_reputation: u16,
+    pub new_reputation: u16,
+}
 
-    #[account(mut)]
-    pub profile: Account<'info, UserProfile>,
+#[event]
+pub struct SymbolAccuracy {
+    pub user: Pubkey,
+    pub symbol: String,
+    pub correct: u32,
+    pub total: u32,
+}
 
-    pub authority: Signer<'info>,
+#[event]
+pub struct ConsensusUpdated {
+    pub symbol: String,
+    pub consensus_score: i64,    // hundredths of a score point
+    pub weighted_consensus: i64, // reputation-weighted, hundredths of a score point
+    pub vote_count: u32,
 }
 
-#[derive(Accounts)]
-#[instruction(_symbol: String)]
-pub struct CloseSentiment<'info> {
-    #[account(
-        seeds = [SENTINEL_SEED],
-        bump = sentinel.bump,
-        constraint = sentinel.authority == authority.key() @ SentinelError::Unauthorized
-    )]
-    pub sentinel: Account<'info, Sentinel>,
+#[event]
+pub struct AggregatesRead {
+    pub entries: Vec<AggregateSummary>,
+}
 
-    #[account(
-        mut,
-        close = authority,
-        seeds = [SENTIMENT_SEED, _symbol.as_bytes()],
-        bump = sentiment.bump,
-    )]
-    pub sentiment: Account<'info, SentimentRecord>,
+#[event]
+pub struct NextUpdateAllowed {
+    pub symbol: String,
+    pub ts: i64,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct SymbolRegistered {
+    pub symbol: String,
+    pub mint: Pubkey,
 }
 
-// ============================================================================
-// Events
-// ============================================================================
+#[event]
+pub struct BlendedPublished {
+    pub symbol: String,
+    pub oracle_score: i8,
+    pub community_score: i64, // hundredths of a point; the oracle score when there are no votes
+    pub vote_count: u32,
+    pub community_weight: u8,
+    pub blended_score: i8,
+}
 
 #[event]
-pub struct SentimentUpdated {
+pub struct SymbolCooldownSet {
     pub symbol: String,
-    pub score: i8,
-    pub confidence: u8,
-    pub volume: u32,
-    pub timestamp: i64,
-    pub updater: Pubkey,
+    pub cooldown: i64,
+    pub set_by: Pubkey,
 }
 
 #[event]
-pub struct CommunityVoteEvent {
-    pub voter: Pubkey,
+pub struct AutoHistorySet {
     pub symbol: String,
-    pub score: i8,
-    pub confidence: u8,
-    pub timestamp: i64,
+    pub enabled: bool,
+    pub set_by: Pubkey,
 }
 
 #[event]
-pub struct AuthorityTransferred {
-    pub old_authority: Pubkey,
-    pub new_authority: Pubkey,
+pub struct ExpectedIntervalSet {
+    pub symbol: String,
+    pub expected_interval: i64,
+    pub set_by: Pubkey,
 }
 
 #[event]
-pub struct PauseToggled {
-    pub paused: bool,
+pub struct SentimentStalled {
+    pub symbol: String,
+    pub seconds_overdue: i64, // past the time the next reading was due
 }
 
 #[event]
-pub struct OperatorAdded {
-    pub operator: Pubkey,
+pub struct SymbolAliased {
+    pub old_symbol: String,
+    pub new_symbol: String,
 }
 
 #[event]
-pub struct OperatorRemoved {
-    pub operator: Pubkey,
+pub struct PriceFeedSet {
+    pub symbol: String,
+    pub feed: Pubkey,
 }
 
 #[event]
-pub struct BatchUpdateCompleted {
-    pub count: u8,
+pub struct SymbolConfigured {
+    pub symbol: String,
+    pub active_hours: u32,
+    pub decimals: Option<u8>,
+    pub scale_factor: i16,
+    pub write_fee: u64,
 }
 
 #[event]
-pub struct HistoryRecorded {
+pub struct DecayedReading {
     pub symbol: String,
-    pub entries: u16,
+    pub score: i8,
+    pub decayed_confidence: u8,
 }
 
 #[event]
-pub struct Unsubscribed {
-    pub user: Pubkey,
+pub struct HistoryMigrated {
     pub symbol: String,
+    pub old_len: u32,
+    pub new_len: u32,
 }
 
 #[event]
-pub struct PredictionResolved {
-    pub user: Pubkey,
-    pub correct: bool,
-    pub new_reputation: u16,
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+}
+
+#[event]
+pub struct SentinelMigrated {
+    pub authority: Pubkey,
+    pub total_updates: u64,
+    pub old_len: u32,
+    pub new_len: u32,
 }
 
 #[event]
@@ -835,6 +9927,16 @@ pub struct SentimentClosed {
     pub symbol: String,
 }
 
+#[event]
+pub struct HistoryClosed {
+    pub symbol: String,
+}
+
+#[event]
+pub struct VoteAggregateClosed {
+    pub symbol: String,
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -906,4 +10008,340 @@ pub enum SentinelError {
 
     #[msg("Invalid timestamp")]
     InvalidTimestamp,
+
+    #[msg("History ring buffer is corrupt")]
+    CorruptHistory,
+
+    #[msg("Probation period cannot be negative")]
+    InvalidProbation,
+
+    #[msg("Profile has open predictions")]
+    HasOpenStakes,
+
+    #[msg("Reputation tombstone account required")]
+    TombstoneRequired,
+
+    #[msg("Aggregation needs at least two readings; use update_sentiment instead")]
+    AggregateTooSmall,
+
+    #[msg("Quorum cannot exceed the maximum operator count")]
+    InvalidQuorum,
+
+    #[msg("A dispute is already open for this symbol")]
+    DisputeAlreadyOpen,
+
+    #[msg("No open dispute for this symbol")]
+    DisputeNotOpen,
+
+    #[msg("Already voted on this dispute")]
+    AlreadyVoted,
+
+    #[msg("Dispute quorum not reached")]
+    QuorumNotReached,
+
+    #[msg("Vote has already been resolved")]
+    VoteAlreadyResolved,
+
+    #[msg("Profile is too new to vote")]
+    ProfileTooNew,
+
+    #[msg("Too many scoped authorities (max 5)")]
+    TooManyAuthorities,
+
+    #[msg("Scoped authority not found")]
+    AuthorityNotFound,
+
+    #[msg("Authority lacks the scope for this action")]
+    InsufficientAuthorityScope,
+
+    #[msg("Note too long (max 64 chars)")]
+    NoteTooLong,
+
+    #[msg("Note must be printable ASCII")]
+    InvalidNote,
+
+    #[msg("Spread penalty factor must be 0-100")]
+    InvalidPenaltyFactor,
+
+    #[msg("Audit log account required while auditing is enabled")]
+    AuditLogRequired,
+
+    #[msg("Active hours must fit in 24 bits")]
+    InvalidActiveHours,
+
+    #[msg("Updates are closed for this symbol at this hour")]
+    MarketClosed,
+
+    #[msg("Invalid display configuration")]
+    InvalidDisplayConfig,
+
+    #[msg("Removing this operator would leave the dispute quorum unreachable")]
+    WouldBreakQuorum,
+
+    #[msg("Vote has already been penalized")]
+    AlreadyPenalized,
+
+    #[msg("Vote has not passed the prediction horizon")]
+    PredictionNotExpired,
+
+    #[msg("Scale factor must be positive")]
+    InvalidScaleFactor,
+
+    #[msg("Metadata too long (max 32 chars)")]
+    MetadataTooLong,
+
+    #[msg("Metadata must be printable ASCII")]
+    InvalidMetadata,
+
+    #[msg("Too many allowlisted readers (max 4)")]
+    TooManyReaders,
+
+    #[msg("Calling program is not on this feed's reader allowlist")]
+    ReaderNotAllowed,
+
+    #[msg("Proposal window must be positive")]
+    InvalidProposalWindow,
+
+    #[msg("Operator proposal review window is still open")]
+    ProposalStillOpen,
+
+    #[msg("Operator proposal review window has closed")]
+    ProposalClosed,
+
+    #[msg("Operator proposal has reached its reviewer limit")]
+    ProposalFull,
+
+    #[msg("Profile reputation is too low to review proposals")]
+    InsufficientReputation,
+
+    #[msg("Volatility circuit is tripped; only the authority may update this symbol")]
+    TooVolatile,
+
+    #[msg("Accuracy tolerance must be at most 200")]
+    InvalidAccuracyTolerance,
+
+    #[msg("Symbol has no sentiment record")]
+    SymbolNotTracked,
+
+    #[msg("TWAP window must be positive")]
+    InvalidTwapWindow,
+
+    #[msg("Fewer than two history snapshots in the window")]
+    InsufficientHistory,
+
+    #[msg("History index is out of range")]
+    InvalidHistoryIndex,
+
+    #[msg("Correction would put history out of chronological order")]
+    WouldBreakChronology,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Watchdog timeout cannot be negative")]
+    InvalidWatchdogTimeout,
+
+    #[msg("Update interval cannot be negative")]
+    InvalidUpdateInterval,
+
+    #[msg("Update is too soon after the previous reading")]
+    UpdateTooFrequent,
+
+    #[msg("Score moved further than the configured maximum delta")]
+    ScoreDeviationTooLarge,
+
+    #[msg("Score must be between -10000 and 10000 basis points")]
+    InvalidScoreBps,
+
+    #[msg("Signer is not an operator for this symbol")]
+    OperatorNotScoped,
+
+    #[msg("History capacity must be between 1 and MAX_HISTORY_CAP")]
+    InvalidHistoryCapacity,
+
+    #[msg("Prediction cannot be resolved before its resolve_after time")]
+    PredictionNotMature,
+
+    #[msg("Reputation decay period must be positive")]
+    InvalidDecayPeriod,
+
+    #[msg("Sentiment reading is older than the allowed age")]
+    SentimentStale,
+
+    #[msg("Consensus threshold exceeds the number of possible approvers")]
+    InvalidConsensusThreshold,
+
+    #[msg("This symbol only accepts updates through operator consensus")]
+    ConsensusRequired,
+
+    #[msg("This symbol does not require operator consensus")]
+    ConsensusNotRequired,
+
+    #[msg("An unexpired update is already pending for this symbol")]
+    PendingUpdateExists,
+
+    #[msg("Pending update has expired")]
+    PendingUpdateExpired,
+
+    #[msg("Operator already approved this update")]
+    AlreadyApproved,
+
+    #[msg("Symbol is not registered")]
+    SymbolNotRegistered,
+
+    #[msg("Maximum vote weight must be positive")]
+    InvalidVoteWeight,
+
+    #[msg("Cannot follow yourself")]
+    CannotFollowSelf,
+
+    #[msg("Rate limit needs a positive update count when enabled")]
+    InvalidRateLimit,
+
+    #[msg("Operator exceeded its update rate limit for this window")]
+    OperatorRateLimited,
+
+    #[msg("Alert cooldown cannot be negative")]
+    InvalidCooldown,
+
+    #[msg("Profile still follows other users")]
+    ProfileHasActivity,
+
+    #[msg("Requested range is outside the recorded history")]
+    InvalidRange,
+
+    #[msg("Unknown pause flag bits")]
+    InvalidPauseFlags,
+
+    #[msg("Price feed does not match the symbol's registered Pyth account")]
+    InvalidPriceFeed,
+
+    #[msg("Pyth price is older than the allowed max age")]
+    PriceFeedStale,
+
+    #[msg("Price max age must be positive")]
+    InvalidPriceMaxAge,
+
+    #[msg("Voter does not hold enough of the gating token")]
+    InsufficientGateBalance,
+
+    #[msg("Contribution window must be positive")]
+    InvalidContributionWindow,
+
+    #[msg("Timestamp is too far from the on-chain clock")]
+    TimestampOutOfRange,
+
+    #[msg("Sentinel already uses the full layout")]
+    AlreadyMigrated,
+
+    #[msg("Half-life must be positive")]
+    InvalidHalfLife,
+
+    #[msg("Subscription list is full")]
+    TooManySubscriptions,
+
+    #[msg("Symbol is already on the subscription list")]
+    WatchAlreadyExists,
+
+    #[msg("Symbol is not on the subscription list")]
+    WatchNotFound,
+
+    #[msg("Operator permissions contain unknown bits")]
+    InvalidOperatorPerms,
+
+    #[msg("Stake amount must be positive")]
+    InvalidStakeAmount,
+
+    #[msg("Slash exceeds the operator's remaining stake")]
+    InsufficientStake,
+
+    #[msg("Alias would point at itself or at another alias")]
+    AliasCycle,
+
+    #[msg("Community weight must be between 0 and 100")]
+    InvalidCommunityWeight,
+
+    #[msg("Profile lacks the reputation or prediction record for operator promotion")]
+    ProfileNotQualified,
+
+    #[msg("Expected update interval cannot be negative")]
+    InvalidExpectedInterval,
+
+    #[msg("Profile reputation is too low to open a dispute")]
+    DisputeReputationTooLow,
+
+    #[msg("A sentiment account appears more than once in the batch")]
+    DuplicateBatchAccount,
+
+    #[msg("Account is not the sentiment PDA for its symbol")]
+    WrongSentimentPda,
+
+    #[msg("Reputation cap must be positive")]
+    InvalidRepParams,
+
+    #[msg("Write fee exceeds the 1 SOL maximum")]
+    InvalidWriteFee,
+
+    #[msg("This symbol charges a write fee; pass the treasury account")]
+    TreasuryRequired,
+
+    #[msg("Random tiebreak is on; pass the SlotHashes sysvar")]
+    EntropyRequired,
+
+    #[msg("Allowlisted feeds only accept CPI reads made directly from a top-level program")]
+    NestedReaderCpi,
+
+    #[msg("History already has a capacity field")]
+    HistoryAlreadyMigrated,
+
+    #[msg("No reading has been stored since the prediction matured")]
+    NoMatureReading,
+
+    #[msg("Pyth price feed is not trading")]
+    PriceFeedNotTrading,
+
+    #[msg("Vote gate token account is frozen")]
+    GateAccountFrozen,
+
+    #[msg("This key already contributed to the current window")]
+    DuplicateContribution,
+
+    #[msg("Contribution window has no room for another source")]
+    ContributionWindowFull,
+
+    #[msg("Vote aggregate still counts open votes")]
+    VoteAggregateNotEmpty,
+
+    #[msg("Sentinel size matches no layout this program can migrate")]
+    UnsupportedSentinelLayout,
+
+    #[msg("Operator has less than the minimum stake posted")]
+    OperatorStakeTooLow,
+
+    #[msg("Unstaking is only open to operators removed from the set")]
+    OperatorStillActive,
+
+    #[msg("Call request_unstake and wait out the delay first")]
+    UnstakeNotRequested,
+
+    #[msg("Unstake delay has not elapsed")]
+    UnstakeDelayNotElapsed,
+
+    #[msg("Unstake delay cannot be negative")]
+    InvalidUnstakeDelay,
+
+    #[msg("Symbol is already registered or has its own record")]
+    SymbolInUse,
+
+    #[msg("Symbol cooldown must be between zero and one day")]
+    InvalidSymbolCooldown,
+
+    #[msg("Account size matches no older layout of its type")]
+    UnsupportedLayout,
+
+    #[msg("Account already uses its type's current layout")]
+    LayoutAlreadyCurrent,
+
+    #[msg("Operator is still on probation")]
+    OperatorOnProbation,
 }
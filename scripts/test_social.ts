@@ -21,11 +21,16 @@ async function main() {
       [Buffer.from("user_profile"), user.toBuffer()],
       program.programId
     );
+    const [claimPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("username"), Buffer.from("testuser")],
+      program.programId
+    );
 
     await program.methods
       .createProfile("testuser")
       .accounts({
         profile: profilePda,
+        usernameClaim: claimPda,
         user: user,
       })
       .rpc();
@@ -4,11 +4,17 @@
 import * as anchor from '@coral-xyz/anchor';
 import { Program, AnchorProvider, web3, BN } from '@coral-xyz/anchor';
 import { PublicKey, SystemProgram, Keypair, Connection } from '@solana/web3.js';
+import * as fs from 'fs';
+import * as path from 'path';
 import { SentimentScore } from './types';
 
 // Program ID - replace with deployed address
 const PROGRAM_ID = new PublicKey('SoLSentineLXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX');
 
+// IDL generated by `anchor build`; its instruction layouts and account
+// resolution track the program, so stores don't need hand-encoding
+const IDL_PATH = process.env.SOLSENTINEL_IDL || path.join(__dirname, '../target/idl/sol_sentinel.json');
+
 // Seeds
 const SENTINEL_SEED = Buffer.from('sentinel');
 const SENTIMENT_SEED = Buffer.from('sentiment');
@@ -22,6 +28,7 @@ export class SolSentinelClient {
   private connection: Connection;
   private wallet: Keypair;
   private provider: AnchorProvider;
+  private program: Program;
 
   constructor(config: OnChainConfig) {
     this.connection = new Connection(config.rpcUrl, 'confirmed');
@@ -45,6 +52,9 @@ export class SolSentinelClient {
     this.provider = new AnchorProvider(this.connection, walletAdapter as any, {
       commitment: 'confirmed'
     });
+
+    const idl = JSON.parse(fs.readFileSync(IDL_PATH, 'utf8'));
+    this.program = new Program({ ...idl, address: PROGRAM_ID.toBase58() }, this.provider);
   }
 
   /**
@@ -94,36 +104,23 @@ export class SolSentinelClient {
    * Store sentiment for a single token
    */
   async storeSentiment(sentiment: SentimentScore): Promise<string> {
-    const [sentinelPDA] = this.getSentinelPDA();
-    const [sentimentPDA] = this.getSentimentPDA(sentiment.token);
     const timestamp = Math.floor(sentiment.timestamp.getTime() / 1000);
 
-    // Encode instruction data
-    // Format: discriminator (8) + symbol (4 + len) + score (1) + confidence (1) + volume (4) + timestamp (8)
-    const symbolBytes = Buffer.from(sentiment.token.toUpperCase());
-    const data = Buffer.concat([
-      Buffer.from([79, 193, 205, 109, 72, 111, 47, 166]), // store_sentiment discriminator
-      Buffer.from(new Uint32Array([symbolBytes.length]).buffer), // symbol length
-      symbolBytes,
-      Buffer.from(new Int8Array([sentiment.score]).buffer),
-      Buffer.from(new Uint8Array([sentiment.confidence]).buffer),
-      Buffer.from(new Uint32Array([sentiment.volume]).buffer),
-      Buffer.from(new BigInt64Array([BigInt(timestamp)]).buffer)
-    ]);
-
-    const ix = new web3.TransactionInstruction({
-      keys: [
-        { pubkey: sentinelPDA, isSigner: false, isWritable: true },
-        { pubkey: sentimentPDA, isSigner: false, isWritable: true },
-        { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
-      programId: PROGRAM_ID,
-      data
-    });
-
-    const tx = new web3.Transaction().add(ix);
-    const sig = await this.provider.sendAndConfirm(tx);
+    // The client derives the PDAs (sentinel, record, symbol config, operator
+    // scope, registry, operator stats and stake) from the IDL. The optional
+    // price feed and treasury are left out, so symbols with a write fee or a
+    // bound Pyth feed can't be stored from here
+    const sig = await this.program.methods
+      .storeSentiment(
+        sentiment.token.toUpperCase(),
+        sentiment.score,
+        sentiment.confidence,
+        sentiment.volume,
+        new BN(timestamp),
+        '' // note
+      )
+      .accounts({ authority: this.wallet.publicKey } as any)
+      .rpc();
     
     console.log(`📊 Stored ${sentiment.token}: ${sentiment.score} (tx: ${sig.slice(0, 8)}...)`);
     return sig;
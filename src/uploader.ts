@@ -6,6 +6,8 @@ import path from "path";
 
 const PROGRAM_ID = new PublicKey("HFkhRjLJVwgm6UHfvSqkzJhaQE8GyzjNet8SUNAGjVgm");
 const DATA_DIR = path.join(__dirname, "..", "data");
+// IDL generated by `anchor build`, used to encode store_sentiment and resolve its accounts
+const IDL_PATH = process.env.SOLSENTINEL_IDL || path.join(__dirname, "..", "target", "idl", "sol_sentinel.json");
 
 // Initialize Anchor provider
 function getProvider() {
@@ -84,6 +86,9 @@ async function uploadSentiment() {
     return;
   }
 
+  const idl = JSON.parse(fs.readFileSync(IDL_PATH, "utf-8"));
+  const program = new anchor.Program({ ...idl, address: PROGRAM_ID.toBase58() }, provider);
+
   const sentimentData = await getSentimentData();
   console.log(`📊 Uploading ${sentimentData.length} tokens to devnet...`);
   console.log(`   Authority: ${provider.wallet.publicKey.toBase58()}`);
@@ -99,11 +104,6 @@ async function uploadSentiment() {
         [Buffer.from("sentiment"), Buffer.from(data.symbol)],
         PROGRAM_ID
       );
-      
-      const [sentinelPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("sentinel")],
-        PROGRAM_ID
-      );
 
       // Check if account already exists
       const existing = await provider.connection.getAccountInfo(sentimentPda);
@@ -116,35 +116,19 @@ async function uploadSentiment() {
       const emoji = data.score > 20 ? "🟢" : data.score < -20 ? "🔴" : "🟡";
       console.log(`  ${emoji} ${data.symbol}: score=${data.score}, confidence=${data.confidence}%, vol=${data.volume}`);
       
-      // Build store_sentiment instruction manually (Anchor discriminator + args)
-      const symbolBytes = Buffer.from(data.symbol);
-      const discriminator = Buffer.from([79, 193, 205, 109, 72, 111, 47, 166]); // store_sentiment
-      const symLen = Buffer.alloc(4);
-      symLen.writeUInt32LE(symbolBytes.length);
-      const scoreBuf = Buffer.alloc(1);
-      scoreBuf.writeInt8(data.score);
-      const confBuf = Buffer.alloc(1);
-      confBuf.writeUInt8(data.confidence);
-      const volBuf = Buffer.alloc(4);
-      volBuf.writeUInt32LE(data.volume);
-      const tsBuf = Buffer.alloc(8);
-      tsBuf.writeBigInt64LE(BigInt(Math.floor(data.timestamp)));
-
-      const ixData = Buffer.concat([discriminator, symLen, symbolBytes, scoreBuf, confBuf, volBuf, tsBuf]);
-
-      const ix = new anchor.web3.TransactionInstruction({
-        keys: [
-          { pubkey: sentinelPda, isSigner: false, isWritable: true },
-          { pubkey: sentimentPda, isSigner: false, isWritable: true },
-          { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: true },
-          { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
-        ],
-        programId: PROGRAM_ID,
-        data: ixData,
-      });
-
-      const tx = new anchor.web3.Transaction().add(ix);
-      const sig = await provider.sendAndConfirm(tx);
+      // The IDL client encodes the args (i16 score, trailing note) and derives
+      // the instruction's PDAs; the optional price feed and treasury are left out
+      const sig = await program.methods
+        .storeSentiment(
+          data.symbol,
+          data.score,
+          data.confidence,
+          data.volume,
+          new anchor.BN(Math.floor(data.timestamp)),
+          "" // note
+        )
+        .accounts({ authority: provider.wallet.publicKey } as any)
+        .rpc();
       console.log(`     ✅ Tx: ${sig.slice(0, 16)}...`);
       successCount++;
       
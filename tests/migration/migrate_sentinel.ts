@@ -0,0 +1,54 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolSentinel } from "../../target/types/sol_sentinel";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+
+// Runs on its own validator (tests/migration/Test.toml) so the main suite can
+// initialize the sentinel PDA this one starts out holding
+describe("sol_sentinel migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.solSentinel as Program<SolSentinel>;
+  const authority = provider.wallet;
+
+  const SENTINEL_SEED = Buffer.from("sentinel");
+
+  const findPDA = (seeds: (Buffer | Uint8Array)[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  it("migrates a slim v2 sentinel and hands it to the test wallet", async () => {
+    // This suite's validator starts with the 49-byte sentinel the slim v2
+    // program created (tests/fixtures/v2_sentinel.json); its admin keypair
+    // comes from a fixed seed
+    const legacyAdmin = Keypair.fromSeed(Buffer.from("slim-sentinel-admin-fixture-0001"));
+    const sig = await provider.connection.requestAirdrop(legacyAdmin.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const sentinelPDA = findPDA([SENTINEL_SEED]);
+    expect((await provider.connection.getAccountInfo(sentinelPDA))!.data.length).to.equal(49);
+
+    const migrate = program.methods
+      .migrateSentinel()
+      .accounts({ authority: legacyAdmin.publicKey } as any)
+      .signers([legacyAdmin]);
+    const event = (await migrate.simulate()).events.find((e: any) => e.name === "sentinelMigrated");
+    expect(event!.data.oldLen).to.equal(49);
+    await migrate.rpc();
+
+    let sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.authority.toBase58()).to.equal(legacyAdmin.publicKey.toBase58());
+    expect(sentinel.totalUpdates.toNumber()).to.equal(42);
+    expect(sentinel.pauseFlags).to.equal(0);
+    expect(sentinel.operators).to.have.length(0);
+    expect(sentinel.displayDecimals).to.equal(2);
+
+    await program.methods
+      .transferAuthority(authority.publicKey)
+      .accounts({ authority: legacyAdmin.publicKey } as any)
+      .signers([legacyAdmin])
+      .rpc();
+    sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+  });
+});
@@ -1,25 +1,78 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { SolSentinel } from "../target/types/sol_sentinel";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { SentinelReader } from "../target/types/sentinel_reader";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 describe("sol_sentinel", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.solSentinel as Program<SolSentinel>;
+  // Minimal consumer that reads feeds over CPI
+  const reader = anchor.workspace.sentinelReader as Program<SentinelReader>;
   const authority = provider.wallet;
 
   const SENTINEL_SEED = Buffer.from("sentinel");
   const SENTIMENT_SEED = Buffer.from("sentiment");
   const HISTORY_SEED = Buffer.from("history");
+  const DELTA_HISTORY_SEED = Buffer.from("delta_history");
+  const COMPACT_HISTORY_SEED = Buffer.from("compact_history");
   const USER_PROFILE_SEED = Buffer.from("user_profile");
   const SUBSCRIPTION_SEED = Buffer.from("subscription");
   const VOTE_SEED = Buffer.from("vote");
+  const OPERATOR_DISPUTE_SEED = Buffer.from("op_dispute");
+  const AUDIT_LOG_SEED = Buffer.from("audit_log");
+  const VOTE_AGG_SEED = Buffer.from("vote_agg");
+  const SYMBOL_CONFIG_SEED = Buffer.from("symbol_config");
+  const OPERATOR_PROPOSAL_SEED = Buffer.from("op_proposal");
+  const PREDICTION_SEED = Buffer.from("prediction");
+  const PENDING_UPDATE_SEED = Buffer.from("pending_update");
+  const SYMBOL_REGISTRY_SEED = Buffer.from("symbol_registry");
+  const FOLLOW_SEED = Buffer.from("follow");
+  const LEADERBOARD_SEED = Buffer.from("leaderboard");
+  const SUB_LIST_SEED = Buffer.from("sub_list");
+  const SYMBOL_ALIAS_SEED = Buffer.from("symbol_alias");
+  const OPERATOR_STAKE_SEED = Buffer.from("op_stake");
+  const DISPUTE_SEED = Buffer.from("dispute");
+  const USERNAME_SEED = Buffer.from("username");
+  const OPERATOR_SCOPE_SEED = Buffer.from("op_scope");
+
+  // OperatorEntry::perms bits
+  const CAN_STORE = 1 << 0;
+  const CAN_UPDATE = 1 << 1;
+  const CAN_ALL = 0b1111;
 
   const findPDA = (seeds: (Buffer | Uint8Array)[]) =>
     PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  // Usernames are claimed case-insensitively
+  const claimPDA = (username: string) => findPDA([USERNAME_SEED, Buffer.from(username.toLowerCase())]);
+  // refresh_symbol pins the OperatorScope PDA of each key recorded on the record
+  const refreshAccounts = async (symbol: string) => {
+    const record = await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+    const scope = (key: PublicKey) => findPDA([OPERATOR_SCOPE_SEED, key.toBuffer(), Buffer.from(symbol)]);
+    return {
+      updaterScope: scope(record.updater),
+      provisionalScope: scope(record.provisionalUpdater),
+      preferredScope: scope(record.preferredUpdater),
+    } as any;
+  };
+  // Record reads take the PDAs of the symbol an alias resolves to
+  const readAccounts = (symbol: string, target = symbol) =>
+    ({
+      sentiment: findPDA([SENTIMENT_SEED, Buffer.from(target)]),
+      symbolConfig: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(target)]),
+    }) as any;
 
   // ===== Initialization =====
 
@@ -30,36 +83,201 @@ describe("sol_sentinel", () => {
     const sentinel = await program.account.sentinel.fetch(sentinelPDA);
     expect(sentinel.authority.toBase58()).to.equal(authority.publicKey.toBase58());
     expect(sentinel.totalUpdates.toNumber()).to.equal(0);
-    expect(sentinel.paused).to.equal(false);
+    expect(sentinel.pauseFlags).to.equal(0);
     expect(sentinel.operators).to.have.length(0);
+    expect(sentinel.displayDecimals).to.equal(2);
+  });
+
+  it("returns the full config snapshot", async () => {
+    const config = await program.methods.getConfig().view();
+    expect(config.schemaVersion).to.equal(18);
+    expect(config.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+    expect(config.operatorCount).to.equal(0);
+    expect(config.displayDecimals).to.equal(2);
+    expect(config.accuracyTolerance).to.equal(10);
+    expect(config.reputationFloor).to.equal(50);
+    expect(config.permissionlessSymbols).to.equal(true);
+    expect(config.maxVoteWeight).to.equal(500);
+  });
+
+  it("refuses to migrate a sentinel that already has the full layout", async () => {
+    try {
+      await program.methods.migrateSentinel().rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AlreadyMigrated");
+    }
   });
 
   // ===== Admin Controls =====
 
   it("adds and removes an operator", async () => {
     const operator = Keypair.generate();
-    await program.methods.addOperator(operator.publicKey).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
 
     const sentinelPDA = findPDA([SENTINEL_SEED]);
     let sentinel = await program.account.sentinel.fetch(sentinelPDA);
     expect(sentinel.operators).to.have.length(1);
-    expect(sentinel.operators[0].toBase58()).to.equal(operator.publicKey.toBase58());
+    expect(sentinel.operators[0].key.toBase58()).to.equal(operator.publicKey.toBase58());
 
     await program.methods.removeOperator(operator.publicKey).rpc();
     sentinel = await program.account.sentinel.fetch(sentinelPDA);
     expect(sentinel.operators).to.have.length(0);
   });
 
+  it("transfers authority in two steps", async () => {
+    const candidate = Keypair.generate();
+    const stranger = Keypair.generate();
+    const sentinelPDA = findPDA([SENTINEL_SEED]);
+
+    await program.methods.nominateAuthority(candidate.publicKey).rpc();
+    let sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.pendingAuthority!.toBase58()).to.equal(candidate.publicKey.toBase58());
+    expect(sentinel.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({ candidate: stranger.publicKey } as any)
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Unauthorized");
+    }
+
+    await program.methods.cancelAuthorityTransfer().rpc();
+    sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.pendingAuthority).to.be.null;
+
+    await program.methods.nominateAuthority(candidate.publicKey).rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ candidate: candidate.publicKey } as any)
+      .signers([candidate])
+      .rpc();
+    sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.authority.toBase58()).to.equal(candidate.publicKey.toBase58());
+    expect(sentinel.pendingAuthority).to.be.null;
+
+    // Hand authority back to the provider wallet
+    await program.methods
+      .nominateAuthority(authority.publicKey)
+      .accounts({ authority: candidate.publicKey } as any)
+      .signers([candidate])
+      .rpc();
+    await program.methods.acceptAuthority().accounts({ candidate: authority.publicKey } as any).rpc();
+  });
+
+  it("refuses to remove an operator needed for the dispute quorum", async () => {
+    const operators = [Keypair.generate(), Keypair.generate()];
+    for (const op of operators) {
+      await program.methods.addOperator(op.publicKey, CAN_ALL).rpc();
+    }
+    await program.methods.setDisputeQuorum(2).rpc();
+
+    try {
+      await program.methods.removeOperator(operators[0].publicKey).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("WouldBreakQuorum");
+    }
+
+    await program.methods.setDisputeQuorum(0).rpc();
+    for (const op of operators) {
+      await program.methods.removeOperator(op.publicKey).rpc();
+    }
+  });
+
+  it("blocks listed operators when the operator subsystem is disabled", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    await program.methods.setOperatorsEnabled(false).rpc();
+
+    try {
+      await program.methods
+        .storeSentiment("OPOFF", 10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Unauthorized");
+    }
+
+    await program.methods.setOperatorsEnabled(true).rpc();
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
   it("pauses and unpauses the oracle", async () => {
     await program.methods.setPaused(true).rpc();
 
     const sentinelPDA = findPDA([SENTINEL_SEED]);
     let sentinel = await program.account.sentinel.fetch(sentinelPDA);
-    expect(sentinel.paused).to.equal(true);
+    expect(sentinel.pauseFlags).to.equal(0b1111);
+
+    await program.methods.setPaused(false).rpc();
+    sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.pauseFlags).to.equal(0);
+  });
+
+  it("auto-pauses a quiet feed via the watchdog", async () => {
+    const sentinelPDA = findPDA([SENTINEL_SEED]);
+    await program.methods.checkWatchdog().rpc();
+    let sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.pauseFlags).to.equal(0);
+
+    await program.methods.setWatchdogTimeout(new anchor.BN(1)).rpc();
+    await new Promise((resolve) => setTimeout(resolve, 2500));
+    const sim = await program.methods.checkWatchdog().simulate();
+    expect(sim.events.find((e: any) => e.name === "watchdogTripped")).to.exist;
+    await program.methods.checkWatchdog().rpc();
+    sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    // Only oracle writes stop
+    expect(sentinel.pauseFlags).to.equal(0b0011);
 
+    // Unpausing re-arms the watchdog rather than leaving it primed to trip
+    await program.methods.setWatchdogTimeout(new anchor.BN(3)).rpc();
+    await new Promise((resolve) => setTimeout(resolve, 3500));
     await program.methods.setPaused(false).rpc();
+    await program.methods.checkWatchdog().rpc();
     sentinel = await program.account.sentinel.fetch(sentinelPDA);
-    expect(sentinel.paused).to.equal(false);
+    expect(sentinel.pauseFlags).to.equal(0);
+
+    await program.methods.setWatchdogTimeout(new anchor.BN(0)).rpc();
+  });
+
+  it("records admin actions in the audit log while enabled", async () => {
+    const auditLogPDA = findPDA([AUDIT_LOG_SEED]);
+    const auditAccount = [{ pubkey: auditLogPDA, isSigner: false, isWritable: true }];
+    await program.methods
+      .storeSentiment("AUDITED", 10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+    await program.methods.setAuditEnabled(true).rpc();
+
+    await program.methods.setPaused(true).remainingAccounts(auditAccount).rpc();
+    await program.methods.setPaused(false).remainingAccounts(auditAccount).rpc();
+    await program.methods.setConsensusThreshold("AUDITED", 0).remainingAccounts(auditAccount).rpc();
+
+    try {
+      await program.methods.setPaused(false).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AuditLogRequired");
+    }
+
+    await program.methods.setAuditEnabled(false).rpc();
+
+    const log = await program.account.auditLog.fetch(auditLogPDA);
+    expect(log.count).to.equal(5);
+    const AUDIT_SET_PAUSED = 4;
+    const AUDIT_SET_CONFIG = 9;
+    expect(log.entries[1].actionCode).to.equal(AUDIT_SET_PAUSED);
+    expect(log.entries[1].actor.toBase58()).to.equal(authority.publicKey.toBase58());
+    expect(log.entries[3].actionCode).to.equal(AUDIT_SET_CONFIG);
   });
 
   it("rejects admin actions from non-authority", async () => {
@@ -79,15 +297,51 @@ describe("sol_sentinel", () => {
     }
   });
 
+  it("limits a scoped authority to its granted scopes", async () => {
+    const pauser = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(pauser.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    const SCOPE_PAUSE = 1;
+    await program.methods.setScopedAuthority(pauser.publicKey, SCOPE_PAUSE).rpc();
+
+    await program.methods
+      .setPaused(true)
+      .accounts({ authority: pauser.publicKey } as any)
+      .signers([pauser])
+      .rpc();
+    await program.methods
+      .setPaused(false)
+      .accounts({ authority: pauser.publicKey } as any)
+      .signers([pauser])
+      .rpc();
+
+    try {
+      await program.methods
+        .addOperator(Keypair.generate().publicKey, CAN_ALL)
+        .accounts({ authority: pauser.publicKey } as any)
+        .signers([pauser])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientAuthorityScope");
+    }
+
+    await program.methods.removeScopedAuthority(pauser.publicKey).rpc();
+    const sentinel = await program.account.sentinel.fetch(findPDA([SENTINEL_SEED]));
+    expect(sentinel.authorities).to.have.length(0);
+  });
+
   // ===== Store Sentiment =====
 
   it("stores sentiment for SOL", async () => {
     const symbol = "SOL";
     const ts = Math.floor(Date.now() / 1000);
 
-    await program.methods
-      .storeSentiment(symbol, 75, 85, 1500, new anchor.BN(ts))
-      .rpc();
+    const store = program.methods.storeSentiment(symbol, 75, 85, 1500, new anchor.BN(ts), "");
+    const sim = await store.simulate();
+    expect(sim.events.find((e: any) => e.name === "sentimentUpdated")).to.exist;
+    await store.rpc();
 
     const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
     const record = await program.account.sentimentRecord.fetch(sentimentPDA);
@@ -98,10 +352,48 @@ describe("sol_sentinel", () => {
     expect(record.updateCount).to.equal(0);
   });
 
+  it("keeps counting when SOL is stored again", async () => {
+    const symbol = "SOL";
+    const ts = Math.floor(Date.now() / 1000) + 1;
+
+    const store = program.methods.storeSentiment(symbol, 70, 85, 1500, new anchor.BN(ts), "");
+    const sim = await store.simulate();
+    expect(sim.events.find((e: any) => e.name === "sentimentUpdated")).to.exist;
+    await store.rpc();
+
+    const record = await program.account.sentimentRecord.fetch(
+      findPDA([SENTIMENT_SEED, Buffer.from(symbol)])
+    );
+    expect(record.score).to.equal(70);
+    expect(record.updateCount).to.equal(1);
+  });
+
+  it("stores a basis-point score alongside the legacy score", async () => {
+    const symbol = "BPS";
+    await program.methods
+      .storeSentimentPrecise(symbol, 5050, 80, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+
+    const record = await program.account.sentimentRecord.fetch(
+      findPDA([SENTIMENT_SEED, Buffer.from(symbol)])
+    );
+    expect(record.scoreBps).to.equal(5050);
+    expect(record.score).to.equal(50);
+
+    try {
+      await program.methods
+        .storeSentimentPrecise("BPS2", 10001, 80, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidScoreBps");
+    }
+  });
+
   it("rejects invalid sentiment score", async () => {
     try {
       await program.methods
-        .storeSentiment("BAD", 127 as any, 85, 100, new anchor.BN(1000))
+        .storeSentiment("BAD", 127 as any, 85, 100, new anchor.BN(1000), "")
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
@@ -110,12 +402,29 @@ describe("sol_sentinel", () => {
     }
   });
 
+  it("rejects timestamps too far from the on-chain clock", async () => {
+    await program.methods.setTimestampSkew(new anchor.BN(300), new anchor.BN(3600)).rpc();
+    const now = Math.floor(Date.now() / 1000);
+
+    for (const ts of [now + 86_400, now - 7 * 86_400]) {
+      try {
+        await program.methods.storeSentiment("SKEW", 10, 50, 100, new anchor.BN(ts), "").rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("TimestampOutOfRange");
+      }
+    }
+    await program.methods.storeSentiment("SKEW", 10, 50, 100, new anchor.BN(now), "").rpc();
+
+    await program.methods.setTimestampSkew(new anchor.BN(0), new anchor.BN(0)).rpc();
+  });
+
   it("rejects storing when paused", async () => {
     await program.methods.setPaused(true).rpc();
 
     try {
       await program.methods
-        .storeSentiment("PAUSE", 50, 50, 100, new anchor.BN(1000))
+        .storeSentiment("PAUSE", 50, 50, 100, new anchor.BN(1000), "")
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
@@ -125,163 +434,3821 @@ describe("sol_sentinel", () => {
     await program.methods.setPaused(false).rpc();
   });
 
-  // ===== Update Sentiment =====
-
-  it("updates an existing sentiment record", async () => {
-    const symbol = "SOL";
-    const ts = Math.floor(Date.now() / 1000) + 1000;
-    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
-
+  it("keeps voting open while only stores are paused", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
     await program.methods
-      .updateSentiment(-20, 60, 2000, new anchor.BN(ts))
-      .accounts({ sentiment: sentimentPDA } as any)
+      .createProfile("halted")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("halted") } as any)
+      .signers([user])
       .rpc();
 
-    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
-    expect(record.score).to.equal(-20);
-    expect(record.confidence).to.equal(60);
-    expect(record.volume).to.equal(2000);
-    expect(record.updateCount).to.equal(1);
-  });
-
-  it("rejects stale timestamp on update", async () => {
-    const symbol = "SOL";
-    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
-
+    await program.methods.setPauseFlags(0b0001).rpc(); // PAUSE_STORE
     try {
       await program.methods
-        .updateSentiment(10, 50, 100, new anchor.BN(1))
-        .accounts({ sentiment: sentimentPDA } as any)
+        .storeSentiment("HALT", 50, 50, 100, new anchor.BN(1000), "")
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
-      expect(e.toString()).to.include("StaleTimestamp");
+      expect(e.toString()).to.include("OraclePaused");
     }
-  });
-
-  // ===== Historical Tracking =====
-
-  it("records history snapshot", async () => {
-    const symbol = "SOL";
-    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
-    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
-
     await program.methods
-      .recordHistory(symbol)
-      .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+      .voteSentiment("HALT", 30, 60)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
       .rpc();
 
-    const history = await program.account.sentimentHistory.fetch(historyPDA);
-    expect(history.symbol).to.equal("SOL");
-    expect(history.count).to.equal(1);
-  });
-
-  // ===== User Profile =====
-
-  it("creates a user profile", async () => {
-    await program.methods.createProfile("oracle_admin").rpc();
-
-    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
-    const profile = await program.account.userProfile.fetch(profilePDA);
-    expect(profile.username).to.equal("oracle_admin");
-    expect(profile.reputation).to.equal(100);
-    expect(profile.predictionsMade).to.equal(0);
-  });
-
-  it("rejects empty username", async () => {
-    const user = Keypair.generate();
-    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
-    await provider.connection.confirmTransaction(sig);
-
+    await program.methods.setPauseFlags(0b0100).rpc(); // PAUSE_VOTE
     try {
       await program.methods
-        .createProfile("")
+        .voteSentiment("HALT2", 30, 60)
         .accounts({ user: user.publicKey } as any)
         .signers([user])
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
-      expect(e.toString()).to.include("EmptyUsername");
+      expect(e.toString()).to.include("OraclePaused");
+    }
+
+    // Re-storing an existing symbol is an update, so PAUSE_UPDATE covers it
+    await program.methods.setPauseFlags(0).rpc();
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment("HALT3", 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setPauseFlags(0b0010).rpc(); // PAUSE_UPDATE
+    try {
+      await program.methods.storeSentiment("HALT3", 20, 50, 100, new anchor.BN(base + 1), "").rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OraclePaused");
     }
+    await program.methods.storeSentiment("HALT4", 20, 50, 100, new anchor.BN(base), "").rpc();
+
+    await program.methods.setPauseFlags(0).rpc();
   });
 
-  // ===== Subscriptions =====
+  it("gates voting on an SPL token balance", async () => {
+    const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    const createTokenAccount = async (kp: Keypair, space: number) =>
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: kp.publicKey,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: TOKEN_PROGRAM_ID,
+      });
 
-  it("subscribes and unsubscribes from a token", async () => {
-    const symbol = "SOL";
-    const subPDA = findPDA([
-      SUBSCRIPTION_SEED,
-      authority.publicKey.toBuffer(),
-      Buffer.from(symbol),
-    ]);
+    // InitializeMint2: 0 decimals, we hold the mint and freeze authorities
+    const mint = Keypair.generate();
+    const initMint = new TransactionInstruction({
+      programId: TOKEN_PROGRAM_ID,
+      keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+      data: Buffer.concat([
+        Buffer.from([20, 0]),
+        authority.publicKey.toBuffer(),
+        Buffer.from([1]),
+        authority.publicKey.toBuffer(),
+      ]),
+    });
+    await provider.sendAndConfirm(new Transaction().add(await createTokenAccount(mint, 82), initMint), [mint]);
 
-    await program.methods.subscribeToken(symbol, 1, 50).rpc();
+    // InitializeAccount3 for `owner`, then MintTo `amount` into it
+    const fund = async (owner: PublicKey, amount: number) => {
+      const account = Keypair.generate();
+      const amountLE = Buffer.alloc(8);
+      amountLE.writeBigUInt64LE(BigInt(amount));
+      const initAccount = new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: account.publicKey, isSigner: false, isWritable: true },
+          { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([Buffer.from([18]), owner.toBuffer()]),
+      });
+      const mintTo = new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: mint.publicKey, isSigner: false, isWritable: true },
+          { pubkey: account.publicKey, isSigner: false, isWritable: true },
+          { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        ],
+        data: Buffer.concat([Buffer.from([7]), amountLE]),
+      });
+      await provider.sendAndConfirm(
+        new Transaction().add(await createTokenAccount(account, 165), initAccount, mintTo),
+        [account]
+      );
+      return account.publicKey;
+    };
 
-    const sub = await program.account.subscription.fetch(subPDA);
-    expect(sub.symbol).to.equal("SOL");
-    expect(sub.direction).to.equal(1);
-    expect(sub.alertThreshold).to.equal(50);
+    const [holder, shrimp, anyone] = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const [i, kp] of [holder, shrimp, anyone].entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`gated${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`gated${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    const holderTokens = await fund(holder.publicKey, 100);
+    const shrimpTokens = await fund(shrimp.publicKey, 10);
 
+    await program.methods.setVoteGate(mint.publicKey, new anchor.BN(50)).rpc();
     await program.methods
-      .unsubscribeToken()
-      .accounts({ subscription: subPDA } as any)
+      .voteSentiment("GATE", 40, 70)
+      .accounts({ user: holder.publicKey, gateTokenAccount: holderTokens } as any)
+      .signers([holder])
       .rpc();
 
-    const info = await provider.connection.getAccountInfo(subPDA);
-    expect(info).to.be.null;
-  });
-
-  // ===== Community Voting =====
+    try {
+      await program.methods
+        .voteSentiment("GATE", 40, 70)
+        .accounts({ user: shrimp.publicKey, gateTokenAccount: shrimpTokens } as any)
+        .signers([shrimp])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientGateBalance");
+    }
 
-  it("casts a community vote", async () => {
-    const symbol = "SOL";
-    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
-    const votePDA = findPDA([VOTE_SEED, authority.publicKey.toBuffer(), Buffer.from(symbol)]);
+    // Changing a vote is gated too, and a frozen account doesn't count
+    const holderVote = findPDA([VOTE_SEED, holder.publicKey.toBuffer(), Buffer.from("GATE")]);
+    const changeVote = (gateTokenAccount: PublicKey | null) =>
+      program.methods
+        .updateVote(50, 70)
+        .accounts({ user: holder.publicKey, vote: holderVote, gateTokenAccount } as any)
+        .signers([holder])
+        .rpc();
+    try {
+      await changeVote(null);
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientGateBalance");
+    }
+    // FreezeAccount
+    const freeze = new TransactionInstruction({
+      programId: TOKEN_PROGRAM_ID,
+      keys: [
+        { pubkey: holderTokens, isSigner: false, isWritable: true },
+        { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      ],
+      data: Buffer.from([10]),
+    });
+    await provider.sendAndConfirm(new Transaction().add(freeze));
+    try {
+      await changeVote(holderTokens);
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("GateAccountFrozen");
+    }
 
+    // Ungated: no token account needed
+    await program.methods.setVoteGate(null, new anchor.BN(0)).rpc();
     await program.methods
-      .voteSentiment(symbol, 80, 70)
+      .voteSentiment("GATE", -10, 50)
+      .accounts({ user: anyone.publicKey } as any)
+      .signers([anyone])
       .rpc();
-
-    const vote = await program.account.communityVote.fetch(votePDA);
-    expect(vote.score).to.equal(80);
-    expect(vote.confidence).to.equal(70);
-
-    const profile = await program.account.userProfile.fetch(profilePDA);
-    expect(profile.predictionsMade).to.equal(1);
   });
 
-  // ===== Resolve Prediction =====
+  it("clamps an operator's confidence to its cap", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
 
-  it("resolves a prediction and adjusts reputation", async () => {
-    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    await program.methods.setOperatorMaxConfidence(operator.publicKey, 80).rpc();
 
     await program.methods
-      .resolvePrediction(true)
-      .accounts({ profile: profilePDA } as any)
+      .storeSentiment("CAPD", 40, 100, 500, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .accounts({ authority: operator.publicKey } as any)
+      .signers([operator])
       .rpc();
 
-    let profile = await program.account.userProfile.fetch(profilePDA);
-    expect(profile.correctPredictions).to.equal(1);
-    expect(profile.reputation).to.equal(110);
-
-    await program.methods
-      .resolvePrediction(false)
-      .accounts({ profile: profilePDA } as any)
-      .rpc();
+    const record = await program.account.sentimentRecord.fetch(
+      findPDA([SENTIMENT_SEED, Buffer.from("CAPD")])
+    );
+    expect(record.confidence).to.equal(80);
 
-    profile = await program.account.userProfile.fetch(profilePDA);
-    expect(profile.reputation).to.equal(105);
+    await program.methods.removeOperator(operator.publicKey).rpc();
   });
 
-  // ===== Close Sentiment =====
+  it("limits an operator to its permission bits", async () => {
+    const symbol = "PERM";
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_UPDATE).rpc();
 
-  it("closes a sentiment record (admin)", async () => {
-    const symbol = "TEMP";
-    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    try {
+      await program.methods
+        .storeSentiment("PERM2", 10, 50, 100, new anchor.BN(base), "")
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorNotScoped");
+    }
+
+    await program.methods
+      .updateSentiment(20, 50, 100, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+
+    // Aggregating a batch of sources needs CAN_BATCH on top of CAN_UPDATE
+    try {
+      await program.methods
+        .batchAggregateSymbol(symbol, [
+          { score: 30, confidence: 50, volume: 100, timestamp: new anchor.BN(base + 2) },
+          { score: 40, confidence: 50, volume: 100, timestamp: new anchor.BN(base + 2) },
+        ])
+        .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorNotScoped");
+    }
+
+    // Re-storing an existing symbol is an update, so CAN_STORE alone won't do
+    await program.methods.setOperatorPerms(operator.publicKey, CAN_STORE).rpc();
+    try {
+      await program.methods
+        .storeSentiment(symbol, 30, 50, 100, new anchor.BN(base + 2), "")
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorNotScoped");
+    }
+
+    await program.methods.setOperatorPerms(operator.publicKey, CAN_ALL).rpc();
+    await program.methods
+      .storeSentiment("PERM2", 10, 50, 100, new anchor.BN(base), "")
+      .accounts({ authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
+  it("slashes posted operator stake into the treasury", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    const stakePDA = findPDA([OPERATOR_STAKE_SEED, operator.publicKey.toBuffer()]);
+
+    await program.methods
+      .postStake(new anchor.BN(1e8))
+      .accounts({ operator: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    const rentReserve = (await provider.connection.getAccountInfo(stakePDA))!.lamports - 1e8;
+
+    // The treasury defaults to the authority; a fee payer can't also be the
+    // treasury without muddying the balance check, so use a fresh one
+    const treasury = Keypair.generate();
+    const tsig = await provider.connection.requestAirdrop(treasury.publicKey, 1e9);
+    await provider.connection.confirmTransaction(tsig);
+    await program.methods.setTreasury(treasury.publicKey).rpc();
+
+    await program.methods
+      .slashOperator(new anchor.BN(4e7))
+      .accounts({ stake: stakePDA, treasury: treasury.publicKey } as any)
+      .rpc();
+    const stake = await program.account.operatorStake.fetch(stakePDA);
+    expect(stake.slashedTotal.toNumber()).to.equal(4e7);
+    expect(await provider.connection.getBalance(treasury.publicKey)).to.equal(1e9 + 4e7);
+
+    try {
+      await program.methods
+        .slashOperator(new anchor.BN(7e7))
+        .accounts({ stake: stakePDA, treasury: treasury.publicKey } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientStake");
+    }
+    // Slashing the rest leaves the rent reserve in place
+    await program.methods
+      .slashOperator(new anchor.BN(6e7))
+      .accounts({ stake: stakePDA, treasury: treasury.publicKey } as any)
+      .rpc();
+    expect(await provider.connection.getBalance(stakePDA)).to.equal(rentReserve);
+
+    await program.methods.setTreasury(authority.publicKey).rpc();
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
+  it("makes operators stake to write and holds stake through an unstake delay", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const stakePDA = findPDA([OPERATOR_STAKE_SEED, operator.publicKey.toBuffer()]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.setStakePolicy(new anchor.BN(5e7), new anchor.BN(2)).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    const store = (timestamp: number) =>
+      program.methods
+        .storeSentiment("STAKE", 10, 50, 100, new anchor.BN(timestamp), "")
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+    try {
+      await store(base);
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorStakeTooLow");
+    }
+    await program.methods
+      .postStake(new anchor.BN(5e7))
+      .accounts({ operator: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    await store(base);
+
+    const unstake = () =>
+      program.methods.requestUnstake().accounts({ operator: operator.publicKey } as any).signers([operator]).rpc();
+    const withdraw = () =>
+      program.methods.withdrawStake().accounts({ operator: operator.publicKey } as any).signers([operator]).rpc();
+    try {
+      await unstake();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorStillActive");
+    }
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    try {
+      await withdraw();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnstakeNotRequested");
+    }
+    await unstake();
+    try {
+      await withdraw();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnstakeDelayNotElapsed");
+    }
+
+    await new Promise((r) => setTimeout(r, 3000));
+    const held = (await provider.connection.getAccountInfo(stakePDA))!.lamports;
+    const before = await provider.connection.getBalance(operator.publicKey);
+    await withdraw();
+    // Stake and rent both come back; the provider wallet pays the fee
+    expect((await provider.connection.getBalance(operator.publicKey)) - before).to.equal(held);
+    expect(await provider.connection.getAccountInfo(stakePDA)).to.be.null;
+
+    await program.methods.setStakePolicy(new anchor.BN(0), new anchor.BN(7 * 86_400)).rpc();
+  });
+
+  it("drops a removed operator's reading on refresh", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    await program.methods
+      .storeSentiment("RFSH", 30, 90, 500, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .accounts({ authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    await program.methods.removeOperator(operator.publicKey).rpc();
+
+    const refresh = program.methods.refreshSymbol("RFSH").accounts(await refreshAccounts("RFSH"));
+    const sim = await refresh.simulate();
+    const event = sim.events.find((e: any) => e.name === "canonicalRefreshed");
+    expect(event!.data.droppedCanonical).to.equal(true);
+    await refresh.rpc();
+
+    const record = await program.account.sentimentRecord.fetch(
+      findPDA([SENTIMENT_SEED, Buffer.from("RFSH")])
+    );
+    expect(record.score).to.equal(30);
+    expect(record.confidence).to.equal(0);
+    expect(record.adjustedScore).to.equal(0);
+  });
+
+  it("normalizes an operator's score with its scale factor", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    // Native scale -10..10: multiply by 10.0
+    await program.methods.setOperatorScaleFactor(operator.publicKey, 1000, 0).rpc();
+
+    const symbol = "SCALE";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods
+      .storeSentiment(symbol, 5, 50, 100, new anchor.BN(base), "")
+      .accounts({ authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    let record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(50);
+
+    await program.methods
+      .updateSentiment(-20, 50, 100, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(-100);
+
+    // Native scale 0..1000: subtract 500, then multiply by 0.2
+    await program.methods.setOperatorScaleFactor(operator.publicKey, 20, 500).rpc();
+    const update = program.methods
+      .updateSentiment(750, 50, 100, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+      .signers([operator]);
+    const sim = await update.simulate();
+    const event = sim.events.find((e: any) => e.name === "sentimentUpdated")!;
+    expect(event.data.rawScore).to.equal(750);
+    expect(event.data.score).to.equal(50);
+    await update.rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(50);
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
+  it("pre-registers a symbol config that the first store inherits", async () => {
+    const symbol = "PREREG";
+    await program.methods
+      .registerSymbolConfig(symbol, {
+        activeHours: (1 << 24) - 1,
+        decimals: 0,
+        scaleFactor: 200, // 2.0x
+        metadata: "equity-linked",
+        writeFee: new anchor.BN(0),
+      })
+      .rpc();
+
+    await program.methods
+      .storeSentiment(symbol, 30, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+    expect(record.score).to.equal(60);
+
+    const display = await program.methods.scoreDisplay(symbol).view();
+    expect(display.decimals).to.equal(0);
+    expect(display.score).to.equal(60);
+  });
+
+  it("clamps an extreme raw score under a large scale factor", async () => {
+    const base = Math.floor(Date.now() / 1000);
+    const scores: Record<string, number> = { WIDEUP: 32767, WIDEDN: -32768 };
+    for (const [symbol, raw] of Object.entries(scores)) {
+      await program.methods
+        .registerSymbolConfig(symbol, {
+          activeHours: (1 << 24) - 1,
+          decimals: null,
+          scaleFactor: 1000, // 10.0x
+          metadata: "",
+          writeFee: new anchor.BN(0),
+        })
+        .rpc();
+      await program.methods.storeSentiment(symbol, raw, 50, 100, new anchor.BN(base), "").rpc();
+    }
+    expect((await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from("WIDEUP")]))).score).to.equal(100);
+    expect((await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from("WIDEDN")]))).score).to.equal(-100);
+  });
+
+  it("charges a symbol's write fee into the treasury", async () => {
+    const symbol = "FEED";
+    const treasury = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(treasury.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.setTreasury(treasury.publicKey).rpc();
+    await program.methods
+      .registerSymbolConfig(symbol, {
+        activeHours: (1 << 24) - 1,
+        decimals: null,
+        scaleFactor: 100,
+        metadata: "",
+        writeFee: new anchor.BN(5000),
+      })
+      .rpc();
+
+    const ts = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods.storeSentiment(symbol, 30, 50, 100, new anchor.BN(ts), "").rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("TreasuryRequired");
+    }
+
+    await program.methods
+      .storeSentiment(symbol, 30, 50, 100, new anchor.BN(ts), "")
+      .accounts({ treasury: treasury.publicKey } as any)
+      .rpc();
+    await program.methods
+      .updateSentiment(35, 50, 100, new anchor.BN(ts + 1), null, "")
+      .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), treasury: treasury.publicKey } as any)
+      .rpc();
+    expect(await provider.connection.getBalance(treasury.publicKey)).to.equal(1e9 + 10000);
+
+    try {
+      await program.methods
+        .registerSymbolConfig(symbol, {
+          activeHours: (1 << 24) - 1,
+          decimals: null,
+          scaleFactor: 100,
+          metadata: "",
+          writeFee: new anchor.BN(2e9),
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidWriteFee");
+    }
+    await program.methods.setTreasury(authority.publicKey).rpc();
+  });
+
+  it("only creates registered symbols when symbols are not permissionless", async () => {
+    const ts = new anchor.BN(Math.floor(Date.now() / 1000));
+    const mint = Keypair.generate().publicKey;
+    await program.methods.setPermissionlessSymbols(false).rpc();
+
+    try {
+      await program.methods.storeSentiment("S0L", 10, 50, 100, ts, "").rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("SymbolNotRegistered");
+    }
+
+    await program.methods.registerSymbol("REG", mint).rpc();
+    const registry = await program.account.symbolRegistry.fetch(
+      findPDA([SYMBOL_REGISTRY_SEED, Buffer.from("REG")])
+    );
+    expect(registry.mint.toBase58()).to.equal(mint.toBase58());
+    await program.methods.storeSentiment("REG", 10, 50, 100, ts, "").rpc();
+
+    await program.methods.setPermissionlessSymbols(true).rpc();
+    await program.methods.storeSentiment("S0L", 10, 50, 100, ts, "").rpc();
+  });
+
+  it("stores the concurrent Pyth price from the registered feed", async () => {
+    // Preloaded by tests/Test.base.toml from tests/fixtures/pyth_*.json
+    const feed = new PublicKey("FUmPw5hKUYaJ9YgXNUTdnurVzetysd8JubEVEuURQwJs");
+    const staleFeed = new PublicKey("GaN9ZanHvsgYhFWr6PnCnCdWnZnRHNzMFV2oDdgi9bvh");
+    const haltedFeed = new PublicKey("4mbogLwcE8s1o5Pt27jC3sYBHFKzCdfbG8kNVwW7hK2d");
+    const unownedFeed = new PublicKey("BRWY6wJ6V1gzQJN8jKtBgopUxapcJmo84xPTSwMpXttL");
+    const symbol = "PYTH";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.registerSymbol(symbol, Keypair.generate().publicKey).rpc();
+    await program.methods.setPriceFeed(symbol, feed).rpc();
+
+    await program.methods.setPythMaxAge(new anchor.BN(60)).rpc();
+    await program.methods
+      .storeSentiment(symbol, 40, 80, 100, new anchor.BN(base), "")
+      .accounts({ priceFeed: feed } as any)
+      .rpc();
+    let record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.price.toNumber()).to.equal(15_000_000_000);
+    expect(record.priceExpo).to.equal(-8);
+    expect(record.pricePublishTime.toNumber()).to.equal(4_102_444_800);
+
+    try {
+      await program.methods
+        .storeSentiment(symbol, 41, 80, 100, new anchor.BN(base + 1), "")
+        .accounts({ priceFeed: Keypair.generate().publicKey } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidPriceFeed");
+    }
+
+    const rejects = async (priceFeed: PublicKey, error: string) => {
+      await program.methods.setPriceFeed(symbol, priceFeed).rpc();
+      try {
+        await program.methods
+          .storeSentiment(symbol, 41, 80, 100, new anchor.BN(base + 1), "")
+          .accounts({ priceFeed } as any)
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include(error);
+      }
+    };
+    await rejects(staleFeed, "PriceFeedStale");
+    await rejects(haltedFeed, "PriceFeedNotTrading");
+    await rejects(unownedFeed, "InvalidPriceFeed");
+
+    // A store without a feed clears the earlier price
+    await program.methods.storeSentiment(symbol, 41, 80, 100, new anchor.BN(base + 1), "").rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.price.toNumber()).to.equal(0);
+    expect(record.pricePublishTime.toNumber()).to.equal(0);
+  });
+
+  it("keeps direct reads open on an allowlisted feed", async () => {
+    const symbol = "PREREG";
+    const reader = Keypair.generate().publicKey;
+    await program.methods.setReaderAllowlist(symbol, [reader]).rpc();
+    const config = await program.account.symbolConfig.fetch(findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]));
+    expect(config.readerAllowlist.map((k: PublicKey) => k.toBase58())).to.deep.equal([reader.toBase58()]);
+
+    // Only CPI callers are checked against the allowlist
+    const display = await program.methods.scoreDisplay(symbol).view();
+    expect(display.score).to.equal(60);
+
+    await program.methods.setReaderAllowlist(symbol, []).rpc();
+  });
+
+  it("rejects CPI reads from a program off the allowlist", async () => {
+    const symbol = "PREREG";
+    const readFeed = () =>
+      reader.methods.readSentiment(symbol, new anchor.BN(0)).accounts({
+        sentinel: findPDA([SENTINEL_SEED]),
+        sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]),
+        symbolConfig: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        alias: findPDA([SYMBOL_ALIAS_SEED, Buffer.from(symbol)]),
+        sentinelProgram: program.programId,
+      } as any);
+
+    await program.methods.setReaderAllowlist(symbol, [Keypair.generate().publicKey]).rpc();
+    try {
+      await readFeed().rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ReaderNotAllowed");
+    }
+
+    await program.methods.setReaderAllowlist(symbol, [reader.programId]).rpc();
+    const reading = await readFeed().view();
+    expect(reading.score).to.equal(60);
+
+    await program.methods.setReaderAllowlist(symbol, []).rpc();
+  });
+
+  // ===== Update Sentiment =====
+
+  it("updates an existing sentiment record", async () => {
+    const symbol = "SOL";
+    const ts = Math.floor(Date.now() / 1000) + 1000;
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+
+    await program.methods
+      .updateSentiment(-20, 60, 2000, new anchor.BN(ts), null, "post-CPI spike")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(-20);
+    expect(record.confidence).to.equal(60);
+    expect(record.volume).to.equal(2000);
+    expect(record.updateCount).to.equal(2);
+    expect(record.note).to.equal("post-CPI spike");
+  });
+
+  it("chains each reading's hash onto the previous one", async () => {
+    const symbol = "CHAIN";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    const link = (prev: Buffer, d: any) => {
+      const fields = Buffer.alloc(14);
+      fields.writeInt8(d.score, 0);
+      fields.writeUInt8(d.confidence, 1);
+      fields.writeUInt32LE(d.volume, 2);
+      fields.writeBigInt64LE(BigInt(d.timestamp.toString()), 6);
+      return createHash("sha256").update(Buffer.concat([prev, fields])).digest();
+    };
+
+    const store = program.methods.storeSentiment(symbol, 5, 60, 100, new anchor.BN(base), "");
+    let sim = await store.simulate();
+    let chain = link(Buffer.alloc(32), sim.events.find((e: any) => e.name === "sentimentUpdated")!.data);
+    await store.rpc();
+
+    for (const [i, score] of [-10, 25, 40].entries()) {
+      const update = program.methods
+        .updateSentiment(score, 70 + i, 200 * (i + 1), new anchor.BN(base + i + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any);
+      sim = await update.simulate();
+      const event = sim.events.find((e: any) => e.name === "sentimentUpdated")!;
+      chain = link(chain, event.data);
+      expect(Buffer.from(event.data.hash).equals(chain)).to.be.true;
+      await update.rpc();
+    }
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(Buffer.from(record.prevHash).equals(chain)).to.be.true;
+  });
+
+  it("snapshots into history on update when auto_history is on", async () => {
+    const symbol = "AUTOH";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+
+    await program.methods.storeSentiment(symbol, 10, 70, 100, new anchor.BN(base), "").rpc();
+    await program.methods.recordHistory(symbol, 8).rpc();
+
+    // Flag off: passing the history leaves it untouched
+    await program.methods
+      .updateSentiment(20, 70, 100, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+      .rpc();
+    expect((await program.account.sentimentHistory.fetch(historyPDA)).count).to.equal(1);
+
+    await program.methods.setAutoHistory(symbol, true).rpc();
+    await program.methods
+      .updateSentiment(30, 80, 200, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(30);
+    const history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.count).to.equal(2);
+    expect(history.snapshots[1].score).to.equal(30);
+
+    // Flag on but no history passed: a plain update
+    await program.methods
+      .updateSentiment(40, 80, 200, new anchor.BN(base + 3), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(40);
+    expect((await program.account.sentimentHistory.fetch(historyPDA)).count).to.equal(2);
+
+    // Another symbol's history is rejected
+    await program.methods.storeSentiment("AUTOX", 0, 50, 0, new anchor.BN(base), "").rpc();
+    await program.methods.recordHistory("AUTOX", 8).rpc();
+    try {
+      await program.methods
+        .updateSentiment(50, 80, 200, new anchor.BN(base + 4), null, "")
+        .accounts({ sentiment: sentimentPDA, history: findPDA([HISTORY_SEED, Buffer.from("AUTOX")]) } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidAccount");
+    }
+  });
+
+  it("rejects a non-printable sentiment note", async () => {
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from("SOL")]);
+    try {
+      await program.methods
+        .updateSentiment(10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000) + 1500), null, "line\nbreak")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidNote");
+    }
+  });
+
+  it("rejects stale timestamp on update", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+
+    try {
+      await program.methods
+        .updateSentiment(10, 50, 100, new anchor.BN(1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("StaleTimestamp");
+    }
+  });
+
+  it("treats a retried update with the same idempotency key as a no-op", async () => {
+    const symbol = "SOL";
+    const ts = Math.floor(Date.now() / 1000) + 2000;
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const key = Array.from(Keypair.generate().publicKey.toBytes().slice(0, 16));
+
+    await program.methods
+      .updateSentiment(-10, 65, 2100, new anchor.BN(ts), key, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    const first = await program.account.sentimentRecord.fetch(sentimentPDA);
+
+    // Retry lands again: succeeds without re-applying
+    await program.methods
+      .updateSentiment(-10, 65, 2100, new anchor.BN(ts), key, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    const retried = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(retried.updateCount).to.equal(first.updateCount);
+    expect(retried.score).to.equal(-10);
+  });
+
+  it("keeps probationary operator updates out of the canonical score", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods.setOperatorProbation(new anchor.BN(86400)).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    const before = await program.account.sentimentRecord.fetch(sentimentPDA);
+
+    await program.methods
+      .updateSentiment(90, 99, 9999, new anchor.BN(Math.floor(Date.now() / 1000) + 3000), null, "")
+      .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+
+    const after = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(after.score).to.equal(before.score);
+    expect(after.updateCount).to.equal(before.updateCount);
+    expect(after.provisionalScore).to.equal(90);
+    expect(after.provisionalUpdater.toBase58()).to.equal(operator.publicKey.toBase58());
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    await program.methods.setOperatorProbation(new anchor.BN(0)).rpc();
+  });
+
+  it("aggregates several readings into one confidence-weighted write", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const ts = Math.floor(Date.now() / 1000) + 4000;
+
+    await program.methods
+      .batchAggregateSymbol(symbol, [
+        { score: 40, confidence: 100, volume: 1000, timestamp: new anchor.BN(ts) },
+        { score: -20, confidence: 50, volume: 500, timestamp: new anchor.BN(ts - 10) },
+      ])
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(20); // (40*100 - 20*50) / 150
+    expect(record.confidence).to.equal(75);
+    expect(record.volume).to.equal(1500);
+    expect(record.timestamp.toNumber()).to.equal(ts);
+  });
+
+  it("applies no batch entries when one is invalid", async () => {
+    const symbols = ["BAT0", "BAT1", "BAT2", "BAT3"];
+    const pdas = symbols.map((symbol) => findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+    const base = Math.floor(Date.now() / 1000);
+    for (const symbol of symbols) {
+      await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    }
+    const remaining = symbols.flatMap((symbol, i) => [
+      { pubkey: pdas[i], isSigner: false, isWritable: true },
+      { pubkey: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: false },
+    ]);
+    const updates = symbols.map((_, i) => ({
+      score: 20,
+      confidence: 60,
+      volume: 200,
+      // Item 3 is no newer than its stored reading
+      timestamp: new anchor.BN(i === 3 ? base : base + 1),
+    }));
+
+    try {
+      await program.methods.batchUpdateSentiments(updates).remainingAccounts(remaining).simulate();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      const logs: string[] = e.simulationResponse.logs;
+      expect(logs.join("\n")).to.include("StaleTimestamp");
+      // The failing item is last, yet nothing was emitted for the three before it
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(logs)];
+      expect(events.some((ev) => ev.name === "sentimentUpdated")).to.equal(false);
+    }
+    try {
+      await program.methods.batchUpdateSentiments(updates).remainingAccounts(remaining).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("StaleTimestamp");
+    }
+    for (const pda of pdas.slice(0, 3)) {
+      const record = await program.account.sentimentRecord.fetch(pda);
+      expect(record.score).to.equal(10);
+      expect(record.updateCount).to.equal(1);
+    }
+
+    try {
+      await program.methods
+        .batchUpdateSentiments(updates.slice(0, 2))
+        .remainingAccounts([remaining[0], remaining[1], remaining[0], remaining[1]])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("DuplicateBatchAccount");
+    }
+  });
+
+  it("applies the symbol scale factor to batch entries", async () => {
+    const symbol = "BSCALE";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    await program.methods
+      .registerSymbolConfig(symbol, {
+        activeHours: (1 << 24) - 1,
+        decimals: null,
+        scaleFactor: 200, // 2.0x
+        metadata: "",
+        writeFee: new anchor.BN(0),
+      })
+      .rpc();
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "seed reading").rpc();
+
+    await program.methods
+      .batchUpdateSentiments([{ score: 15, confidence: 60, volume: 200, timestamp: new anchor.BN(base + 1) }])
+      .remainingAccounts([
+        { pubkey: sentimentPDA, isSigner: false, isWritable: true },
+        { pubkey: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: false },
+      ])
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(30);
+    // A batch entry has no note, so the stored reading's note doesn't carry over
+    expect(record.note).to.equal("");
+
+    await program.methods
+      .batchAggregateSymbol(symbol, [
+        { score: 20, confidence: 50, volume: 100, timestamp: new anchor.BN(base + 2) },
+        { score: 10, confidence: 50, volume: 100, timestamp: new anchor.BN(base + 2) },
+      ])
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(30);
+  });
+
+  it("rejects a sentiment record outside its canonical PDA", async () => {
+    // Fixture account: a program-owned SentimentRecord for SOL at an arbitrary address
+    const forged = new PublicKey("Am86fDC8utbu78cyNiahgzoz5g14WtvYQBebMTx59gbP");
+    try {
+      await program.methods
+        .batchUpdateSentiments([
+          { score: -90, confidence: 100, volume: 1, timestamp: new anchor.BN(Math.floor(Date.now() / 1000)) },
+        ])
+        .remainingAccounts([
+          { pubkey: forged, isSigner: false, isWritable: true },
+          { pubkey: findPDA([SYMBOL_CONFIG_SEED, Buffer.from("SOL")]), isSigner: false, isWritable: false },
+        ])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("WrongSentimentPda");
+    }
+  });
+
+  it("flags a volume spike against the running average", async () => {
+    const symbol = "VOLA";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 1000, new anchor.BN(base), "").rpc();
+    for (const [i, volume] of [1200, 800, 1100].entries()) {
+      await program.methods
+        .updateSentiment(10, 50, volume, new anchor.BN(base + i + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+    }
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.volumeStddev.toNumber()).to.be.greaterThan(0);
+
+    await program.methods.setVolumeAnomalySigmas(3).rpc();
+    const sim = await program.methods
+      .updateSentiment(10, 50, 100000, new anchor.BN(base + 10), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "volumeAnomaly");
+    expect(event).to.exist;
+    expect(event!.data.volume).to.equal(100000);
+    await program.methods.setVolumeAnomalySigmas(0).rpc();
+  });
+
+  it("trips the volatility circuit and holds off operator updates", async () => {
+    const symbol = "CALM";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 1000, new anchor.BN(base), "").rpc();
+    await program.methods.setVolatilityCircuit(10, true).rpc();
+
+    const spike = program.methods
+      .updateSentiment(10, 50, 100000, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA } as any);
+    const sim = await spike.simulate();
+    const event = sim.events.find((e: any) => e.name === "volatilityCircuitTripped");
+    expect(event).to.exist;
+    expect(event!.data.enforced).to.equal(true);
+    await spike.rpc();
+
+    try {
+      await program.methods
+        .updateSentiment(12, 50, 1000, new anchor.BN(base + 2), null, "")
+        .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("TooVolatile");
+    }
+
+    // The authority can still push values by hand
+    await program.methods
+      .updateSentiment(12, 50, 1000, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(12);
+
+    await program.methods.setVolatilityCircuit(0, false).rpc();
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
+  it("gates updates to a symbol's active UTC hours", async () => {
+    const symbol = "MKT";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+
+    // Open only the validator's current hour and its neighbours so the test
+    // cannot straddle a boundary
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+    const hour = Math.floor((now! % 86400) / 3600);
+    const around = [23, 0, 1].reduce((mask, d) => mask | (1 << ((hour + d) % 24)), 0);
+    const ALL_HOURS = (1 << 24) - 1;
+
+    await program.methods.setActiveHours(symbol, ALL_HOURS & ~around).rpc();
+    try {
+      await program.methods
+        .updateSentiment(20, 50, 100, new anchor.BN(base + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("MarketClosed");
+    }
+
+    await program.methods.setActiveHours(symbol, around).rpc();
+    await program.methods
+      .updateSentiment(20, 50, 100, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(20);
+
+    await program.methods.setActiveHours(symbol, ALL_HOURS).rpc();
+  });
+
+  it("formats a score for display with configured labels", async () => {
+    // MKT was last updated to a score of 20, on the default bullish threshold
+    let display = await program.methods.scoreDisplay("MKT").view();
+    expect(display.score).to.equal(2000);
+    expect(display.decimals).to.equal(2);
+    expect(display.label).to.equal("Bullish");
+
+    await program.methods.setDisplayConfig(1, 50, -50).rpc();
+    display = await program.methods.scoreDisplay("MKT").view();
+    expect(display.score).to.equal(200);
+    expect(display.label).to.equal("Neutral");
+    await program.methods.setDisplayConfig(2, 20, -20).rpc();
+  });
+
+  it("returns the record and current slots for freshness checks", async () => {
+    const reading = await program.methods.getSentimentWithSlot("MKT").accounts(readAccounts("MKT")).view();
+    expect(reading.score).to.equal(20);
+    expect(reading.recordSlot.toNumber()).to.be.greaterThan(0);
+    expect(reading.currentSlot.toNumber()).to.be.at.least(reading.recordSlot.toNumber());
+  });
+
+  it("returns the stored reading with a staleness flag", async () => {
+    const symbol = "VIEW";
+    const ts = Math.floor(Date.now() / 1000) - 100;
+    await program.methods.storeSentiment(symbol, -35, 70, 4200, new anchor.BN(ts), "").rpc();
+    const record = await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+
+    const view = await program.methods.getSentiment(symbol, new anchor.BN(3600)).accounts(readAccounts(symbol)).view();
+    expect(view.score).to.equal(record.score);
+    expect(view.confidence).to.equal(record.confidence);
+    expect(view.volume).to.equal(record.volume);
+    expect(view.timestamp.toNumber()).to.equal(record.timestamp.toNumber());
+    expect(view.stale).to.equal(false);
+
+    const old = await program.methods.getSentiment(symbol, new anchor.BN(10)).accounts(readAccounts(symbol)).view();
+    expect(old.stale).to.equal(true);
+  });
+
+  it("hands the reading to a consumer program over CPI", async () => {
+    const symbol = "VIEW";
+    const record = await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+    const readFeed = (maxAge: number) =>
+      reader.methods.readSentiment(symbol, new anchor.BN(maxAge)).accounts({
+        sentinel: findPDA([SENTINEL_SEED]),
+        sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]),
+        symbolConfig: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        alias: findPDA([SYMBOL_ALIAS_SEED, Buffer.from(symbol)]),
+        sentinelProgram: program.programId,
+      } as any);
+
+    const reading = await readFeed(3600).view();
+    expect(reading.score).to.equal(-35);
+    expect(reading.confidence).to.equal(70);
+    expect(reading.timestamp.toNumber()).to.equal(record.timestamp.toNumber());
+    expect(reading.stale).to.equal(false);
+
+    const old = await readFeed(10).view();
+    expect(old.score).to.equal(-35);
+    expect(old.stale).to.equal(true);
+  });
+
+  it("decays confidence by half-life", async () => {
+    const symbol = "DECAY";
+    const halfLife = 3600;
+    await program.methods
+      .storeSentiment(symbol, 25, 80, 100, new anchor.BN(Math.floor(Date.now() / 1000) - halfLife), "")
+      .rpc();
+
+    const sim = await program.methods.readDecayed(symbol, new anchor.BN(halfLife)).simulate();
+    const event = sim.events.find((e: any) => e.name === "decayedReading");
+    expect(event!.data.score).to.equal(25);
+    expect(event!.data.decayedConfidence).to.be.closeTo(40, 1);
+
+    try {
+      await program.methods.readDecayed(symbol, new anchor.BN(0)).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidHalfLife");
+    }
+  });
+
+  it("commits a consensus-gated update only after enough approvals", async () => {
+    const symbol = "CRIT";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const pendingPDA = findPDA([PENDING_UPDATE_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setConsensusThreshold(symbol, 2).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    try {
+      await program.methods
+        .updateSentiment(50, 80, 100, new anchor.BN(base + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ConsensusRequired");
+    }
+    try {
+      await program.methods
+        .updateConfidence(symbol, 90, new anchor.BN(base + 1))
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ConsensusRequired");
+    }
+
+    await program.methods.proposeUpdate(symbol, 50, 80, 100, new anchor.BN(base + 1)).rpc();
+    try {
+      await program.methods
+        .approveUpdate(symbol)
+        .accounts({ proposer: authority.publicKey } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AlreadyApproved");
+    }
+    let record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(10);
+
+    await program.methods
+      .approveUpdate(symbol)
+      .accounts({ proposer: authority.publicKey, approver: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(50);
+    expect(record.timestamp.toNumber()).to.equal(base + 1);
+    expect(await provider.connection.getAccountInfo(pendingPDA)).to.be.null;
+
+    // An approval from an operator removed before the commit no longer counts
+    const second = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .proposeUpdate(symbol, 55, 80, 100, new anchor.BN(base + 2))
+      .accounts({ proposer: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    await program.methods
+      .approveUpdate(symbol)
+      .accounts({ proposer: operator.publicKey } as any)
+      .rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(50);
+    const pending = await program.account.pendingUpdate.fetch(pendingPDA);
+    expect(pending.approvals.map((k: PublicKey) => k.toBase58())).to.deep.equal([authority.publicKey.toBase58()]);
+
+    await program.methods.addOperator(second.publicKey, CAN_ALL).rpc();
+    await program.methods
+      .approveUpdate(symbol)
+      .accounts({ proposer: operator.publicKey, approver: second.publicKey } as any)
+      .signers([second])
+      .rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(55);
+
+    await program.methods.removeOperator(second.publicKey).rpc();
+  });
+
+  it("keeps unstaked operators out of consensus updates", async () => {
+    const symbol = "STKCON";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const pendingPDA = findPDA([PENDING_UPDATE_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const stakePDA = findPDA([OPERATOR_STAKE_SEED, operator.publicKey.toBuffer()]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setConsensusThreshold(symbol, 1).rpc();
+    await program.methods.setStakePolicy(new anchor.BN(5e7), new anchor.BN(2)).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    // A single-approval threshold would commit an unstaked proposal at once
+    try {
+      await program.methods
+        .proposeUpdate(symbol, 40, 80, 100, new anchor.BN(base + 1))
+        .accounts({ proposer: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorStakeTooLow");
+    }
+
+    await program.methods.setConsensusThreshold(symbol, 2).rpc();
+    await program.methods.proposeUpdate(symbol, 40, 80, 100, new anchor.BN(base + 1)).rpc();
+    const approve = () =>
+      program.methods
+        .approveUpdate(symbol)
+        .accounts({ proposer: authority.publicKey, approver: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+    try {
+      await approve();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorStakeTooLow");
+    }
+    await program.methods
+      .postStake(new anchor.BN(5e7))
+      .accounts({ operator: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    await approve();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(40);
+
+    // An approval whose stake is slashed below the minimum is dropped
+    await program.methods
+      .proposeUpdate(symbol, 55, 80, 100, new anchor.BN(base + 2))
+      .accounts({ proposer: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    await program.methods
+      .slashOperator(new anchor.BN(1e7))
+      .accounts({ stake: stakePDA, treasury: authority.publicKey } as any)
+      .rpc();
+    await program.methods
+      .approveUpdate(symbol)
+      .accounts({ proposer: operator.publicKey } as any)
+      .remainingAccounts([{ pubkey: stakePDA, isSigner: false, isWritable: false }])
+      .rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(40);
+    const pending = await program.account.pendingUpdate.fetch(pendingPDA);
+    expect(pending.approvals.map((k: PublicKey) => k.toBase58())).to.deep.equal([authority.publicKey.toBase58()]);
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    await program.methods.setStakePolicy(new anchor.BN(0), new anchor.BN(7 * 86_400)).rpc();
+  });
+
+  it("doesn't count probationary operators toward consensus", async () => {
+    const symbol = "PROBCON";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const pendingPDA = findPDA([PENDING_UPDATE_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setConsensusThreshold(symbol, 1).rpc();
+    await program.methods.setOperatorProbation(new anchor.BN(86400)).rpc();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    await program.methods.setOperatorMaxConfidence(operator.publicKey, 50).rpc();
+
+    // The proposal opens without the proposer's approval, so it doesn't commit
+    await program.methods
+      .proposeUpdate(symbol, 60, 90, 100, new anchor.BN(base + 1))
+      .accounts({ proposer: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(10);
+    const pending = await program.account.pendingUpdate.fetch(pendingPDA);
+    expect(pending.approvals).to.be.empty;
+    expect(pending.confidence).to.equal(50);
+
+    try {
+      await program.methods
+        .approveUpdate(symbol)
+        .accounts({ proposer: operator.publicKey, approver: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorOnProbation");
+    }
+
+    await program.methods.approveUpdate(symbol).accounts({ proposer: operator.publicKey } as any).rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(60);
+    expect(record.confidence).to.equal(50);
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    await program.methods.setOperatorProbation(new anchor.BN(0)).rpc();
+  });
+
+  it("guards consumers against a stale reading", async () => {
+    const symbol = "OLD";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    // A reading stamped ten minutes ago
+    const ts = Math.floor(Date.now() / 1000) - 600;
+    await program.methods.storeSentiment(symbol, 5, 50, 100, new anchor.BN(ts), "").rpc();
+
+    await program.methods.assertFresh(new anchor.BN(3600)).accounts({ sentiment: sentimentPDA } as any).rpc();
+    try {
+      await program.methods.assertFresh(new anchor.BN(60)).accounts({ sentiment: sentimentPDA } as any).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("SentimentStale");
+    }
+  });
+
+  it("flags updates made out of the operator rotation", async () => {
+    const symbol = "TURN";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const operator = Keypair.generate();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.preferredUpdater.toBase58()).to.equal(operator.publicKey.toBase58());
+
+    // The authority may still write during the operator's turn, but it is flagged
+    const sim = await program.methods
+      .updateSentiment(15, 50, 100, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "sentimentUpdated");
+    expect(event!.data.outOfTurn).to.equal(true);
+
+    await program.methods.removeOperator(operator.publicKey).rpc();
+  });
+
+  it("revises confidence without touching score or volume", async () => {
+    const symbol = "VOLA";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const before = await program.account.sentimentRecord.fetch(sentimentPDA);
+
+    await program.methods
+      .updateConfidence(symbol, 15, new anchor.BN(Math.floor(Date.now() / 1000) + 6000))
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+
+    const after = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(after.confidence).to.equal(15);
+    expect(after.score).to.equal(before.score);
+    expect(after.volume).to.equal(before.volume);
+    expect(after.timestamp.toNumber()).to.be.greaterThan(before.timestamp.toNumber());
+  });
+
+  it("averages contributions from several operators by confidence", async () => {
+    const symbol = "MEAN";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 0, 50, 100, new anchor.BN(base), "").rpc();
+
+    const readings = [
+      { score: 30, confidence: 100 },
+      { score: 50, confidence: 50 },
+      { score: 70, confidence: 50 },
+    ];
+    const operators = readings.map(() => Keypair.generate());
+    for (const op of operators) {
+      const sig = await provider.connection.requestAirdrop(op.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods.addOperator(op.publicKey, CAN_ALL).rpc();
+    }
+
+    let event: any;
+    for (let i = 0; i < readings.length; i++) {
+      const { score, confidence } = readings[i];
+      const method = program.methods
+        .contributeSentiment(symbol, score, confidence, 100, new anchor.BN(base + 1 + i))
+        .accounts({ sentiment: sentimentPDA, authority: operators[i].publicKey } as any)
+        .signers([operators[i]]);
+      if (i === readings.length - 1) {
+        const sim = await method.simulate();
+        event = sim.events.find((e: any) => e.name === "sentimentContributed");
+      }
+      await method.rpc();
+    }
+
+    // (30 * 100 + 50 * 50 + 70 * 50) / 200 = 45
+    expect(event.data.runningScore).to.equal(45);
+    expect(event.data.sourceCount).to.equal(3);
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(45);
+    expect(record.sourceCount).to.equal(3);
+    expect(record.confidence).to.equal(66);
+
+    // Same window: a second reading from the same key is rejected, and so
+    // is a reading that isn't strictly newer
+    const contribute = (timestamp: number) =>
+      program.methods.contributeSentiment(symbol, 90, 100, 100, new anchor.BN(timestamp));
+    try {
+      await contribute(base + 4)
+        .accounts({ sentiment: sentimentPDA, authority: operators[0].publicKey } as any)
+        .signers([operators[0]])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("DuplicateContribution");
+    }
+    try {
+      await contribute(base + 3).accounts({ sentiment: sentimentPDA } as any).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("StaleTimestamp");
+    }
+
+    for (const op of operators) {
+      await program.methods.removeOperator(op.publicKey).rpc();
+    }
+  });
+
+  it("stores a symmetric confidence-adjusted score", async () => {
+    const symbol = "ADJ";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, -50, 50, 1000, new anchor.BN(base), "").rpc();
+    let record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.adjustedScore).to.equal(-25);
+
+    const sim = await program.methods
+      .updateSentiment(50, 50, 1000, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "sentimentUpdated");
+    expect(event!.data.adjustedScore).to.equal(25);
+
+    await program.methods
+      .updateSentiment(33, 75, 1000, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.adjustedScore).to.equal(25);
+  });
+
+  it("still updates state with event emission disabled", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const ts = Math.floor(Date.now() / 1000) + 5000;
+
+    await program.methods.setEventsEnabled(false).rpc();
+    const before = await program.account.sentimentRecord.fetch(sentimentPDA);
+
+    await program.methods
+      .updateSentiment(33, 66, 3300, new anchor.BN(ts), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(33);
+    expect(record.timestamp.toNumber()).to.equal(ts);
+    expect(record.updateCount).to.equal(before.updateCount + 1);
+
+    await program.methods.setEventsEnabled(true).rpc();
+  });
+
+  it("limits a symbol-scoped operator to its granted symbol", async () => {
+    const operator = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(operator.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from("SOL")]);
+    const ts = Math.floor(Date.now() / 1000) + 5001;
+
+    await program.methods.grantSymbolOperator(operator.publicKey, "SOL").rpc();
+    await program.methods
+      .updateSentiment(33, 66, 3300, new anchor.BN(ts), null, "")
+      .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+      .signers([operator])
+      .rpc();
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.timestamp.toNumber()).to.equal(ts);
+
+    // A scoped operator's reading survives a refresh while its grant stands
+    let sim = await program.methods.refreshSymbol("SOL").accounts(await refreshAccounts("SOL")).simulate();
+    let event = sim.events.find((e: any) => e.name === "canonicalRefreshed");
+    expect(event!.data.droppedCanonical).to.equal(false);
+
+    try {
+      await program.methods
+        .storeSentiment("BONK", 10, 50, 100, new anchor.BN(ts), "")
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorNotScoped");
+    }
+
+    await program.methods.revokeSymbolOperator(operator.publicKey, "SOL").rpc();
+    try {
+      await program.methods
+        .updateSentiment(33, 66, 3300, new anchor.BN(ts + 1), null, "")
+        .accounts({ sentiment: sentimentPDA, authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorNotScoped");
+    }
+
+    // Once revoked, its reading is no longer backed by a live grant
+    sim = await program.methods.refreshSymbol("SOL").accounts(await refreshAccounts("SOL")).simulate();
+    event = sim.events.find((e: any) => e.name === "canonicalRefreshed");
+    expect(event!.data.droppedCanonical).to.equal(true);
+  });
+
+  it("resolves an operator value dispute to the median vote", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const disputePDA = findPDA([OPERATOR_DISPUTE_SEED, Buffer.from(symbol)]);
+    const operators = [Keypair.generate(), Keypair.generate()];
+    for (const op of operators) {
+      await program.methods.addOperator(op.publicKey, CAN_ALL).rpc();
+    }
+
+    await program.methods.openValueDispute(symbol).rpc();
+
+    const values = [10, 30];
+    for (let i = 0; i < operators.length; i++) {
+      await program.methods
+        .operatorDisputeVote(values[i])
+        .accounts({ dispute: disputePDA, operator: operators[i].publicKey } as any)
+        .signers([operators[i]])
+        .rpc();
+    }
+
+    try {
+      await program.methods
+        .operatorDisputeVote(50)
+        .accounts({ dispute: disputePDA, operator: operators[0].publicKey } as any)
+        .signers([operators[0]])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AlreadyVoted");
+    }
+
+    // Full-strength penalty: a 20-point spread costs 10% of the confidence
+    await program.methods.setSpreadPenaltyFactor(100).rpc();
+    const before = await program.account.sentimentRecord.fetch(sentimentPDA);
+
+    await program.methods
+      .resolveValueDispute(symbol)
+      .accounts({ sentiment: sentimentPDA, dispute: disputePDA } as any)
+      .rpc();
+
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(20);
+    expect(record.confidence).to.equal(before.confidence - Math.floor(before.confidence / 10));
+    await program.methods.setSpreadPenaltyFactor(0).rpc();
+    const dispute = await program.account.operatorDispute.fetch(disputePDA);
+    expect(dispute.open).to.equal(false);
+
+    for (const op of operators) {
+      await program.methods.removeOperator(op.publicKey).rpc();
+    }
+  });
+
+  // ===== Historical Tracking =====
+
+  it("rejects a second rapid update under the minimum interval", async () => {
+    const symbol = "RAPID";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setUpdateInterval(new anchor.BN(60)).rpc();
+
+    await program.methods
+      .updateSentiment(12, 50, 100, new anchor.BN(base + 60), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    try {
+      await program.methods
+        .updateSentiment(14, 50, 100, new anchor.BN(base + 70), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UpdateTooFrequent");
+    }
+    await program.methods.setUpdateInterval(new anchor.BN(0)).rpc();
+  });
+
+  it("enforces a per-symbol cooldown", async () => {
+    const symbol = "HOURLY";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.setSymbolCooldown(symbol, new anchor.BN(3600)).rpc();
+
+    // Bounded, and closed to plain operators without a grant for the symbol
+    try {
+      await program.methods.setSymbolCooldown(symbol, new anchor.BN(86_401)).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidSymbolCooldown");
+    }
+    const operator = Keypair.generate();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    try {
+      await program.methods
+        .setSymbolCooldown(symbol, new anchor.BN(0))
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientAuthorityScope");
+    }
+    await program.methods.removeOperator(operator.publicKey).rpc();
+
+    try {
+      await program.methods
+        .updateSentiment(12, 50, 100, new anchor.BN(base + 60), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UpdateTooFrequent");
+    }
+    await program.methods
+      .updateSentiment(12, 50, 100, new anchor.BN(base + 3600), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+  });
+
+  it("flags a symbol that misses its expected cadence", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    // Backdating the reading stands in for a warped clock
+    await program.methods.storeSentiment("LIVE", 10, 50, 100, new anchor.BN(now), "").rpc();
+    await program.methods.storeSentiment("STALL", 10, 50, 100, new anchor.BN(now - 1000), "").rpc();
+
+    // Unmonitored symbols never stall
+    let sim = await program.methods.checkLiveness("STALL").simulate();
+    expect(sim.events.find((e: any) => e.name === "sentimentStalled")).to.be.undefined;
+
+    await program.methods.setExpectedInterval("LIVE", new anchor.BN(100)).rpc();
+    await program.methods.setExpectedInterval("STALL", new anchor.BN(100)).rpc();
+    // Only admins set the cadence; an operator could otherwise mute its own alarm
+    const operator = Keypair.generate();
+    await program.methods.addOperator(operator.publicKey, CAN_ALL).rpc();
+    try {
+      await program.methods
+        .setExpectedInterval("STALL", new anchor.BN(0))
+        .accounts({ authority: operator.publicKey } as any)
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Unauthorized");
+    }
+    await program.methods.removeOperator(operator.publicKey).rpc();
+    sim = await program.methods.checkLiveness("LIVE").simulate();
+    expect(sim.events.find((e: any) => e.name === "sentimentStalled")).to.be.undefined;
+
+    sim = await program.methods.checkLiveness("STALL").simulate();
+    const event = sim.events.find((e: any) => e.name === "sentimentStalled");
+    expect(event!.data.symbol).to.equal("STALL");
+    expect(event!.data.secondsOverdue.toNumber()).to.be.closeTo(900, 30);
+  });
+
+  it("trips the circuit breaker on an oversized score jump", async () => {
+    const symbol = "JUMP";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.setMaxScoreDelta(20).rpc();
+    // The first store has no prior value to compare against
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+
+    try {
+      await program.methods
+        .updateSentiment(50, 50, 100, new anchor.BN(base + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ScoreDeviationTooLarge");
+    }
+    await program.methods
+      .updateSentiment(30, 50, 100, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    await program.methods.setMaxScoreDelta(0).rpc();
+  });
+
+  it("applies the write guards and fee to contributions", async () => {
+    const symbol = "CONTRIB";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const treasury = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(treasury.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods.setTreasury(treasury.publicKey).rpc();
+    await program.methods
+      .registerSymbolConfig(symbol, {
+        activeHours: (1 << 24) - 1,
+        decimals: null,
+        scaleFactor: 100,
+        metadata: "",
+        writeFee: new anchor.BN(5000),
+      })
+      .rpc();
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods
+      .storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "")
+      .accounts({ treasury: treasury.publicKey } as any)
+      .rpc();
+    const contribute = (score: number, timestamp: number) =>
+      program.methods
+        .contributeSentiment(symbol, score, 50, 100, new anchor.BN(timestamp))
+        .accounts({ sentiment: sentimentPDA, treasury: treasury.publicKey } as any);
+
+    await program.methods.setUpdateInterval(new anchor.BN(60)).rpc();
+    try {
+      await contribute(12, base + 10).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UpdateTooFrequent");
+    }
+    await program.methods.setUpdateInterval(new anchor.BN(0)).rpc();
+
+    await program.methods.setMaxScoreDelta(20).rpc();
+    try {
+      await contribute(90, base + 20).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ScoreDeviationTooLarge");
+    }
+    await program.methods.setMaxScoreDelta(0).rpc();
+
+    await contribute(30, base + 30).rpc();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).score).to.equal(30);
+    expect(await provider.connection.getBalance(treasury.publicKey)).to.equal(1e9 + 10000);
+    await program.methods.setTreasury(authority.publicKey).rpc();
+  });
+
+  it("rate limits a writer within one window", async () => {
+    const symbol = "RATE";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.setOperatorRateLimit(new anchor.BN(3600), 3).rpc();
+
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    for (let i = 1; i < 3; i++) {
+      await program.methods
+        .updateSentiment(10 + i, 50, 100, new anchor.BN(base + i), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+    }
+
+    try {
+      await program.methods
+        .updateSentiment(20, 50, 100, new anchor.BN(base + 3), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorRateLimited");
+    }
+    // Batch entries count against the same window
+    try {
+      await program.methods
+        .batchUpdateSentiments([{ score: 20, confidence: 50, volume: 100, timestamp: new anchor.BN(base + 3) }])
+        .remainingAccounts([
+          { pubkey: sentimentPDA, isSigner: false, isWritable: true },
+          { pubkey: findPDA([SYMBOL_CONFIG_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: false },
+        ])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OperatorRateLimited");
+    }
+    const record = await program.account.sentimentRecord.fetch(sentimentPDA);
+    expect(record.score).to.equal(12);
+
+    await program.methods.setOperatorRateLimit(new anchor.BN(0), 0).rpc();
+  });
+
+  it("reports the next allowed update time", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const ts = await program.methods.nextUpdateTime("SOL").view();
+    // No interval configured: an update is allowed immediately
+    expect(ts.toNumber()).to.be.closeTo(now, 60);
+  });
+
+  it("records history snapshot", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+
+    await program.methods
+      .recordHistory(symbol, 24)
+      .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+      .rpc();
+
+    const history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.symbol).to.equal("SOL");
+    expect(history.count).to.equal(1);
+  });
+
+  it("computes a confidence-weighted TWAP over history", async () => {
+    const symbol = "TWAP";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const history = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+    await program.methods.storeSentiment(symbol, 20, 100, 1000, new anchor.BN(base), "").rpc();
+    await program.methods.recordHistory(symbol, 24).rpc();
+
+    try {
+      await program.methods
+        .computeTwap(symbol, new anchor.BN(3600), true)
+        .accounts({ history } as any)
+        .view();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientHistory");
+    }
+
+    await sleep(2500);
+    await program.methods
+      .updateSentiment(80, 0, 1000, new anchor.BN(base + 1), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    await program.methods.recordHistory(symbol, 24).rpc();
+    await sleep(1500);
+    await program.methods
+      .updateSentiment(50, 100, 1000, new anchor.BN(base + 2), null, "")
+      .accounts({ sentiment: sentimentPDA } as any)
+      .rpc();
+    await program.methods.recordHistory(symbol, 24).rpc();
+
+    // The zero-confidence reading carries no weight and the latest snapshot
+    // only closes the window
+    const weighted = await program.methods
+      .computeTwap(symbol, new anchor.BN(3600), true)
+      .accounts({ history } as any)
+      .view();
+    expect(weighted.toNumber()).to.equal(2000);
+    const timeOnly = await program.methods
+      .computeTwap(symbol, new anchor.BN(3600), false)
+      .accounts({ history } as any)
+      .view();
+    expect(timeOnly.toNumber()).to.be.greaterThan(2000);
+  });
+
+  it("pages through history via events", async () => {
+    const symbol = "PAGE";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 10, 50, 100, new anchor.BN(base), "").rpc();
+    await program.methods.recordHistory(symbol, 24).rpc();
+    for (const [i, score] of [20, 30].entries()) {
+      await program.methods
+        .updateSentiment(score, 50, 100, new anchor.BN(base + i + 1), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+      await program.methods.recordHistory(symbol, 24).rpc();
+    }
+
+    const page = async (start: number, limit: number) => {
+      const sim = await program.methods
+        .queryHistory(symbol, start, limit)
+        .accounts({ history: findPDA([HISTORY_SEED, Buffer.from(symbol)]) } as any)
+        .simulate();
+      return sim.events
+        .filter((e: any) => e.name === "historySnapshotEvent")
+        .map((e: any) => [e.data.position, e.data.score]);
+    };
+    // Newest two, oldest first
+    expect(await page(0, 2)).to.deep.equal([[1, 20], [0, 30]]);
+    // The limit stops at the oldest snapshot
+    expect(await page(2, 12)).to.deep.equal([[2, 10]]);
+
+    try {
+      await page(3, 1);
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidRange");
+    }
+  });
+
+  it("summarizes history across the ring buffer wraparound", async () => {
+    const symbol = "HSTAT";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    const stats = async () => {
+      const sim = await program.methods
+        .computeHistoryStats(symbol)
+        .accounts({ history: findPDA([HISTORY_SEED, Buffer.from(symbol)]) } as any)
+        .simulate();
+      return sim.events.find((e: any) => e.name === "historyStats")!.data;
+    };
+
+    const readings = [
+      { score: -40, volume: 900 },
+      { score: 10, volume: 300 },
+      { score: 30, volume: 500 },
+      { score: -20, volume: 200 },
+    ];
+    for (const [i, { score, volume }] of readings.entries()) {
+      if (i === 0) {
+        await program.methods.storeSentiment(symbol, score, 50, volume, new anchor.BN(base), "").rpc();
+      } else {
+        await program.methods
+          .updateSentiment(score, 50, volume, new anchor.BN(base + i), null, "")
+          .accounts({ sentiment: sentimentPDA } as any)
+          .rpc();
+      }
+      await program.methods.recordHistory(symbol, 3).rpc();
+
+      if (i === 0) {
+        // A single snapshot is its own min, max and mean
+        const one = await stats();
+        expect([one.minScore, one.maxScore, one.avgScore]).to.deep.equal([-40, -40, -40]);
+      }
+    }
+
+    // The fourth snapshot overwrote the first (-40, 900)
+    const s = await stats();
+    expect(s.minScore).to.equal(-20);
+    expect(s.maxScore).to.equal(30);
+    expect(s.avgScore).to.equal(6); // 20 / 3
+    expect(s.minVol).to.equal(200);
+    expect(s.maxVol).to.equal(500);
+  });
+
+  it("reads a renamed symbol's history through its alias", async () => {
+    const [oldSymbol, newSymbol] = ["RENA", "RENB"];
+    const oldHistory = findPDA([HISTORY_SEED, Buffer.from(oldSymbol)]);
+    await program.methods.registerSymbol(oldSymbol, Keypair.generate().publicKey).rpc();
+    await program.methods
+      .storeSentiment(oldSymbol, 42, 60, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+    await program.methods.recordHistory(oldSymbol, 24).rpc();
+
+    await program.methods.aliasSymbol(oldSymbol, newSymbol).rpc();
+    const sim = await program.methods
+      .queryHistory(newSymbol, 0, 12)
+      .accounts({ history: oldHistory } as any)
+      .simulate();
+    const scores = sim.events
+      .filter((e: any) => e.name === "historySnapshotEvent")
+      .map((e: any) => e.data.score);
+    expect(scores).to.deep.equal([42]);
+    const alias = await program.account.symbolAlias.fetch(findPDA([SYMBOL_ALIAS_SEED, Buffer.from(newSymbol)]));
+    expect(alias.target).to.equal(oldSymbol);
+
+    // Record reads resolve the alias too
+    const view = await program.methods
+      .getSentiment(newSymbol, new anchor.BN(3600))
+      .accounts(readAccounts(newSymbol, oldSymbol))
+      .view();
+    expect(view.score).to.equal(42);
+
+    // Aliases never point at themselves
+    try {
+      await program.methods.aliasSymbol(oldSymbol, oldSymbol).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AliasCycle");
+    }
+    // A registered symbol, an alias target or a symbol with its own record
+    // can't become an alias
+    await program.methods.registerSymbol("RENC", Keypair.generate().publicKey).rpc();
+    await program.methods
+      .storeSentiment("REND", 10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+    for (const [from, to] of [[oldSymbol, "RENC"], ["RENC", oldSymbol], [oldSymbol, "REND"]]) {
+      try {
+        await program.methods.aliasSymbol(from, to).rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("SymbolInUse");
+      }
+    }
+    // Only registered symbols can be targets, so an alias can't be one
+    try {
+      await program.methods.aliasSymbol(newSymbol, "RENE").rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("corrects a history entry without breaking chronology", async () => {
+    const symbol = "TWAP";
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    const before = await program.account.sentimentHistory.fetch(historyPDA);
+    const oldest = before.snapshots[0];
+
+    try {
+      await program.methods
+        .correctHistoryEntry(symbol, 0, { ...oldest, recordedAt: oldest.recordedAt.addn(3600) })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("WouldBreakChronology");
+    }
+
+    await program.methods.correctHistoryEntry(symbol, 0, { ...oldest, score: 25 }).rpc();
+    const after = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(after.snapshots[0].score).to.equal(25);
+    expect(after.snapshots[1].score).to.equal(before.snapshots[1].score);
+  });
+
+  it("keeps head and count consistent across the ring buffer wraparound", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+
+    // Fill up to exactly MAX_HISTORY (24) entries
+    for (let i = 0; i < 23; i++) {
+      await program.methods
+        .recordHistory(symbol, 24)
+        .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+        .rpc();
+    }
+
+    let history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.count).to.equal(24);
+    expect(history.head).to.equal(0); // head is stored modulo MAX_HISTORY
+
+    // One more wraps around and overwrites the oldest slot
+    await program.methods
+      .recordHistory(symbol, 24)
+      .accounts({ sentiment: sentimentPDA, history: historyPDA } as any)
+      .rpc();
+
+    history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.count).to.equal(24);
+    expect(history.head).to.equal(1);
+    expect(history.snapshots).to.have.length(24);
+    expect(history.snapshots[0].recordedAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("normalizes a head that counted past the u16 boundary", async () => {
+    // Fixture written by the old code: head reached 65535, slot 15 holds the oldest entry
+    const symbol = "WRAP";
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    let history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.head).to.equal(65535);
+
+    await program.methods.storeSentiment(symbol, 99, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "").rpc();
+    await program.methods.recordHistory(symbol, 24).rpc();
+
+    history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.head).to.equal(16); // 65535 % 24, then one write
+    expect(history.count).to.equal(24);
+    expect(history.snapshots[15].score).to.equal(99); // overwrote the oldest
+    expect(history.snapshots[16].score).to.equal(1); // now the oldest
+    expect(history.snapshots[14].score).to.equal(23);
+  });
+
+  it("sizes a history buffer to the capacity chosen at creation", async () => {
+    const symbol = "DEEP";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 0, 50, 100, new anchor.BN(base), "").rpc();
+
+    try {
+      await program.methods.recordHistory(symbol, 0).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidHistoryCapacity");
+    }
+
+    for (let i = 0; i < 30; i++) {
+      if (i > 0) {
+        await program.methods
+          .updateSentiment(i, 50, 100, new anchor.BN(base + i), null, "")
+          .accounts({ sentiment: sentimentPDA } as any)
+          .rpc();
+      }
+      await program.methods.recordHistory(symbol, 48).rpc();
+    }
+
+    const history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.capacity).to.equal(48);
+    expect(history.snapshots).to.have.length(48);
+    expect(history.count).to.equal(30);
+    expect(history.head).to.equal(30);
+    for (let i = 0; i < 30; i++) {
+      expect(history.snapshots[i].score).to.equal(i);
+    }
+  });
+
+  it("leaves a consistent history untouched on repair", async () => {
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from("SOL")]);
+    const before = await program.account.sentimentHistory.fetch(historyPDA);
+
+    await program.methods.repairHistory("SOL").rpc();
+
+    const after = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(after.count).to.equal(before.count);
+    expect(after.head).to.equal(before.head);
+  });
+
+  it("rebuilds a history whose snapshots fell short of its capacity", async () => {
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from("BROKE")]);
+    const before = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(before.snapshots).to.have.length(20);
+    expect(before.head).to.equal(5);
+
+    const sim = await program.methods.repairHistory("BROKE").simulate();
+    const repaired = sim.events.find((e: any) => e.name === "historyRepaired");
+    expect(repaired!.data.recoveredEntries).to.equal(20);
+
+    await program.methods.repairHistory("BROKE").rpc();
+
+    const after = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(after.snapshots).to.have.length(24);
+    expect(after.count).to.equal(20);
+    expect(after.head).to.equal(20);
+    for (let i = 0; i < 20; i++) {
+      expect(after.snapshots[i].score).to.equal(i);
+    }
+  });
+
+  it("migrates a history created before the capacity field", async () => {
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from("OLDH")]);
+    expect((await provider.connection.getAccountInfo(historyPDA))!.data.length).to.equal(559);
+
+    const sim = await program.methods.migrateHistory("OLDH").simulate();
+    const event = sim.events.find((e: any) => e.name === "historyMigrated");
+    expect(event!.data.oldLen).to.equal(559);
+    expect(event!.data.newLen).to.equal(561);
+    await program.methods.migrateHistory("OLDH").rpc();
+
+    const history = await program.account.sentimentHistory.fetch(historyPDA);
+    expect(history.symbol).to.equal("OLDH");
+    expect(history.capacity).to.equal(24);
+    expect(history.count).to.equal(3);
+    expect(history.head).to.equal(3);
+    expect(history.snapshots).to.have.length(24);
+    for (let i = 0; i < 3; i++) {
+      expect(history.snapshots[i].score).to.equal(7 + i);
+    }
+    // A migrated history is refused a second time
+    try {
+      await program.methods.migrateHistory("OLDH").rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("HistoryAlreadyMigrated");
+    }
+    // Repair now decodes it and finds it consistent
+    await program.methods.repairHistory("OLDH").rpc();
+  });
+
+  it("grows a record and a profile created in the baseline layouts", async () => {
+    const recordPDA = findPDA([SENTIMENT_SEED, Buffer.from("LEGACY")]);
+    expect((await provider.connection.getAccountInfo(recordPDA))!.data.length).to.equal(73);
+    const sim = await program.methods.migrateAccount().accounts({ account: recordPDA } as any).simulate();
+    const event = sim.events.find((e: any) => e.name === "accountMigrated");
+    expect(event!.data.oldLen).to.equal(73);
+    await program.methods.migrateAccount().accounts({ account: recordPDA } as any).rpc();
+    expect((await provider.connection.getAccountInfo(recordPDA))!.data.length).to.equal(event!.data.newLen);
+
+    const record = await program.account.sentimentRecord.fetch(recordPDA);
+    expect(record.symbol).to.equal("LEGACY");
+    expect([record.score, record.confidence, record.volume]).to.deep.equal([42, 70, 1234]);
+    expect(record.timestamp.toNumber()).to.equal(1_700_000_000);
+    expect(record.updateCount).to.equal(3);
+    expect(record.scoreBps).to.equal(4200);
+    expect(record.adjustedScore).to.equal(29);
+    expect(record.note).to.equal("");
+    expect(record.contributors).to.deep.equal([]);
+    const [, bump] = PublicKey.findProgramAddressSync([SENTIMENT_SEED, Buffer.from("LEGACY")], program.programId);
+    expect(record.bump).to.equal(bump);
+    try {
+      await program.methods.migrateAccount().accounts({ account: recordPDA } as any).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("LayoutAlreadyCurrent");
+    }
+
+    // Matches the profile fixture loaded from tests/fixtures/v1_profile.json
+    const owner = Keypair.fromSeed(Buffer.from("baseline-profile-owner-fixture01"));
+    const profilePDA = findPDA([USER_PROFILE_SEED, owner.publicKey.toBuffer()]);
+    await program.methods.migrateAccount().accounts({ account: profilePDA } as any).rpc();
+    const profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.username).to.equal("pioneer");
+    expect([profile.predictionsMade, profile.correctPredictions, profile.reputation]).to.deep.equal([9, 6, 140]);
+    expect(profile.lastActive.toNumber()).to.equal(1_700_000_060);
+    expect(profile.openPredictions).to.equal(0);
+    expect(profile.followerCount).to.equal(0);
+
+    // The sentinel is not an account this can grow
+    try {
+      await program.methods.migrateAccount().accounts({ account: findPDA([SENTINEL_SEED]) } as any).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnsupportedLayout");
+    }
+  });
+
+  it("pauses and snapshots several symbols atomically", async () => {
+    const symbols = ["SOL", "CAPD"];
+    const remaining = symbols.flatMap((symbol) => [
+      { pubkey: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: false },
+      { pubkey: findPDA([HISTORY_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: true },
+    ]);
+
+    await program.methods.pauseAndSnapshot(symbols).remainingAccounts(remaining).rpc();
+
+    const sentinel = await program.account.sentinel.fetch(findPDA([SENTINEL_SEED]));
+    expect(sentinel.pauseFlags).to.equal(0b0011);
+    const fresh = await program.account.sentimentHistory.fetch(
+      findPDA([HISTORY_SEED, Buffer.from("CAPD")])
+    );
+    expect(fresh.symbol).to.equal("CAPD");
+    expect(fresh.count).to.equal(1);
+
+    await program.methods.setPaused(false).rpc();
+  });
+
+  it("records history for several symbols in one transaction", async () => {
+    const symbols = ["SOL", "OLD"];
+    const remaining = symbols.flatMap((symbol) => [
+      { pubkey: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: false },
+      { pubkey: findPDA([HISTORY_SEED, Buffer.from(symbol)]), isSigner: false, isWritable: true },
+    ]);
+    const solHistoryPDA = findPDA([HISTORY_SEED, Buffer.from("SOL")]);
+    const before = await program.account.sentimentHistory.fetch(solHistoryPDA);
+
+    const sim = await program.methods.batchRecordHistory(symbols).remainingAccounts(remaining).simulate();
+    const event = sim.events.find((e: any) => e.name === "batchHistoryRecorded");
+    expect(event!.data.count).to.equal(2);
+    await program.methods.batchRecordHistory(symbols).remainingAccounts(remaining).rpc();
+
+    const sol = await program.account.sentimentHistory.fetch(solHistoryPDA);
+    expect(sol.head).to.equal((before.head + 1) % before.capacity);
+    const fresh = await program.account.sentimentHistory.fetch(
+      findPDA([HISTORY_SEED, Buffer.from("OLD")])
+    );
+    expect(fresh.count).to.equal(1);
+
+    // Pairs must be the canonical PDAs for their symbol
+    try {
+      await program.methods
+        .batchRecordHistory(["SOL"])
+        .remainingAccounts([remaining[0], remaining[3]])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidAccount");
+    }
+
+    // A history address someone already sent lamports to is still created
+    const funded = "FUND";
+    await program.methods.storeSentiment(funded, 10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "").rpc();
+    const fundedHistory = findPDA([HISTORY_SEED, Buffer.from(funded)]);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: authority.publicKey, toPubkey: fundedHistory, lamports: 1_000_000 })
+      )
+    );
+    const fundedPair = [
+      { pubkey: findPDA([SENTIMENT_SEED, Buffer.from(funded)]), isSigner: false, isWritable: false },
+      { pubkey: fundedHistory, isSigner: false, isWritable: true },
+    ];
+    await program.methods.batchRecordHistory([funded]).remainingAccounts(fundedPair).rpc();
+    expect((await program.account.sentimentHistory.fetch(fundedHistory)).count).to.equal(1);
+
+    await program.methods.setPauseFlags(0b0001).rpc(); // PAUSE_STORE
+    try {
+      await program.methods.batchRecordHistory([funded]).remainingAccounts(fundedPair).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("OraclePaused");
+    }
+    await program.methods.setPauseFlags(0).rpc();
+  });
+
+  it("records delta-encoded history with a leading keyframe", async () => {
+    const symbol = "SOL";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const deltaPDA = findPDA([DELTA_HISTORY_SEED, Buffer.from(symbol)]);
+
+    for (let i = 0; i < 3; i++) {
+      await program.methods
+        .recordDeltaHistory(symbol)
+        .accounts({ sentiment: sentimentPDA, history: deltaPDA } as any)
+        .rpc();
+    }
+
+    const history = await program.account.deltaHistory.fetch(deltaPDA);
+    expect(history.count).to.equal(3);
+    expect(history.kfCount).to.equal(1);
+    expect(history.slots[0].keyframe).to.equal(true);
+    expect(history.slots[1].keyframe).to.equal(false);
+    expect(history.slots[1].score).to.equal(0);
+    expect(history.keyframes[0].score).to.equal(history.last.score);
+  });
+
+  it("decodes delta history back to the recorded snapshots", async () => {
+    const symbol = "PACK";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const deltaPDA = findPDA([DELTA_HISTORY_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    // The +1000 volume step overflows a byte; +198,895 overflows a delta and forces a keyframe
+    const readings = [
+      [10, 50, 100],
+      [12, 55, 1100],
+      [9, 52, 1105],
+      [-20, 80, 200000],
+    ];
+
+    for (const [i, [score, confidence, volume]] of readings.entries()) {
+      const ts = new anchor.BN(base + i);
+      if (i === 0) {
+        await program.methods.storeSentiment(symbol, score, confidence, volume, ts, "").rpc();
+      } else {
+        await program.methods
+          .updateSentiment(score, confidence, volume, ts, null, "")
+          .accounts({ sentiment: sentimentPDA } as any)
+          .rpc();
+      }
+      await program.methods
+        .recordDeltaHistory(symbol)
+        .accounts({ sentiment: sentimentPDA, history: deltaPDA } as any)
+        .rpc();
+    }
+    expect((await program.account.deltaHistory.fetch(deltaPDA)).kfCount).to.equal(2);
+
+    const sim = await program.methods.decodeDeltaHistory(symbol, 0, 12).simulate();
+    const decoded = sim.events
+      .filter((e: any) => e.name === "historySnapshotEvent")
+      .map((e: any) => [e.data.score, e.data.confidence, e.data.volume, e.data.timestamp.toNumber()]);
+    expect(decoded).to.deep.equal(readings.map(([s, c, v], i) => [s, c, v, base + i]));
+  });
+
+  it("round-trips compact history through varint deltas", async () => {
+    const symbol = "PAKD";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const compactPDA = findPDA([COMPACT_HISTORY_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    // +1000 needs a two-byte varint, -199,900 a three-byte one; the score swing wraps an i8 delta
+    const readings = [
+      [10, 50, 100],
+      [12, 55, 1100],
+      [-90, 5, 200000],
+      [100, 100, 100],
+    ];
+
+    for (const [i, [score, confidence, volume]] of readings.entries()) {
+      const ts = new anchor.BN(base + i);
+      if (i === 0) {
+        await program.methods.storeSentiment(symbol, score, confidence, volume, ts, "").rpc();
+      } else {
+        await program.methods
+          .updateSentiment(score, confidence, volume, ts, null, "")
+          .accounts({ sentiment: sentimentPDA } as any)
+          .rpc();
+      }
+      await program.methods
+        .recordCompactHistory(symbol)
+        .accounts({ sentiment: sentimentPDA, history: compactPDA } as any)
+        .rpc();
+    }
+
+    const history = await program.account.compactHistory.fetch(compactPDA);
+    expect(history.count).to.equal(4);
+    expect(history.base.volume).to.equal(100);
+    // First delta: score and confidence bytes, then +1000 as a two-byte varint and the timestamp step
+    expect(Buffer.from(history.data).subarray(2, 5)).to.deep.equal(Buffer.from([0xd0, 0x0f, 0x02]));
+
+    const sim = await program.methods.decodeCompactHistory(symbol, 0, 12).simulate();
+    const decoded = sim.events
+      .filter((e: any) => e.name === "historySnapshotEvent")
+      .map((e: any) => [e.data.score, e.data.confidence, e.data.volume, e.data.timestamp.toNumber()]);
+    expect(decoded).to.deep.equal(readings.map(([s, c, v], i) => [s, c, v, base + i]));
+
+    // Output is capped per call even when more is stored
+    for (let i = 0; i < 10; i++) {
+      await program.methods
+        .recordCompactHistory(symbol)
+        .accounts({ sentiment: sentimentPDA, history: compactPDA } as any)
+        .rpc();
+    }
+    const capped = await program.methods.decodeCompactHistory(symbol, 0, 50).simulate();
+    const page = capped.events.filter((e: any) => e.name === "historySnapshotEvent");
+    expect(page.length).to.equal(12);
+    expect(page[page.length - 1].data.position).to.equal(0);
+    expect(page[page.length - 1].data.volume).to.equal(100);
+  });
+
+  // ===== User Profile =====
+
+  it("creates a user profile", async () => {
+    await program.methods
+      .createProfile("oracle_admin")
+      .accounts({ usernameClaim: claimPDA("oracle_admin") } as any)
+      .rpc();
+
+    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
+    const profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.username).to.equal("oracle_admin");
+    expect(profile.reputation).to.equal(100);
+    expect(profile.predictionsMade).to.equal(0);
+  });
+
+  it("rejects empty username", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .createProfile("")
+        .accounts({ user: user.publicKey, usernameClaim: claimPDA("") } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("EmptyUsername");
+    }
+  });
+
+  it("rejects a username that is already claimed, ignoring case", async () => {
+    const [first, second] = [Keypair.generate(), Keypair.generate()];
+    for (const kp of [first, second]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+    const create = (kp: Keypair, username: string) =>
+      program.methods
+        .createProfile(username)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(username) } as any)
+        .signers([kp])
+        .rpc();
+
+    await create(first, "Whale_Watcher");
+    const claim = await program.account.usernameClaim.fetch(claimPDA("whale_watcher"));
+    expect(claim.owner.equals(first.publicKey)).to.equal(true);
+    expect(claim.username).to.equal("Whale_Watcher");
+
+    for (const username of ["Whale_Watcher", "WHALE_watcher"]) {
+      try {
+        await create(second, username);
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        // The claim PDA already exists
+        expect(e.toString()).to.not.include("Should have thrown");
+      }
+    }
+    const secondPDA = findPDA([USER_PROFILE_SEED, second.publicKey.toBuffer()]);
+    expect(await program.account.userProfile.fetchNullable(secondPDA)).to.be.null;
+  });
+
+  // ===== Subscriptions =====
+
+  it("subscribes and unsubscribes from a token", async () => {
+    const symbol = "SOL";
+    const subPDA = findPDA([
+      SUBSCRIPTION_SEED,
+      authority.publicKey.toBuffer(),
+      Buffer.from(symbol),
+    ]);
+
+    await program.methods.subscribeToken(symbol, 1, 50, 0, new anchor.BN(0)).rpc();
+
+    const sub = await program.account.subscription.fetch(subPDA);
+    expect(sub.symbol).to.equal("SOL");
+    expect(sub.direction).to.equal(1);
+    expect(sub.alertThreshold).to.equal(50);
+
+    await program.methods
+      .unsubscribeToken()
+      .accounts({ subscription: subPDA } as any)
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(subPDA);
+    expect(info).to.be.null;
+  });
+
+  it("triggers score and volume alerts with a cooldown", async () => {
+    const symbol = "ALRT";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
+    const base = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment(symbol, 60, 80, 500, new anchor.BN(base), "").rpc();
+    const update = (score: number, offset: number) =>
+      program.methods
+        .updateSentiment(score, 80, 500, new anchor.BN(base + offset), null, "")
+        .accounts({ sentiment: sentimentPDA } as any)
+        .rpc();
+
+    // Score-only: bullish at 50+, volume trigger off
+    const scoreSub = findPDA([SUBSCRIPTION_SEED, authority.publicKey.toBuffer(), Buffer.from(symbol)]);
+    await program.methods.subscribeToken(symbol, 1, 50, 0, new anchor.BN(3600)).rpc();
+    let sim = await program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any)
+      .simulate();
+    let event = sim.events.find((e: any) => e.name === "alertTriggered");
+    expect(event).to.exist;
+    expect(event.data.reason).to.equal(1);
+
+    await program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any)
+      .rpc();
+    expect((await program.account.subscription.fetch(scoreSub)).lastAlert.toNumber()).to.be.greaterThan(0);
+    // A reading below the threshold is not an alert, so nothing is suppressed
+    await update(45, 1);
+    sim = await program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any)
+      .simulate();
+    expect(sim.events.find((e: any) => e.name === "alertTriggered")).to.be.undefined;
+    expect(sim.events.find((e: any) => e.name === "alertSuppressed")).to.be.undefined;
+
+    // A triggering reading while still cooling down is suppressed
+    await update(62, 2);
+    sim = await program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any)
+      .simulate();
+    expect(sim.events.find((e: any) => e.name === "alertTriggered")).to.be.undefined;
+    const suppressed = sim.events.find((e: any) => e.name === "alertSuppressed");
+    expect(suppressed).to.exist;
+    expect(suppressed.data.symbol).to.equal(symbol);
+    expect(suppressed.data.secondsRemaining.toNumber()).to.be.within(1, 3600);
+
+    // Dropping the cooldown lets the suppressed reading fire, but only once
+    await program.methods
+      .setCooldown(new anchor.BN(0))
+      .accounts({ subscription: scoreSub } as any)
+      .rpc();
+    expect((await program.account.subscription.fetch(scoreSub)).cooldown.toNumber()).to.equal(0);
+    const check = program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any);
+    sim = await check.simulate();
+    expect(sim.events.find((e: any) => e.name === "alertTriggered")).to.exist;
+    expect(sim.events.find((e: any) => e.name === "alertSuppressed")).to.be.undefined;
+    await check.rpc();
+    sim = await program.methods
+      .checkAlert()
+      .accounts({ subscription: scoreSub, sentiment: sentimentPDA } as any)
+      .simulate();
+    expect(sim.events.find((e: any) => e.name === "alertTriggered")).to.be.undefined;
+
+    try {
+      await program.methods
+        .setCooldown(new anchor.BN(-1))
+        .accounts({ subscription: scoreSub } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidCooldown");
+    }
+
+    // Volume-only: bearish direction never matches, volume 500 >= 400 does
+    const trader = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(trader.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const volumeSub = findPDA([SUBSCRIPTION_SEED, trader.publicKey.toBuffer(), Buffer.from(symbol)]);
+    await program.methods
+      .subscribeToken(symbol, -1, 0, 400, new anchor.BN(0))
+      .accounts({ user: trader.publicKey } as any)
+      .signers([trader])
+      .rpc();
+    sim = await program.methods
+      .checkAlert()
+      .accounts({ subscription: volumeSub, sentiment: sentimentPDA } as any)
+      .simulate();
+    event = sim.events.find((e: any) => e.name === "alertTriggered");
+    expect(event).to.exist;
+    expect(event.data.reason).to.equal(2);
+    expect(event.data.user.toBase58()).to.equal(trader.publicKey.toBase58());
+  });
+
+  it("keeps many watched symbols in one subscription list", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const listPDA = findPDA([SUB_LIST_SEED, user.publicKey.toBuffer()]);
+    const watch = (symbol: string) =>
+      program.methods.addWatch(symbol, 1, 50).accounts({ user: user.publicKey } as any).signers([user]).rpc();
+
+    await watch("SOL");
+    await watch("BONK");
+    const update = program.methods
+      .updateWatch("BONK", -1, 70)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user]);
+    const updated = (await update.simulate()).events.find((e: any) => e.name === "watchUpdated");
+    expect(updated!.data.threshold).to.equal(70);
+    await update.rpc();
+    let list = await program.account.subscriptionList.fetch(listPDA);
+    expect(list.subs.map((s: any) => s.symbol)).to.deep.equal(["SOL", "BONK"]);
+    expect(list.subs[1].direction).to.equal(-1);
+    expect(list.subs[1].threshold).to.equal(70);
+
+    const sizeBefore = (await provider.connection.getAccountInfo(listPDA))!.data.length;
+    await program.methods
+      .removeWatch("SOL")
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+    list = await program.account.subscriptionList.fetch(listPDA);
+    expect(list.subs.map((s: any) => s.symbol)).to.deep.equal(["BONK"]);
+    expect((await provider.connection.getAccountInfo(listPDA))!.data.length).to.be.lessThan(sizeBefore);
+
+    try {
+      await watch("BONK");
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("WatchAlreadyExists");
+    }
+
+    for (let i = 1; i < 20; i++) {
+      await watch(`W${i}`);
+    }
+    try {
+      await watch("W20");
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("TooManySubscriptions");
+    }
+
+    // Emptying the list closes it
+    for (const symbol of ["BONK", ...Array.from({ length: 19 }, (_, i) => `W${i + 1}`)]) {
+      await program.methods.removeWatch(symbol).accounts({ user: user.publicKey } as any).signers([user]).rpc();
+    }
+    expect(await provider.connection.getAccountInfo(listPDA)).to.be.null;
+  });
+
+  // ===== Community Voting =====
+
+  it("casts a community vote", async () => {
+    const symbol = "SOL";
+    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
+    const votePDA = findPDA([VOTE_SEED, authority.publicKey.toBuffer(), Buffer.from(symbol)]);
+
+    const sim = await program.methods.voteSentiment(symbol, 80, 70).simulate();
+    const consensus = sim.events.find((e: any) => e.name === "consensusUpdated");
+    expect(consensus!.data.consensusScore.toNumber()).to.equal(8000);
+    expect(consensus!.data.voteCount).to.equal(1);
+
+    await program.methods
+      .voteSentiment(symbol, 80, 70)
+      .rpc();
+
+    const vote = await program.account.communityVote.fetch(votePDA);
+    expect(vote.score).to.equal(80);
+    expect(vote.confidence).to.equal(70);
+
+    const profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.predictionsMade).to.equal(1);
+  });
+
+  it("votes and subscribes in a single transaction", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("onboarder")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("onboarder") } as any)
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .storeAndSubscribe("COMBO", 40, 70, 1, 30)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const vote = await program.account.communityVote.fetch(
+      findPDA([VOTE_SEED, user.publicKey.toBuffer(), Buffer.from("COMBO")])
+    );
+    expect(vote.score).to.equal(40);
+    const subscription = await program.account.subscription.fetch(
+      findPDA([SUBSCRIPTION_SEED, user.publicKey.toBuffer(), Buffer.from("COMBO")])
+    );
+    expect(subscription.direction).to.equal(1);
+    expect(subscription.alertThreshold).to.equal(30);
+
+    try {
+      await program.methods
+        .storeAndSubscribe("COMBO2", 40, 70, 5, 30)
+        .accounts({ user: user.publicKey } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidDirection");
+    }
+    const orphan = findPDA([VOTE_SEED, user.publicKey.toBuffer(), Buffer.from("COMBO2")]);
+    expect(await provider.connection.getAccountInfo(orphan)).to.be.null;
+  });
+
+  it("gates votes on untracked symbols when configured", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("untracked")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("untracked") } as any)
+      .signers([user])
+      .rpc();
+
+    // Open voting by default
+    await program.methods
+      .voteSentiment("NOFEED", 10, 50)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    await program.methods.setRequireTrackedSymbol(true).rpc();
+    try {
+      await program.methods
+        .voteSentiment("NOFEED2", 10, 50)
+        .accounts({ user: user.publicKey } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("SymbolNotTracked");
+    }
+    await program.methods
+      .voteSentiment("VOLA", 10, 50)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+    await program.methods.setRequireTrackedSymbol(false).rpc();
+  });
+
+  it("batch-reads community aggregates", async () => {
+    const symbols = ["SOL", "NOVOTE"];
+    const sim = await program.methods
+      .readAggregates(symbols)
+      .remainingAccounts(
+        symbols.map((s) => ({
+          pubkey: findPDA([VOTE_AGG_SEED, Buffer.from(s)]),
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "aggregatesRead");
+    const [sol, none] = event!.data.entries;
+    expect(sol.voteCount).to.equal(1);
+    expect(sol.meanScore).to.equal(80);
+    expect(sol.confidence).to.equal(70);
+    expect(none.voteCount).to.equal(0);
+    expect(none.meanScore).to.equal(0);
+  });
+
+  it("blends the oracle score with the community consensus", async () => {
+    const symbol = "BLEND";
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const ts = new anchor.BN(Math.floor(Date.now() / 1000));
+    await program.methods.storeSentiment(symbol, 20, 60, 100, ts, "").rpc();
+    await program.methods.storeSentiment("BLEND0", -30, 60, 100, ts, "").rpc();
+    await program.methods
+      .createProfile("blender")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("blender") } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .voteSentiment(symbol, 80, 50)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const publish = async (s: string, weight: number) => {
+      await program.methods.setCommunityWeight(weight).rpc();
+      const sim = await program.methods.publishBlended(s).simulate();
+      await program.methods.publishBlended(s).rpc();
+      return sim.events.find((e: any) => e.name === "blendedPublished")!.data.blendedScore;
+    };
+    expect(await publish(symbol, 0)).to.equal(20);
+    expect(await publish(symbol, 50)).to.equal(50);
+    expect(await publish(symbol, 100)).to.equal(80);
+    const record = await program.account.sentimentRecord.fetch(findPDA([SENTIMENT_SEED, Buffer.from(symbol)]));
+    expect(record.blendedScore).to.equal(80);
+
+    // No community votes: the oracle score stands whatever the weight
+    expect(await publish("BLEND0", 100)).to.equal(-30);
+
+    await program.methods.setCommunityWeight(0).rpc();
+  });
+
+  it("rejects votes from profiles younger than the minimum age", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods.setMinProfileAge(new anchor.BN(3600)).rpc();
+    await program.methods
+      .createProfile("newbie")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("newbie") } as any)
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .voteSentiment("SOL", 50, 50)
+        .accounts({ user: user.publicKey } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ProfileTooNew");
+    }
+
+    await program.methods.setMinProfileAge(new anchor.BN(0)).rpc();
+  });
+
+  // ===== Resolve Prediction =====
+
+  it("resolves a prediction and adjusts reputation", async () => {
+    const profilePDA = findPDA([USER_PROFILE_SEED, authority.publicKey.toBuffer()]);
+
+    await program.methods
+      .judgePrediction(true)
+      .accounts({ profile: profilePDA } as any)
+      .rpc();
+
+    let profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.correctPredictions).to.equal(1);
+    expect(profile.reputation).to.equal(110);
+
+    await program.methods
+      .judgePrediction(false)
+      .accounts({ profile: profilePDA } as any)
+      .rpc();
+
+    profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.reputation).to.equal(105);
+  });
+
+  it("applies the configured reputation reward and cap", async () => {
+    const voter = Keypair.generate();
+    const profilePDA = findPDA([USER_PROFILE_SEED, voter.publicKey.toBuffer()]);
+    const sig = await provider.connection.requestAirdrop(voter.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("tuned")
+      .accounts({ user: voter.publicKey, usernameClaim: claimPDA("tuned") } as any)
+      .signers([voter])
+      .rpc();
+    const resolve = () => program.methods.judgePrediction(true).accounts({ profile: profilePDA } as any).rpc();
+
+    await program.methods.setRepParams(20, 5, 1000).rpc();
+    await resolve();
+    expect((await program.account.userProfile.fetch(profilePDA)).reputation).to.equal(120);
+
+    await program.methods.setRepParams(20, 5, 130).rpc();
+    await resolve();
+    expect((await program.account.userProfile.fetch(profilePDA)).reputation).to.equal(130);
+
+    // Lowering the cap below a profile's reputation doesn't claw it back
+    await program.methods.setRepParams(20, 5, 110).rpc();
+    await resolve();
+    expect((await program.account.userProfile.fetch(profilePDA)).reputation).to.equal(130);
+
+    await program.methods.setRepParams(10, 5, 1000).rpc();
+  });
+
+  it("resolves a prediction against the actual score", async () => {
+    const voter = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(voter.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("forecaster")
+      .accounts({ user: voter.publicKey, usernameClaim: claimPDA("forecaster") } as any)
+      .signers([voter])
+      .rpc();
+    await program.methods
+      .voteSentiment("ACC", 40, 80)
+      .accounts({ user: voter.publicKey } as any)
+      .signers([voter])
+      .rpc();
+
+    const profilePDA = findPDA([USER_PROFILE_SEED, voter.publicKey.toBuffer()]);
+    const votePDA = findPDA([VOTE_SEED, voter.publicKey.toBuffer(), Buffer.from("ACC")]);
+    const resolve = program.methods
+      .resolvePredictionAgainst("ACC", 48)
+      .accounts({ profile: profilePDA, vote: votePDA } as any);
+    const sim = await resolve.simulate();
+    const event = sim.events.find((e: any) => e.name === "predictionScored");
+    expect(event!.data.error).to.equal(8);
+    expect(event!.data.tolerance).to.equal(10);
+    await resolve.rpc();
+
+    const vote = await program.account.communityVote.fetch(votePDA);
+    expect(vote.correct).to.equal(true);
+    const profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.correctPredictions).to.equal(1);
+    expect(profile.reputation).to.equal(110);
+  });
+
+  it("settles a target prediction against the live score once mature", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("targeter")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("targeter") } as any)
+      .signers([user])
+      .rpc();
+
+    // ADJ last read 33
+    const resolveAfter = Math.floor(Date.now() / 1000) + 2;
+    await program.methods
+      .openPrediction("ADJ", 30, new anchor.BN(resolveAfter))
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const predictionPDA = findPDA([PREDICTION_SEED, user.publicKey.toBuffer(), Buffer.from("ADJ")]);
+    const resolve = program.methods
+      .resolvePrediction()
+      .accounts({ prediction: predictionPDA, owner: user.publicKey } as any);
+    try {
+      await resolve.rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("PredictionNotMature");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+    // matured, but ADJ hasn't been read since: the stale score can't settle it
+    try {
+      await resolve.rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NoMatureReading");
+    }
+
+    await program.methods
+      .updateSentiment(32, 75, 1000, new anchor.BN(Math.floor(Date.now() / 1000)), null, "")
+      .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from("ADJ")]) } as any)
+      .rpc();
+    const sim = await resolve.simulate();
+    const event = sim.events.find((e: any) => e.name === "predictionScored");
+    expect(event!.data.actual).to.equal(32);
+    expect(event!.data.expired).to.equal(false);
+    await resolve.rpc();
+
+    // settled predictions are closed to their owner
+    expect(await provider.connection.getAccountInfo(predictionPDA)).to.equal(null);
+    const profile = await program.account.userProfile.fetch(
+      findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()])
+    );
+    expect(profile.predictionsMade).to.equal(1);
+    expect(profile.openPredictions).to.equal(0);
+    expect(profile.reputation).to.equal(110);
+  });
+
+  it("reports per-symbol accuracy from resolved votes", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const profilePDA = findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()]);
+    const votePDA = findPDA([VOTE_SEED, user.publicKey.toBuffer(), Buffer.from("SOL")]);
+
+    await program.methods
+      .createProfile("accurate")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("accurate") } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .voteSentiment("SOL", 40, 80)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .judgePrediction(true)
+      .accounts({ profile: profilePDA, vote: votePDA } as any)
+      .rpc();
+
+    const sim = await program.methods
+      .symbolAccuracy(user.publicKey, "SOL")
+      .remainingAccounts([{ pubkey: votePDA, isSigner: false, isWritable: false }])
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "symbolAccuracy");
+    expect(event).to.exist;
+    expect(event!.data.correct).to.equal(1);
+    expect(event!.data.total).to.equal(1);
+  });
+
+  it("transfers a profile to a new wallet", async () => {
+    const oldWallet = Keypair.generate();
+    const newWallet = Keypair.generate();
+    const followed = Keypair.generate();
+    for (const kp of [oldWallet, newWallet, followed]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+    const oldPDA = findPDA([USER_PROFILE_SEED, oldWallet.publicKey.toBuffer()]);
+    const newPDA = findPDA([USER_PROFILE_SEED, newWallet.publicKey.toBuffer()]);
+
+    await program.methods
+      .createProfile("rotator")
+      .accounts({ user: oldWallet.publicKey, usernameClaim: claimPDA("rotator") } as any)
+      .signers([oldWallet])
+      .rpc();
+    await program.methods
+      .createProfile("rotated")
+      .accounts({ user: followed.publicKey, usernameClaim: claimPDA("rotated") } as any)
+      .signers([followed])
+      .rpc();
+    await program.methods
+      .followUser(followed.publicKey)
+      .accounts({ user: oldWallet.publicKey } as any)
+      .signers([oldWallet])
+      .rpc();
+    const before = await program.account.userProfile.fetch(oldPDA);
+
+    await program.methods
+      .transferProfile()
+      .accounts({
+        user: oldWallet.publicKey,
+        newOwner: newWallet.publicKey,
+        usernameClaim: claimPDA("rotator"),
+      } as any)
+      .signers([oldWallet, newWallet])
+      .rpc();
+
+    const after = await program.account.userProfile.fetch(newPDA);
+    expect(after.owner.toBase58()).to.equal(newWallet.publicKey.toBase58());
+    expect(after.username).to.equal("rotator");
+    expect(after.reputation).to.equal(before.reputation);
+    expect(after.createdAt.toNumber()).to.equal(before.createdAt.toNumber());
+    // Follow edges stay with the old wallet, so their counts don't carry over
+    expect(before.followingCount).to.equal(1);
+    expect(after.followingCount).to.equal(0);
+    expect(await provider.connection.getAccountInfo(oldPDA)).to.be.null;
+  });
+
+  it("decays an idle profile's reputation without marking it active", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("idler")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("idler") } as any)
+      .signers([user])
+      .rpc();
+    const profilePDA = findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()]);
+    const before = await program.account.userProfile.fetch(profilePDA);
+
+    // One-second periods stand in for a month of idling
+    await program.methods.setReputationDecay(new anchor.BN(1), 50).rpc();
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await program.methods.decayReputation().accounts({ profile: profilePDA } as any).rpc();
+
+    const after = await program.account.userProfile.fetch(profilePDA);
+    const idle = after.decayedThrough.toNumber() - before.lastActive.toNumber();
+    expect(idle).to.be.at.least(2);
+    expect(after.reputation).to.equal(before.reputation - idle);
+    expect(after.lastActive.toNumber()).to.equal(before.lastActive.toNumber());
+
+    await program.methods.setReputationDecay(new anchor.BN(30 * 86_400), 50).rpc();
+  });
+
+  it("follows and unfollows another profile", async () => {
+    const [fan, star] = [Keypair.generate(), Keypair.generate()];
+    for (const [i, kp] of [fan, star].entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`social${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`social${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    const fanPDA = findPDA([USER_PROFILE_SEED, fan.publicKey.toBuffer()]);
+    const starPDA = findPDA([USER_PROFILE_SEED, star.publicKey.toBuffer()]);
+    const followPDA = findPDA([FOLLOW_SEED, fan.publicKey.toBuffer(), star.publicKey.toBuffer()]);
+
+    await program.methods
+      .followUser(star.publicKey)
+      .accounts({ user: fan.publicKey } as any)
+      .signers([fan])
+      .rpc();
+    expect((await program.account.userProfile.fetch(fanPDA)).followingCount).to.equal(1);
+    expect((await program.account.userProfile.fetch(starPDA)).followerCount).to.equal(1);
+    const follow = await program.account.follow.fetch(followPDA);
+    expect(follow.followee.toBase58()).to.equal(star.publicKey.toBase58());
+
+    try {
+      await program.methods
+        .followUser(fan.publicKey)
+        .accounts({ user: fan.publicKey } as any)
+        .signers([fan])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("CannotFollowSelf");
+    }
+
+    try {
+      await program.methods
+        .followUser(star.publicKey)
+        .accounts({ user: fan.publicKey } as any)
+        .signers([fan])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      // The Follow PDA already exists
+      expect(e.toString()).to.not.include("Should have thrown");
+    }
+
+    await program.methods
+      .unfollowUser(star.publicKey)
+      .accounts({ user: fan.publicKey } as any)
+      .signers([fan])
+      .rpc();
+    expect((await program.account.userProfile.fetch(fanPDA)).followingCount).to.equal(0);
+    expect((await program.account.userProfile.fetch(starPDA)).followerCount).to.equal(0);
+    expect(await provider.connection.getAccountInfo(followPDA)).to.be.null;
+  });
+
+  it("onboards an operator through a community proposal", async () => {
+    const candidate = Keypair.generate();
+    const reviewers = [Keypair.generate(), Keypair.generate()];
+    const proposalPDA = findPDA([OPERATOR_PROPOSAL_SEED, candidate.publicKey.toBuffer()]);
+    const sentinelPDA = findPDA([SENTINEL_SEED]);
+
+    await program.methods.setProposalConfig(new anchor.BN(2), 100).rpc();
+    await program.methods.proposeOperator(candidate.publicKey).rpc();
+
+    for (const [i, kp] of reviewers.entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`reviewer${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`reviewer${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    await program.methods
+      .endorseOperator()
+      .accounts({ proposal: proposalPDA, user: reviewers[0].publicKey } as any)
+      .signers([reviewers[0]])
+      .rpc();
+    await program.methods
+      .endorseOperator()
+      .accounts({ proposal: proposalPDA, user: reviewers[1].publicKey } as any)
+      .signers([reviewers[1]])
+      .rpc();
+
+    try {
+      await program.methods
+        .objectOperator()
+        .accounts({ proposal: proposalPDA, user: reviewers[0].publicKey } as any)
+        .signers([reviewers[0]])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AlreadyVoted");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await program.methods
+      .finalizeOperator()
+      .accounts({ proposal: proposalPDA, proposer: authority.publicKey } as any)
+      .rpc();
+
+    const sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    expect(sentinel.operators.some((o: any) => o.key.equals(candidate.publicKey))).to.equal(true);
+    expect(await provider.connection.getAccountInfo(proposalPDA)).to.be.null;
+
+    await program.methods.removeOperator(candidate.publicKey).rpc();
+    await program.methods.setProposalConfig(new anchor.BN(86_400), 200).rpc();
+  });
+
+  it("closes a passing proposal as rejected when it can't be applied", async () => {
+    const candidate = Keypair.generate();
+    const proposalPDA = findPDA([OPERATOR_PROPOSAL_SEED, candidate.publicKey.toBuffer()]);
+    const reviewer = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(reviewer.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("latereviewer")
+      .accounts({ user: reviewer.publicKey, usernameClaim: claimPDA("latereviewer") } as any)
+      .signers([reviewer])
+      .rpc();
+
+    await program.methods.setProposalConfig(new anchor.BN(2), 100).rpc();
+    await program.methods.proposeOperator(candidate.publicKey).rpc();
+    await program.methods
+      .endorseOperator()
+      .accounts({ proposal: proposalPDA, user: reviewer.publicKey } as any)
+      .signers([reviewer])
+      .rpc();
+    // Added directly while the proposal was open, so finalizing can't add it again
+    await program.methods.addOperator(candidate.publicKey, CAN_ALL).rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    const finalize = program.methods
+      .finalizeOperator()
+      .accounts({ proposal: proposalPDA, proposer: authority.publicKey } as any);
+    const sim = await finalize.simulate();
+    const event = sim.events.find((e: any) => e.name === "operatorProposalFinalized");
+    expect(event!.data.endorsements).to.equal(1);
+    expect(event!.data.accepted).to.equal(false);
+    await finalize.rpc();
+    expect(await provider.connection.getAccountInfo(proposalPDA)).to.be.null;
+
+    await program.methods.removeOperator(candidate.publicKey).rpc();
+    await program.methods.setProposalConfig(new anchor.BN(86_400), 200).rpc();
+  });
+
+  it("promotes a qualifying community profile to operator", async () => {
+    const predictor = Keypair.generate();
+    const profilePDA = findPDA([USER_PROFILE_SEED, predictor.publicKey.toBuffer()]);
+    const sig = await provider.connection.requestAirdrop(predictor.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("promotable")
+      .accounts({ user: predictor.publicKey, usernameClaim: claimPDA("promotable") } as any)
+      .signers([predictor])
+      .rpc();
+
+    // A fresh profile has 100 reputation and no predictions
+    await program.methods.setOperatorPromotion(100, 1).rpc();
+    try {
+      await program.methods
+        .nominateOperatorFromProfile()
+        .accounts({ profile: profilePDA } as any)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ProfileNotQualified");
+    }
+
+    await program.methods.setOperatorPromotion(100, 0).rpc();
+    const sim = await program.methods
+      .nominateOperatorFromProfile()
+      .accounts({ profile: profilePDA } as any)
+      .simulate();
+    const promoted = sim.events.find((e: any) => e.name === "operatorPromoted");
+    expect(promoted!.data.profileOwner.equals(predictor.publicKey)).to.equal(true);
+    expect(promoted!.data.reputation).to.equal(100);
+    await program.methods
+      .nominateOperatorFromProfile()
+      .accounts({ profile: profilePDA } as any)
+      .rpc();
+
+    const sentinel = await program.account.sentinel.fetch(findPDA([SENTINEL_SEED]));
+    const entry = sentinel.operators.find((o: any) => o.key.equals(predictor.publicKey));
+    expect(entry!.perms).to.equal(CAN_ALL);
+
+    await program.methods.removeOperator(predictor.publicKey).rpc();
+    await program.methods.setOperatorPromotion(400, 50).rpc();
+  });
+
+  it("lets a reputable user dispute a reading", async () => {
+    const disputer = Keypair.generate();
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from("DISP")]);
+    const disputePDA = findPDA([DISPUTE_SEED, Buffer.from("DISP")]);
+    const profilePDA = findPDA([USER_PROFILE_SEED, disputer.publicKey.toBuffer()]);
+    const sig = await provider.connection.requestAirdrop(disputer.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("disputer")
+      .accounts({ user: disputer.publicKey, usernameClaim: claimPDA("disputer") } as any)
+      .signers([disputer])
+      .rpc();
+    const ts = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment("DISP", 90, 80, 100, new anchor.BN(ts), "").rpc();
+    const open = () =>
+      program.methods
+        .openDispute("DISP", 1)
+        .accounts({ user: disputer.publicKey } as any)
+        .signers([disputer])
+        .rpc();
+    const resolve = (upheld: boolean) =>
+      program.methods
+        .resolveDispute(upheld)
+        .accounts({ sentiment: sentimentPDA, dispute: disputePDA, profile: profilePDA } as any)
+        .rpc();
+
+    // A fresh profile has 100 reputation
+    await program.methods.setMinDisputeRep(101).rpc();
+    try {
+      await open();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("DisputeReputationTooLow");
+    }
+
+    await program.methods.setMinDisputeRep(100).rpc();
+    await open();
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).disputed).to.equal(true);
+    const dispute = await program.account.dispute.fetch(disputePDA);
+    expect(dispute.reasonCode).to.equal(1);
+    expect(dispute.disputedScore).to.equal(90);
+    try {
+      await open();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("DisputeAlreadyOpen");
+    }
+
+    await resolve(true);
+    expect((await program.account.sentimentRecord.fetch(sentimentPDA)).disputed).to.equal(false);
+    expect((await program.account.userProfile.fetch(profilePDA)).reputation).to.equal(110);
+
+    await open();
+    await resolve(false);
+    expect((await program.account.userProfile.fetch(profilePDA)).reputation).to.equal(100);
+    try {
+      await resolve(false);
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("DisputeNotOpen");
+    }
+    await program.methods.setMinDisputeRep(200).rpc();
+  });
+
+  it("merges two wallets' votes on the same symbol", async () => {
+    const keeper = Keypair.generate();
+    const source = Keypair.generate();
+    for (const [i, kp] of [keeper, source].entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`merger${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`merger${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    await program.methods
+      .voteSentiment("MERGE", 40, 100)
+      .accounts({ user: keeper.publicKey } as any)
+      .signers([keeper])
+      .rpc();
+    await program.methods
+      .voteSentiment("MERGE", -20, 50)
+      .accounts({ user: source.publicKey } as any)
+      .signers([source])
+      .rpc();
+
+    await program.methods
+      .mergeVotes("MERGE", source.publicKey)
+      .accounts({ user: keeper.publicKey, sourceWallet: source.publicKey } as any)
+      .signers([keeper, source])
+      .rpc();
+
+    const vote = await program.account.communityVote.fetch(
+      findPDA([VOTE_SEED, keeper.publicKey.toBuffer(), Buffer.from("MERGE")])
+    );
+    expect(vote.score).to.equal(20);
+    expect(vote.confidence).to.equal(75);
+    const sourcePDA = findPDA([VOTE_SEED, source.publicKey.toBuffer(), Buffer.from("MERGE")]);
+    expect(await provider.connection.getAccountInfo(sourcePDA)).to.be.null;
+
+    const aggregate = await program.account.voteAggregate.fetch(
+      findPDA([VOTE_AGG_SEED, Buffer.from("MERGE")])
+    );
+    expect(aggregate.voteCount).to.equal(1);
+    expect(aggregate.sumScore.toNumber()).to.equal(20);
+    expect(aggregate.sumWeightedScore.toNumber()).to.equal(20 * 75);
+  });
+
+  it("changes an open vote without counting a second prediction", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("flipper")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("flipper") } as any)
+      .signers([user])
+      .rpc();
+
+    const votePDA = findPDA([VOTE_SEED, user.publicKey.toBuffer(), Buffer.from("FLIP")]);
+    const aggregatePDA = findPDA([VOTE_AGG_SEED, Buffer.from("FLIP")]);
+    await program.methods
+      .voteSentiment("FLIP", 30, 60)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const sim = await program.methods
+      .updateVote(-20, 60)
+      .accounts({ vote: votePDA, aggregate: aggregatePDA, user: user.publicKey } as any)
+      .signers([user])
+      .simulate();
+    const changed = sim.events.find((e: any) => e.name === "voteChanged");
+    expect(changed!.data.oldScore).to.equal(30);
+    expect(changed!.data.newScore).to.equal(-20);
+
+    await program.methods
+      .updateVote(-20, 60)
+      .accounts({ vote: votePDA, aggregate: aggregatePDA, user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const vote = await program.account.communityVote.fetch(votePDA);
+    expect(vote.score).to.equal(-20);
+    const profile = await program.account.userProfile.fetch(
+      findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()])
+    );
+    expect(profile.predictionsMade).to.equal(1);
+    const aggregate = await program.account.voteAggregate.fetch(aggregatePDA);
+    expect(aggregate.voteCount).to.equal(1);
+    expect(aggregate.sumScore.toNumber()).to.equal(-20);
+  });
+
+  it("weights the consensus by voter reputation up to the cap", async () => {
+    const [novice, whale] = [Keypair.generate(), Keypair.generate()];
+    for (const [i, kp] of [novice, whale].entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`weigher${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`weigher${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+
+    // 90 correct calls take the whale from 100 to the 1000 reputation ceiling
+    const whaleProfile = findPDA([USER_PROFILE_SEED, whale.publicKey.toBuffer()]);
+    const resolve = () => program.methods.judgePrediction(true).accounts({ profile: whaleProfile } as any);
+    for (const batch of [30, 31, 29]) {
+      const extra = await Promise.all([...Array(batch - 1)].map(() => resolve().instruction()));
+      await resolve().preInstructions(extra).rpc();
+    }
+    const profile = await program.account.userProfile.fetch(whaleProfile);
+    expect(profile.reputation).to.equal(1000);
+
+    await program.methods
+      .voteSentiment("WHALE", 50, 100)
+      .accounts({ user: novice.publicKey } as any)
+      .signers([novice])
+      .rpc();
+    const sim = await program.methods
+      .voteSentiment("WHALE", -50, 100)
+      .accounts({ user: whale.publicKey } as any)
+      .signers([whale])
+      .simulate();
+    const event = sim.events.find((e: any) => e.name === "consensusUpdated");
+    // Equal confidence cancels out; by reputation the whale counts 500 (the cap) to 100
+    expect(event!.data.consensusScore.toNumber()).to.equal(0);
+    expect(event!.data.weightedConsensus.toNumber()).to.equal(-3333);
+  });
+
+  it("keeps a bounded top-predictor leaderboard", async () => {
+    const leaderboardPDA = findPDA([LEADERBOARD_SEED]);
+    const init = await program.methods.initLeaderboard().simulate();
+    expect(init.events.find((e: any) => e.name === "leaderboardInitialized")).to.exist;
+    await program.methods.initLeaderboard().rpc();
+
+    const users = [...Array(11)].map(() => Keypair.generate());
+    await Promise.all(
+      users.map(async (kp) => {
+        const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+        await provider.connection.confirmTransaction(sig);
+      })
+    );
+    const profiles = users.map((kp) => findPDA([USER_PROFILE_SEED, kp.publicKey.toBuffer()]));
+    for (const [i, kp] of users.entries()) {
+      await program.methods
+        .createProfile(`ranked${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`ranked${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    const resolve = (i: number) => program.methods.judgePrediction(true).accounts({ profile: profiles[i] } as any);
+
+    // Insertion: the first ten fill the board at 110
+    for (let i = 0; i < 10; i++) {
+      await resolve(i).rpc();
+    }
+    let board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.length).to.equal(10);
+
+    // In-place update: a second win moves user 0 to the top without duplicating it
+    await resolve(0).rpc();
+    board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.length).to.equal(10);
+    expect(board.entries[0].user.toBase58()).to.equal(users[0].publicKey.toBase58());
+    expect(board.entries[0].reputation).to.equal(120);
+    const mentions = board.entries.filter((e: any) => e.user.equals(users[0].publicKey));
+    expect(mentions.length).to.equal(1);
+
+    // Matching the lowest entry isn't enough to get on
+    let sim = await resolve(10).simulate();
+    expect(sim.events.find((e: any) => e.name === "leaderboardChanged")).to.be.undefined;
+    await resolve(10).rpc();
+
+    // Displacement: beating it pushes the lowest entry off
+    const lowest = board.entries[9].user;
+    sim = await resolve(10).simulate();
+    const event = sim.events.find((e: any) => e.name === "leaderboardChanged");
+    expect(event).to.exist;
+    expect(event.data.reputation).to.equal(120);
+    expect(event.data.rank).to.be.at.most(1);
+    await resolve(10).rpc();
+    board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.length).to.equal(10);
+    expect(board.entries.some((e: any) => e.user.equals(users[10].publicKey))).to.be.true;
+    expect(board.entries.some((e: any) => e.user.equals(lowest))).to.be.false;
+
+    // Random tiebreak: refreshes must pass the SlotHashes sysvar for entropy
+    await program.methods.setRandomTiebreak(true).rpc();
+    try {
+      await resolve(1).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("EntropyRequired");
+    }
+    await program.methods
+      .judgePrediction(true)
+      .accounts({ profile: profiles[1], slotHashes: SYSVAR_SLOT_HASHES_PUBKEY } as any)
+      .rpc();
+    board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.some((e: any) => e.user.equals(users[1].publicKey) && e.reputation === 120)).to.be.true;
+    await program.methods.setRandomTiebreak(false).rpc();
+
+    // Closing a ranked profile takes it off the board
+    const close = program.methods
+      .closeProfile()
+      .accounts({ user: users[0].publicKey, usernameClaim: claimPDA("ranked0"), tombstone: null } as any)
+      .signers([users[0]]);
+    sim = await close.simulate();
+    expect(sim.events.find((e: any) => e.name === "leaderboardEvicted")).to.exist;
+    await close.rpc();
+    board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.some((e: any) => e.user.equals(users[0].publicKey))).to.be.false;
+
+    // Transferring one moves its entry to the new wallet
+    const heir = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(heir.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .transferProfile()
+      .accounts({ user: users[1].publicKey, newOwner: heir.publicKey, usernameClaim: claimPDA("ranked1") } as any)
+      .signers([users[1], heir])
+      .rpc();
+    board = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(board.entries.some((e: any) => e.user.equals(users[1].publicKey))).to.be.false;
+    expect(board.entries.some((e: any) => e.user.equals(heir.publicKey))).to.be.true;
+  });
+
+  it("promotes a profile through the reputation tiers", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("climber")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("climber") } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .voteSentiment("TIER", 20, 60)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+    await program.methods.setMinPredictionsForExpert(1).rpc();
+
+    const profilePDA = findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()]);
+    const resolve = () => program.methods.judgePrediction(true).accounts({ profile: profilePDA } as any);
+    // Resolve `n` correct predictions (+10 each); report the last reputation and tier
+    const resolveMany = async (n: number) => {
+      const extra = await Promise.all([...Array(n - 1)].map(() => resolve().instruction()));
+      const sim = await resolve().preInstructions(extra).simulate();
+      await resolve().preInstructions(extra).rpc();
+      const last = sim.events.filter((e: any) => e.name === "predictionResolved").pop();
+      return [last.data.newReputation, Object.keys(last.data.tier)[0]];
+    };
+
+    expect(await resolveMany(9)).to.deep.equal([190, "novice"]);
+    expect(await resolveMany(1)).to.deep.equal([200, "skilled"]);
+    expect(await resolveMany(19)).to.deep.equal([390, "skilled"]);
+
+    // Expert reputation, but too few predictions made to count
+    await program.methods.setMinPredictionsForExpert(2).rpc();
+    const sim = await resolve().simulate();
+    const event = sim.events.find((e: any) => e.name === "predictionResolved");
+    expect(event.data.newReputation).to.equal(400);
+    expect(event.data.tier).to.have.property("skilled");
+    await program.methods.setMinPredictionsForExpert(1).rpc();
+
+    expect(await resolveMany(1)).to.deep.equal([400, "expert"]);
+    expect(await resolveMany(20)).to.deep.equal([600, "expert"]);
+    expect(await resolveMany(19)).to.deep.equal([790, "expert"]);
+    expect(await resolveMany(1)).to.deep.equal([800, "oracle"]);
+
+    await program.methods.setMinPredictionsForExpert(20).rpc();
+  });
+
+  it("expires an unresolved prediction past the horizon", async () => {
+    const voter = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(voter.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const profilePDA = findPDA([USER_PROFILE_SEED, voter.publicKey.toBuffer()]);
+    const votePDA = findPDA([VOTE_SEED, voter.publicKey.toBuffer(), Buffer.from("SOL")]);
+
+    await program.methods
+      .createProfile("sprayer")
+      .accounts({ user: voter.publicKey, usernameClaim: claimPDA("sprayer") } as any)
+      .signers([voter])
+      .rpc();
+    await program.methods
+      .voteSentiment("SOL", -40, 90)
+      .accounts({ user: voter.publicKey } as any)
+      .signers([voter])
+      .rpc();
+
+    await program.methods.setPredictionExpiry(new anchor.BN(1), 5).rpc();
+    await new Promise((r) => setTimeout(r, 2500));
+
+    await program.methods
+      .expirePrediction(voter.publicKey, "SOL")
+      .accounts({ voterWallet: voter.publicKey } as any)
+      .rpc();
+
+    const profile = await program.account.userProfile.fetch(profilePDA);
+    expect(profile.reputation).to.equal(95);
+    expect(profile.openPredictions).to.equal(0);
+    expect(await provider.connection.getAccountInfo(votePDA)).to.be.null;
+
+    await program.methods.setPredictionExpiry(new anchor.BN(0), 0).rpc();
+  });
+
+  it("refuses to close a profile with open predictions", async () => {
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    const profilePDA = findPDA([USER_PROFILE_SEED, user.publicKey.toBuffer()]);
+
+    await program.methods
+      .createProfile("closer")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("closer") } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .voteSentiment("SOL", 10, 50)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .closeProfile()
+        .accounts({ user: user.publicKey, usernameClaim: claimPDA("closer"), tombstone: null } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ProfileHasActivity");
+    }
+
+    await program.methods
+      .judgePrediction(true)
+      .accounts({ profile: profilePDA } as any)
+      .rpc();
+    await program.methods
+      .closeProfile()
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("closer"), tombstone: null } as any)
+      .signers([user])
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(profilePDA);
+    expect(info).to.be.null;
+  });
+
+  it("closes a profile created before usernames were claimed", async () => {
+    // Matches the profile fixture loaded from tests/fixtures/legacy_profile.json
+    const legacy = Keypair.fromSeed(Buffer.from("legacy-profile-owner-fixture-001"));
+    const profilePDA = findPDA([USER_PROFILE_SEED, legacy.publicKey.toBuffer()]);
+    expect((await program.account.userProfile.fetch(profilePDA)).username).to.equal("oldtimer");
+    expect(await provider.connection.getAccountInfo(claimPDA("oldtimer"))).to.be.null;
+    const sig = await provider.connection.requestAirdrop(legacy.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .closeProfile()
+      .accounts({ user: legacy.publicKey, usernameClaim: claimPDA("oldtimer"), tombstone: null } as any)
+      .signers([legacy])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(profilePDA)).to.be.null;
+
+    // The name was never reserved, so it is free to claim
+    const user = Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(airdrop);
+    await program.methods
+      .createProfile("OldTimer")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("OldTimer") } as any)
+      .signers([user])
+      .rpc();
+    const claim = await program.account.usernameClaim.fetch(claimPDA("oldtimer"));
+    expect(claim.owner.toBase58()).to.equal(user.publicKey.toBase58());
+  });
+
+  it("closes profiles around the follow graph", async () => {
+    const [fan, star] = [Keypair.generate(), Keypair.generate()];
+    for (const [i, kp] of [fan, star].entries()) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .createProfile(`leaver${i}`)
+        .accounts({ user: kp.publicKey, usernameClaim: claimPDA(`leaver${i}`) } as any)
+        .signers([kp])
+        .rpc();
+    }
+    await program.methods
+      .followUser(star.publicKey)
+      .accounts({ user: fan.publicKey } as any)
+      .signers([fan])
+      .rpc();
+
+    // Being followed doesn't block closing
+    const close = program.methods
+      .closeProfile()
+      .accounts({ user: star.publicKey, usernameClaim: claimPDA("leaver1"), tombstone: null } as any)
+      .signers([star]);
+    const sim = await close.simulate();
+    const event = sim.events.find((e: any) => e.name === "profileClosed");
+    expect(event.data.username).to.equal("leaver1");
+    await close.rpc();
+
+    // Nor does following someone
+    await program.methods
+      .closeProfile()
+      .accounts({ user: fan.publicKey, usernameClaim: claimPDA("leaver0"), tombstone: null } as any)
+      .signers([fan])
+      .rpc();
+    const info = await provider.connection.getAccountInfo(findPDA([USER_PROFILE_SEED, fan.publicKey.toBuffer()]));
+    expect(info).to.be.null;
+
+    // With both profiles gone the follower can still reclaim the edge's rent
+    const followPDA = findPDA([FOLLOW_SEED, fan.publicKey.toBuffer(), star.publicKey.toBuffer()]);
+    await program.methods
+      .unfollowUser(star.publicKey)
+      .accounts({ user: fan.publicKey, profile: null, followeeProfile: null } as any)
+      .signers([fan])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(followPDA)).to.be.null;
+  });
+
+  // ===== Close Sentiment =====
+
+  it("closes a sentiment record (admin)", async () => {
+    const symbol = "TEMP";
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(symbol)]);
     const ts = Math.floor(Date.now() / 1000);
 
     await program.methods
-      .storeSentiment(symbol, 10, 50, 100, new anchor.BN(ts))
+      .storeSentiment(symbol, 10, 50, 100, new anchor.BN(ts), "")
       .rpc();
 
     await program.methods
@@ -293,6 +4260,76 @@ describe("sol_sentinel", () => {
     expect(info).to.be.null;
   });
 
+  it("closes a dead symbol's history and vote aggregate (admin)", async () => {
+    const symbol = "DEAD";
+    const historyPDA = findPDA([HISTORY_SEED, Buffer.from(symbol)]);
+    const aggregatePDA = findPDA([VOTE_AGG_SEED, Buffer.from(symbol)]);
+    const user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .storeSentiment(symbol, 10, 50, 100, new anchor.BN(Math.floor(Date.now() / 1000)), "")
+      .rpc();
+    await program.methods
+      .recordHistory(symbol, 24)
+      .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from(symbol)]), history: historyPDA } as any)
+      .rpc();
+    await program.methods
+      .createProfile("mourner")
+      .accounts({ user: user.publicKey, usernameClaim: claimPDA("mourner") } as any)
+      .signers([user])
+      .rpc();
+    await program.methods
+      .voteSentiment(symbol, 10, 50)
+      .accounts({ user: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    // The aggregate can't go while it still counts the vote
+    try {
+      await program.methods.closeVoteAggregate(symbol).accounts({ aggregate: aggregatePDA } as any).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("VoteAggregateNotEmpty");
+    }
+    await program.methods.setPredictionExpiry(new anchor.BN(1), 5).rpc();
+    await new Promise((r) => setTimeout(r, 2500));
+    await program.methods
+      .expirePrediction(user.publicKey, symbol)
+      .accounts({ voterWallet: user.publicKey } as any)
+      .rpc();
+    await program.methods.setPredictionExpiry(new anchor.BN(0), 0).rpc();
+    expect((await program.account.voteAggregate.fetch(aggregatePDA)).voteCount).to.equal(0);
+
+    for (const [pda, close] of [
+      [historyPDA, () => program.methods.closeHistory(symbol).accounts({ history: historyPDA } as any)],
+      [aggregatePDA, () => program.methods.closeVoteAggregate(symbol).accounts({ aggregate: aggregatePDA } as any)],
+    ] as const) {
+      const rent = (await provider.connection.getAccountInfo(pda))!.lamports;
+      const before = await provider.connection.getBalance(authority.publicKey);
+      await close().rpc();
+      const after = await provider.connection.getBalance(authority.publicKey);
+      // Rent comes back less the transaction fee
+      expect(after - before).to.be.closeTo(rent, 10_000);
+      expect(await provider.connection.getAccountInfo(pda)).to.be.null;
+    }
+    expect(await program.account.sentimentHistory.fetchNullable(historyPDA)).to.be.null;
+    expect(await program.account.voteAggregate.fetchNullable(aggregatePDA)).to.be.null;
+
+    // Only the admin can close them
+    try {
+      await program.methods
+        .closeHistory("SOL")
+        .accounts({ history: findPDA([HISTORY_SEED, Buffer.from("SOL")]), authority: user.publicKey } as any)
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Unauthorized");
+    }
+  });
+
   // ===== Multi-token =====
 
   it("stores sentiment for multiple tokens", async () => {
@@ -305,7 +4342,7 @@ describe("sol_sentinel", () => {
     for (const t of tokens) {
       const ts = Math.floor(Date.now() / 1000);
       await program.methods
-        .storeSentiment(t.symbol, t.score, t.confidence, t.volume, new anchor.BN(ts))
+        .storeSentiment(t.symbol, t.score, t.confidence, t.volume, new anchor.BN(ts), "")
         .rpc();
 
       const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from(t.symbol)]);
@@ -321,4 +4358,83 @@ describe("sol_sentinel", () => {
     expect(sentinel.totalUpdates.toNumber()).to.be.greaterThan(0);
     console.log(`Total updates: ${sentinel.totalUpdates.toNumber()}`);
   });
+
+  it("reports aggregate stats in one call", async () => {
+    const sentinelPDA = findPDA([SENTINEL_SEED]);
+    const sentinel = await program.account.sentinel.fetch(sentinelPDA);
+    const stats = await program.methods.getStats().view();
+    expect(stats.totalUpdates.toNumber()).to.equal(sentinel.totalUpdates.toNumber());
+    expect(stats.operatorCount).to.equal(sentinel.operators.length);
+    expect(stats.paused).to.equal(false);
+    expect(stats.uptimeSeconds.toNumber()).to.be.at.least(0);
+
+    const ts = Math.floor(Date.now() / 1000);
+    await program.methods.storeSentiment("STATS", 5, 50, 100, new anchor.BN(ts), "").rpc();
+    await program.methods
+      .updateSentiment(6, 50, 100, new anchor.BN(ts + 1), null, "")
+      .accounts({ sentiment: findPDA([SENTIMENT_SEED, Buffer.from("STATS")]) } as any)
+      .rpc();
+    const after = await program.methods.getStats().view();
+    expect(after.totalUpdates.toNumber()).to.equal(stats.totalUpdates.toNumber() + 2);
+    expect(after.uptimeSeconds.toNumber()).to.be.at.least(stats.uptimeSeconds.toNumber());
+  });
+
+  it("numbers events gaplessly across instructions", async () => {
+    const sentimentPDA = findPDA([SENTIMENT_SEED, Buffer.from("SEQ")]);
+    const ts = Math.floor(Date.now() / 1000);
+    // Simulate first to read the seqs the transaction will emit, then land it
+    const seqsOf = async (builder: any): Promise<number[]> => {
+      const sim = await builder.simulate();
+      await builder.rpc();
+      return sim.events
+        .filter((e: any) => e.name === "eventSequenced")
+        .map((e: any) => e.data.seq.toNumber());
+    };
+
+    // A proposal to review, so the sequence covers the review path as well
+    const candidate = Keypair.generate();
+    const reviewer = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(reviewer.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .createProfile("seqreviewer")
+      .accounts({ user: reviewer.publicKey, usernameClaim: claimPDA("seqreviewer") } as any)
+      .signers([reviewer])
+      .rpc();
+    await program.methods.setProposalConfig(new anchor.BN(86_400), 100).rpc();
+    await program.methods.proposeOperator(candidate.publicKey).rpc();
+
+    const start = (await program.methods.getStats().view()).eventSeq.toNumber();
+    const seqs = [
+      ...(await seqsOf(program.methods.storeSentiment("SEQ", 5, 50, 100, new anchor.BN(ts), ""))),
+      ...(await seqsOf(program.methods.setMaxScoreDelta(0))),
+      ...(await seqsOf(
+        program.methods
+          .updateSentiment(6, 50, 100, new anchor.BN(ts + 1), null, "")
+          .accounts({ sentiment: sentimentPDA } as any)
+      )),
+      ...(await seqsOf(
+        program.methods
+          .endorseOperator()
+          .accounts({
+            proposal: findPDA([OPERATOR_PROPOSAL_SEED, candidate.publicKey.toBuffer()]),
+            user: reviewer.publicKey,
+          } as any)
+          .signers([reviewer])
+      )),
+      ...(await seqsOf(
+        program.methods.batchRecordHistory(["SEQ"]).remainingAccounts([
+          { pubkey: sentimentPDA, isSigner: false, isWritable: false },
+          { pubkey: findPDA([HISTORY_SEED, Buffer.from("SEQ")]), isSigner: false, isWritable: true },
+        ])
+      )),
+    ];
+    expect(seqs.length).to.be.at.least(5);
+    seqs.forEach((seq, i) => expect(seq).to.equal(start + i + 1));
+
+    const sentinel = await program.account.sentinel.fetch(findPDA([SENTINEL_SEED]));
+    expect(sentinel.eventSeq.toNumber()).to.equal(start + seqs.length);
+
+    await program.methods.setProposalConfig(new anchor.BN(86_400), 200).rpc();
+  });
 });